@@ -0,0 +1,276 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_quote, Attribute, Error, Expr, ExprPath, LitByteStr, LitInt, LitStr, Member, Meta, Token,
+    Type, WherePredicate,
+};
+
+/// The byte order named by the string after `endian`.
+fn parse_endian(meta: &ParseNestedMeta) -> syn::Result<TokenStream2> {
+    let lit = meta.value()?.parse::<LitStr>()?;
+    match lit.value().as_str() {
+        "big" => Ok(quote!(::byteorder_pack::byteorder::BigEndian)),
+        "little" => Ok(quote!(::byteorder_pack::byteorder::LittleEndian)),
+        "native" => Ok(quote!(::byteorder_pack::byteorder::NativeEndian)),
+        _ => Err(Error::new(
+            lit.span(),
+            "expected \"big\", \"little\" or \"native\"",
+        )),
+    }
+}
+
+/// Integer types accepted in `#[repr(...)]` as an enum's tag type.
+const REPR_INTS: [&str; 8] = ["u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64"];
+
+/// Options from `#[pack(...)]` on the struct or enum itself.
+#[derive(Default)]
+pub struct ContainerAttrs {
+    /// Integer type of an enum's tag, from `tag = T` or else `#[repr(T)]`.
+    pub tag: Option<Type>,
+    /// Byte order of the inherent `pack` and `unpack` methods to generate,
+    /// from `endian = "..."`.
+    pub endian: Option<TokenStream2>,
+    /// Bytes packed before everything else and checked when unpacking, from
+    /// `magic = b"..."`.
+    pub magic: Option<LitByteStr>,
+    /// Whether an enum is packed without a tag and unpacked by trying each
+    /// variant in turn, from `untagged`.
+    pub untagged: bool,
+    /// Where predicates replacing the inferred bounds on type parameters,
+    /// from `bound = "..."`.
+    pub bound: Option<Punctuated<WherePredicate, Token![,]>>,
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+        let mut repr = None;
+        for attr in attrs {
+            if attr.path().is_ident("pack") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("tag") {
+                        if out.untagged {
+                            return Err(meta.error("`tag` and `untagged` are exclusive"));
+                        }
+                        out.tag = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else if meta.path.is_ident("endian") {
+                        out.endian = Some(parse_endian(&meta)?);
+                        Ok(())
+                    } else if meta.path.is_ident("untagged") {
+                        if out.tag.is_some() {
+                            return Err(meta.error("`tag` and `untagged` are exclusive"));
+                        }
+                        out.untagged = true;
+                        Ok(())
+                    } else if meta.path.is_ident("magic") {
+                        out.magic = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else if meta.path.is_ident("bound") {
+                        let lit = meta.value()?.parse::<LitStr>()?;
+                        out.bound = Some(lit.parse_with(Punctuated::parse_terminated)?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("unknown `pack` option"))
+                    }
+                })?;
+            } else if attr.path().is_ident("repr") {
+                let metas =
+                    attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+                for meta in metas {
+                    if let Meta::Path(path) = meta {
+                        if REPR_INTS.iter().any(|int| path.is_ident(int)) {
+                            repr = Some(parse_quote!(#path));
+                        }
+                    }
+                }
+            }
+        }
+        if !out.untagged {
+            out.tag = out.tag.or(repr);
+        }
+        Ok(out)
+    }
+}
+
+/// Options from `#[pack(...)]` on an enum variant.
+#[derive(Default)]
+pub struct VariantAttrs {
+    /// The variant's tag, from `tag = expr`.
+    pub tag: Option<Expr>,
+    /// Whether the variant catches unknown tags, from `other`.
+    pub other: bool,
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("pack")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    out.tag = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("other") {
+                    out.other = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `pack` option"))
+                }
+            })?;
+        }
+        Ok(out)
+    }
+}
+
+/// Options from `#[pack(...)]` on a field.
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// Byte order overriding the requested one, from `endian = "..."`.
+    pub endian: Option<TokenStream2>,
+    /// Whether the field is left out of the packed form, from `skip`.
+    pub skip: bool,
+    /// Value of a skipped field when unpacking, from `default = "path"` as a
+    /// call to `path`. `Default::default()` if not given.
+    pub default: Option<ExprPath>,
+    /// Alignment of the field's offset within the struct, from `align = N`.
+    pub align: Option<u64>,
+    /// Padding bytes following the field, from `pad_after = N`.
+    pub pad_after: Option<u64>,
+    /// Integer type of the element count packed before a `Vec` or `String`
+    /// field, from `len_prefix = T`.
+    pub len_prefix: Option<Type>,
+    /// Condition the field must meet once unpacked, from `assert = "expr"`,
+    /// with the source text for the error message.
+    pub assert: Option<(Expr, String)>,
+    /// Element count of a `Vec` or `String` field, given by an expression
+    /// over earlier fields, from `count = "expr"`.
+    pub count: Option<Expr>,
+    /// Name of a later field whose packed size the field holds, from
+    /// `len_of = "field"`.
+    pub len_of: Option<LitStr>,
+    /// Later `Option` fields whose presence the field holds as a bitmap, the
+    /// first in the least significant bit, from `presence_of = "a, b"`.
+    pub presence_of: Option<Punctuated<Member, Token![,]>>,
+    /// Condition over earlier fields under which an `Option` field is
+    /// present, from `if = "expr"`.
+    pub cond: Option<Expr>,
+    /// Conversion from the wire value when unpacking, from `map = "f"`.
+    pub map: Option<Expr>,
+    /// Fallible conversion from the wire value when unpacking, from
+    /// `try_map = "f"`.
+    pub try_map: Option<Expr>,
+    /// Conversion from a reference to the field to the wire value when
+    /// packing, from `unmap = "g"`.
+    pub unmap: Option<Expr>,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("pack")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("endian") {
+                    out.endian = Some(parse_endian(&meta)?);
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    out.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("align") {
+                    let lit = meta.value()?.parse::<LitInt>()?;
+                    match lit.base10_parse()? {
+                        0 => Err(Error::new(lit.span(), "alignment must be at least 1")),
+                        align => {
+                            out.align = Some(align);
+                            Ok(())
+                        }
+                    }
+                } else if meta.path.is_ident("assert") {
+                    let lit = meta.value()?.parse::<LitStr>()?;
+                    out.assert = Some((lit.parse()?, lit.value()));
+                    Ok(())
+                } else if meta.path.is_ident("count") {
+                    out.count = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("len_of") {
+                    out.len_of = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("presence_of") {
+                    let lit = meta.value()?.parse::<LitStr>()?;
+                    out.presence_of = Some(lit.parse_with(Punctuated::parse_terminated)?);
+                    Ok(())
+                } else if meta.path.is_ident("if") {
+                    out.cond = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("map") {
+                    out.map = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("try_map") {
+                    out.try_map = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("unmap") {
+                    out.unmap = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("len_prefix") {
+                    out.len_prefix = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("pad_after") {
+                    out.pad_after = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    // A bare `default` just spells out the default.
+                    if meta.input.peek(Token![=]) {
+                        out.default = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    }
+                    if !out.skip {
+                        return Err(meta.error("`default` only applies to `skip` fields"));
+                    }
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown `pack` option"))
+                }
+            })?;
+            if out.map.is_some() && out.try_map.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "`map` and `try_map` are exclusive",
+                ));
+            }
+            if out.skip && out.cond.is_some() {
+                return Err(Error::new_spanned(attr, "`skip` and `if` are exclusive"));
+            }
+            let mapped = out.map.is_some() || out.try_map.is_some() || out.unmap.is_some();
+            let special = mapped
+                || out.skip
+                || out.len_prefix.is_some()
+                || out.count.is_some()
+                || out.cond.is_some();
+            if out.len_of.is_some() && special {
+                return Err(Error::new_spanned(
+                    attr,
+                    "`len_of` cannot be combined with `skip`, `if`, `len_prefix`, `count` or mapping",
+                ));
+            }
+            if out.presence_of.is_some() && (special || out.len_of.is_some()) {
+                return Err(Error::new_spanned(
+                    attr,
+                    "`presence_of` cannot be combined with `skip`, `if`, `len_prefix`, `count`, `len_of` or mapping",
+                ));
+            }
+            if out.count.is_some() && (mapped || out.skip || out.len_prefix.is_some()) {
+                return Err(Error::new_spanned(
+                    attr,
+                    "`count` cannot be combined with `skip`, `len_prefix` or mapping",
+                ));
+            }
+            if mapped && out.len_prefix.is_some() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "`len_prefix` cannot be combined with `map`, `try_map` or `unmap`",
+                ));
+            }
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,135 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, Error, Fields};
+
+use crate::input::{Field, Input, Shape};
+
+pub fn expand(input: &Input) -> TokenStream2 {
+    let magic = input.attrs.magic.as_ref().map(|magic| {
+        let len = magic.value().len();
+        quote!(__schema.field("magic", ::byteorder_pack::Schema::Bytes(#len));)
+    });
+    let body = match &input.shape {
+        Shape::Struct(fields) => {
+            if let Some(f) = fields.iter().find(|f| f.attrs.len_prefix.is_some()) {
+                return Error::new_spanned(
+                    &f.ty,
+                    "length-prefixed fields have no fixed layout to describe",
+                )
+                .to_compile_error();
+            }
+            if let Some(f) = fields.iter().find(|f| f.attrs.count.is_some()) {
+                return Error::new_spanned(
+                    &f.ty,
+                    "counted fields have no fixed layout to describe",
+                )
+                .to_compile_error();
+            }
+            if let Some(f) = fields.iter().find(|f| f.attrs.cond.is_some()) {
+                return Error::new_spanned(
+                    &f.ty,
+                    "conditional fields have no fixed layout to describe",
+                )
+                .to_compile_error();
+            }
+            if let Some(f) = fields
+                .iter()
+                .find(|f| !f.attrs.skip && f.attrs.endian.is_some())
+            {
+                return Error::new_spanned(
+                    &f.ty,
+                    "fields with their own byte order cannot be described, since a schema \
+                     has one byte order throughout",
+                )
+                .to_compile_error();
+            }
+            let mapped = |f: &&Field| f.attrs.map.is_some() || f.attrs.try_map.is_some();
+            if let Some(f) = fields.iter().find(mapped) {
+                return Error::new_spanned(
+                    &f.ty,
+                    "mapped fields have no known wire type to describe",
+                )
+                .to_compile_error();
+            }
+            // Newtypes are described as the type they wrap.
+            let newtype = match (&input.ast.data, &fields[..]) {
+                (Data::Struct(data), [f]) => {
+                    matches!(data.fields, Fields::Unnamed(_))
+                        && magic.is_none()
+                        && !f.attrs.skip
+                        && f.attrs.align.is_none()
+                        && f.attrs.pad_after.is_none()
+                }
+                _ => false,
+            };
+            if newtype {
+                let ty = &fields[0].ty;
+                return expand_impl(input, quote!(<#ty as ::byteorder_pack::Describe>::schema()));
+            }
+            let stmts = fields.iter().filter(|f| !f.attrs.skip).map(|f| {
+                let name = &f.name;
+                let ty = &f.ty;
+                let align = f.align().map(|align| quote!(__schema.align(#align);));
+                let pad = f.pad_after().map(|n| quote!(__schema.pad(#n);));
+                quote! {
+                    #align
+                    __schema.field(#name, <#ty as ::byteorder_pack::Describe>::schema());
+                    #pad
+                }
+            });
+            quote! {
+                let mut __schema = ::byteorder_pack::__private::StructSchema::default();
+                #magic
+                #(#stmts)*
+                __schema.finish()
+            }
+        }
+        // A schema has a fixed layout, so only fieldless enums, which are
+        // just their tag and magic, can be described.
+        Shape::Enum { tag: None, .. } => {
+            return Error::new_spanned(
+                &input.ast.ident,
+                "untagged enums have no fixed layout to describe",
+            )
+            .to_compile_error()
+        }
+        Shape::Enum {
+            tag: Some(tag),
+            variants,
+        } => match variants
+            .iter()
+            .find(|v| v.fields.iter().any(|f| !f.attrs.skip))
+        {
+            None if magic.is_none() => quote!(<#tag as ::byteorder_pack::Describe>::schema()),
+            None => quote! {
+                let mut __schema = ::byteorder_pack::__private::StructSchema::default();
+                #magic
+                __schema.field("tag", <#tag as ::byteorder_pack::Describe>::schema());
+                __schema.finish()
+            },
+            Some(v) => {
+                return Error::new_spanned(
+                    &v.ident,
+                    "enums with fields have no fixed layout to describe",
+                )
+                .to_compile_error()
+            }
+        },
+    };
+
+    expand_impl(input, body)
+}
+
+/// The `Describe` impl returning `body` as the schema.
+fn expand_impl(input: &Input, body: TokenStream2) -> TokenStream2 {
+    let name = &input.ast.ident;
+    let generics = input.generics(quote!(::byteorder_pack::Describe));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::byteorder_pack::Describe for #name #ty_generics #where_clause {
+            fn schema() -> ::byteorder_pack::Schema {
+                #body
+            }
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use proc_macro2::{Group, Literal, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Expr, Fields, GenericArgument, Generics, Ident, Index,
+    Member, PathArguments, Type,
+};
+
+use crate::attr::{ContainerAttrs, FieldAttrs, VariantAttrs};
+
+/// One field of a struct or enum variant, in declaration order.
+pub struct Field {
+    pub member: Member,
+    /// The field's name, or its index in a tuple variant.
+    pub name: String,
+    /// The type packed for the field: its declared type, or `T` for an
+    /// `Option<T>` field with an `if` condition or a presence bit.
+    pub ty: Type,
+    pub attrs: FieldAttrs,
+    /// The bit of an earlier field's `presence_of` bitmap that is set when
+    /// the field is present. Such fields also get the matching `if`
+    /// condition.
+    pub present_bit: Option<u32>,
+}
+
+impl Field {
+    /// The byte order to pack the field with.
+    pub fn order(&self) -> TokenStream2 {
+        self.attrs.endian.clone().unwrap_or_else(|| quote!(__E))
+    }
+
+    /// The field's alignment, if any, as an unsuffixed literal.
+    pub fn align(&self) -> Option<Literal> {
+        self.attrs.align.map(Literal::u64_unsuffixed)
+    }
+
+    /// The number of padding bytes after the field, if any, as an unsuffixed
+    /// literal.
+    pub fn pad_after(&self) -> Option<Literal> {
+        self.attrs.pad_after.map(Literal::u64_unsuffixed)
+    }
+
+    /// The field's `if` condition, if any, with each `self.field` for which
+    /// `replace` returns tokens replaced by them.
+    pub fn cond(&self, replace: impl Fn(&Member) -> Option<TokenStream2>) -> Option<TokenStream2> {
+        let cond = self.attrs.cond.as_ref()?;
+        Some(replace_self(cond.to_token_stream(), &replace))
+    }
+}
+
+/// `tokens` with each `self.field` for which `replace` returns tokens
+/// replaced by them.
+fn replace_self(
+    tokens: TokenStream2,
+    replace: &dyn Fn(&Member) -> Option<TokenStream2>,
+) -> TokenStream2 {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut out = TokenStream2::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if let [TokenTree::Ident(this), TokenTree::Punct(dot), field, ..] = &tokens[i..] {
+            let member = match field {
+                TokenTree::Ident(ident) => Some(Member::Named(ident.clone())),
+                TokenTree::Literal(lit) => syn::parse2::<Index>(lit.to_token_stream())
+                    .ok()
+                    .map(Member::Unnamed),
+                _ => None,
+            };
+            if this == "self" && dot.as_char() == '.' {
+                if let Some(replacement) = member.as_ref().and_then(replace) {
+                    out.extend(replacement);
+                    i += 3;
+                    continue;
+                }
+            }
+        }
+        out.extend([match &tokens[i] {
+            TokenTree::Group(g) => {
+                let mut group = Group::new(g.delimiter(), replace_self(g.stream(), replace));
+                group.set_span(g.span());
+                TokenTree::Group(group)
+            }
+            tt => tt.clone(),
+        }]);
+        i += 1;
+    }
+    out
+}
+
+/// The `T` of `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else { return None };
+    let last = path.path.segments.last()?;
+    match &last.arguments {
+        PathArguments::AngleBracketed(args) if last.ident == "Option" && args.args.len() == 1 => {
+            match &args.args[0] {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Whether `tokens` mention `ident` anywhere.
+fn mentions(tokens: TokenStream2, ident: &Ident) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(i) => i == *ident,
+        TokenTree::Group(g) => mentions(g.stream(), ident),
+        _ => false,
+    })
+}
+
+/// One variant of an enum.
+pub struct Variant {
+    pub ident: Ident,
+    /// The variant's tag from `#[pack(tag = ...)]` or its discriminant, if
+    /// given. Otherwise it is one more than the previous variant's, or 0.
+    pub tag: Option<Expr>,
+    /// Whether the variant catches unknown tags, which are kept in its first
+    /// field.
+    pub other: bool,
+    pub fields: Vec<Field>,
+}
+
+pub enum Shape {
+    Struct(Vec<Field>),
+    Enum {
+        /// The tag type, or `None` for an untagged enum.
+        tag: Option<Box<Type>>,
+        variants: Vec<Variant>,
+    },
+}
+
+/// The parsed item a derive is applied to.
+pub struct Input<'a> {
+    pub ast: &'a DeriveInput,
+    pub attrs: ContainerAttrs,
+    pub shape: Shape,
+}
+
+fn fields(fields: &Fields) -> syn::Result<Vec<Field>> {
+    let mut fields = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let member = match &f.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index {
+                    index: i as u32,
+                    span: Span::call_site(),
+                }),
+            };
+            let name = match &member {
+                Member::Named(ident) => ident.to_string(),
+                Member::Unnamed(index) => index.index.to_string(),
+            };
+            let attrs = FieldAttrs::parse(&f.attrs)?;
+            let ty = match attrs.cond {
+                Some(_) => option_inner(&f.ty).cloned().ok_or_else(|| {
+                    Error::new_spanned(&f.ty, "fields with `if` must be an `Option`")
+                })?,
+                None => f.ty.clone(),
+            };
+            Ok(Field {
+                member,
+                name,
+                ty,
+                attrs,
+                present_bit: None,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    for i in 0..fields.len() {
+        let Some(targets) = fields[i].attrs.presence_of.clone() else {
+            continue;
+        };
+        let bitmap = fields[i].member.clone();
+        for (bit, target) in targets.iter().enumerate() {
+            let j = fields[i + 1..]
+                .iter()
+                .position(|g| g.member == *target)
+                .map(|j| i + 1 + j)
+                .filter(|&j| {
+                    let g = &fields[j].attrs;
+                    !g.skip && g.cond.is_none()
+                })
+                .ok_or_else(|| {
+                    Error::new_spanned(
+                        target,
+                        "`presence_of` must name later fields that are packed, without `if`",
+                    )
+                })?;
+            let g = &mut fields[j];
+            g.ty = option_inner(&g.ty).cloned().ok_or_else(|| {
+                Error::new_spanned(&g.ty, "fields named by `presence_of` must be an `Option`")
+            })?;
+            let bit = bit as u32;
+            g.attrs.cond = Some(parse_quote!((self.#bitmap & (1 << #bit)) != 0));
+            g.present_bit = Some(bit);
+        }
+    }
+    for (i, f) in fields.iter().enumerate() {
+        if let Some(target) = &f.attrs.len_of {
+            if !fields[i + 1..]
+                .iter()
+                .any(|g| g.name == target.value() && !g.attrs.skip)
+            {
+                return Err(Error::new(
+                    target.span(),
+                    "`len_of` must name a later field that is packed",
+                ));
+            }
+        }
+    }
+    Ok(fields)
+}
+
+/// Index of the field whose packed size `fields[i]` holds, if any.
+pub fn len_target(fields: &[Field], i: usize) -> Option<usize> {
+    let target = fields[i].attrs.len_of.as_ref()?.value();
+    fields.iter().position(|f| f.name == target)
+}
+
+/// Index of the field holding the packed size of `fields[i]`, if any.
+pub fn len_source(fields: &[Field], i: usize) -> Option<usize> {
+    (0..i).find(|&j| len_target(fields, j) == Some(i))
+}
+
+impl<'a> Input<'a> {
+    pub fn parse(ast: &'a DeriveInput) -> syn::Result<Self> {
+        let attrs = ContainerAttrs::parse(&ast.attrs)?;
+        let shape = match &ast.data {
+            Data::Struct(data) => Shape::Struct(fields(&data.fields)?),
+            Data::Enum(data) => {
+                if attrs.tag.is_none() && !attrs.untagged {
+                    return Err(Error::new_spanned(
+                        &ast.ident,
+                        "enums need a tag type: add `#[pack(tag = u8)]` or similar",
+                    ));
+                }
+                if data.variants.is_empty() {
+                    return Err(Error::new_spanned(
+                        &ast.ident,
+                        "enums without variants cannot be derived",
+                    ));
+                }
+                let variants = data
+                    .variants
+                    .iter()
+                    .map(|v| {
+                        let variant_attrs = VariantAttrs::parse(&v.attrs)?;
+                        if attrs.untagged && variant_attrs.tag.is_some() {
+                            return Err(Error::new_spanned(
+                                v,
+                                "variants of untagged enums have no tag",
+                            ));
+                        }
+                        let fields = fields(&v.fields)?;
+                        if variant_attrs.other {
+                            let msg = if attrs.untagged {
+                                Some("untagged enums have no `other` variant")
+                            } else if variant_attrs.tag.is_some() {
+                                Some("`other` variants have no tag of their own")
+                            } else if fields.first().is_none_or(|f| f.attrs.skip) {
+                                Some("`other` variants need a first field to hold the tag")
+                            } else {
+                                None
+                            };
+                            if let Some(msg) = msg {
+                                return Err(Error::new_spanned(v, msg));
+                            }
+                        }
+                        let discriminant = v.discriminant.as_ref().map(|(_, expr)| expr.clone());
+                        Ok(Variant {
+                            ident: v.ident.clone(),
+                            tag: variant_attrs.tag.or(discriminant),
+                            other: variant_attrs.other,
+                            fields,
+                        })
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+                if let Some(v) = variants.iter().filter(|v| v.other).nth(1) {
+                    return Err(Error::new_spanned(
+                        &v.ident,
+                        "only one variant can be `other`",
+                    ));
+                }
+                Shape::Enum {
+                    tag: attrs.tag.clone().map(Box::new),
+                    variants,
+                }
+            }
+            Data::Union(_) => {
+                return Err(Error::new_spanned(&ast.ident, "unions cannot be derived"))
+            }
+        };
+        Ok(Self { ast, attrs, shape })
+    }
+
+    /// The fields of the struct, or of all variants of the enum.
+    fn fields(&self) -> Box<dyn Iterator<Item = &Field> + '_> {
+        match &self.shape {
+            Shape::Struct(fields) => Box::new(fields.iter()),
+            Shape::Enum { variants, .. } => Box::new(variants.iter().flat_map(|v| &v.fields)),
+        }
+    }
+
+    /// Generics for an impl of `bound`: the type's own, plus `T: bound` for
+    /// each type parameter `T` used by a field packed as is, or else the
+    /// predicates from `#[pack(bound = "...")]`.
+    pub fn generics(&self, bound: TokenStream2) -> Generics {
+        let mut generics = self.ast.generics.clone();
+        let predicates = &mut generics.make_where_clause().predicates;
+        if let Some(bound) = &self.attrs.bound {
+            predicates.extend(bound.iter().cloned());
+            return generics;
+        }
+        let direct = self
+            .fields()
+            .filter(|f| !f.attrs.skip && f.attrs.map.is_none() && f.attrs.try_map.is_none())
+            .map(|f| f.ty.to_token_stream())
+            .collect::<Vec<_>>();
+        for param in self.ast.generics.type_params() {
+            let ident = &param.ident;
+            if direct.iter().any(|ty| mentions(ty.clone(), ident)) {
+                predicates.push(parse_quote!(#ident: #bound));
+            }
+        }
+        generics
+    }
+
+    /// Whether `ty` mentions any of the type parameters.
+    pub fn is_generic(&self, ty: &Type) -> bool {
+        let ty = ty.to_token_stream();
+        self.ast
+            .generics
+            .type_params()
+            .any(|param| mentions(ty.clone(), &param.ident))
+    }
+}
+
+/// Local constants `__T0`, `__T1`... holding each variant's tag as a `tag`,
+/// usable both as values and as match patterns, along with compile-time
+/// checks that no two variants other than an `other` one share a tag.
+pub fn tag_consts(tag: &Type, variants: &[Variant]) -> (Vec<Ident>, TokenStream2) {
+    let names = (0..variants.len())
+        .map(|i| format_ident!("__T{}", i, span = Span::mixed_site()))
+        .collect::<Vec<_>>();
+    let defs = variants.iter().enumerate().map(|(i, v)| {
+        let name = &names[i];
+        let value = match (&v.tag, i) {
+            (Some(expr), _) => quote!(#expr),
+            (None, 0) => quote!(0),
+            (None, _) => {
+                let prev = &names[i - 1];
+                quote!(#prev + 1)
+            }
+        };
+        quote!(const #name: #tag = #value;)
+    });
+    let tagged = variants
+        .iter()
+        .zip(&names)
+        .filter(|(v, _)| !v.other)
+        .collect::<Vec<_>>();
+    let checks = tagged.iter().enumerate().flat_map(|(i, (a, a_tag))| {
+        tagged[i + 1..].iter().map(move |(b, b_tag)| {
+            let msg = format!("variants `{}` and `{}` have the same tag", a.ident, b.ident);
+            quote!(
+                const _: () = ::core::assert!(#a_tag != #b_tag, #msg);
+            )
+        })
+    });
+    let defs = quote!(#(#defs)* #(#checks)*);
+    (names, defs)
+}
+
+/// Local variables `__b0`, `__b1`... for binding `fields`.
+pub fn bindings(fields: &[Field]) -> Vec<Ident> {
+    (0..fields.len())
+        .map(|i| format_ident!("__b{}", i, span = Span::mixed_site()))
+        .collect()
+}
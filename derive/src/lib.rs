@@ -0,0 +1,346 @@
+//! Derive macros for [`byteorder-pack`](https://docs.rs/byteorder-pack).
+//!
+//! These are re-exported by `byteorder-pack` when its `derive` feature is
+//! enabled; use them from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::DeriveInput;
+
+mod attr;
+mod describe;
+mod input;
+mod pack;
+mod unpack;
+
+use input::Input;
+
+fn expand(input: TokenStream, f: fn(&Input) -> TokenStream2) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as DeriveInput);
+    match Input::parse(&ast) {
+        Ok(input) => f(&input).into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derive `PackTo` for a struct, packing each field in declaration order
+/// with the requested byte order. Tuple structs and newtypes work alike.
+///
+/// Every field type must implement `PackTo`, except for skipped fields. Enums
+/// are packed as an integer tag followed by the variant's fields. Type
+/// parameters that packed fields use are bounded by `PackTo` in the impl, and
+/// likewise for the other derives.
+///
+/// Field attributes, which `UnpackFrom` honours too:
+///
+/// - `#[pack(endian = "little")]` (or `"big"` or `"native"`) always packs the
+///   field in that byte order, so legacy fields can be mixed into an
+///   otherwise big-endian struct.
+/// - `#[pack(skip)]` leaves the field out. It is unpacked as
+///   `Default::default()` or, with `#[pack(skip, default = "path")]`, as the
+///   result of calling `path`.
+/// - `#[pack(align = 4)]` inserts zero bytes before the field so that its
+///   offset from the start of the struct (including an enum's tag) is a
+///   multiple of 4. Unpacking skips them.
+/// - `#[pack(pad_after = 3)]` inserts 3 zero bytes after the field.
+///   Unpacking skips them.
+/// - `#[pack(len_prefix = u16)]` on a collection field (a `Vec<T>`,
+///   `VecDeque<T>`, `String`, set or map) packs its element count (bytes for a
+///   `String`) as a `u16`, or any other `LengthPrefix` type, followed by the
+///   elements. Unpacking reads exactly that many elements and rejects strings
+///   that are not UTF-8.
+/// - `#[pack(count = "header.n_entries")]` on a collection field packs just
+///   its elements, and unpacks as many as the expression, an unsigned integer
+///   over earlier named fields, gives. Packing does not check that the count
+///   field matches.
+/// - `#[pack(len_of = "payload")]` on an integer field packs the packed size
+///   in bytes of the later field `payload` in its place, whatever its value.
+///   Unpacking reads it as usual and then unpacks `payload` from exactly that
+///   many bytes, failing with `ErrorKind::InvalidData` if it needs more or
+///   fewer.
+/// - `#[pack(if = "self.flags & 1 != 0")]` on an `Option<T>` field packs
+///   and unpacks a `T` only if the condition holds, and the field is `None`
+///   otherwise. The condition refers to earlier fields as `self.field`.
+///   Packing fails with `ErrorKind::InvalidInput` if the condition holds but
+///   the field is `None`.
+/// - `#[pack(presence_of = "altitude, speed")]` on an integer field packs a
+///   bitmap of which of the later `Option` fields `altitude` and `speed` are
+///   `Some`, the first in the least significant bit, whatever the field's
+///   value, and packs and unpacks those fields only if their bit is set.
+///   Unpacking rejects bitmaps with other bits set with
+///   `ErrorKind::InvalidData`. Listing more fields than the integer has bits
+///   fails to compile. The `Presence` wrapper does the same for a tuple.
+/// - `#[pack(assert = "len <= 1024")]` makes unpacking fail with
+///   `ErrorKind::InvalidData` unless the expression holds once the field is
+///   read. The field and the named fields before it are in scope by name.
+/// - `#[pack(map = "f", unmap = "g")]` stores the field as a different wire
+///   type: unpacking reads the argument type of `f` and converts it with `f`,
+///   and packing converts a reference to the field with `g` and packs the
+///   result. `f` and `g` are paths or closures, such as
+///   `unmap = "|b: &bool| *b as u32"`.
+/// - `#[pack(try_map = "f", unmap = "g")]` is like `map`, but `f` returns a
+///   `Result`, and its errors make unpacking fail with
+///   `ErrorKind::InvalidData`.
+///
+/// Struct and enum attributes:
+///
+/// - `#[pack(magic = b"RIFF")]` packs the bytes before everything else, and
+///   makes unpacking fail with `ErrorKind::InvalidData`, showing the bytes
+///   found, unless they match.
+/// - `#[pack(endian = "big")]` (or `"little"` or `"native"`) also generates
+///   inherent `pack(&self, dst)` and `unpack(src)` methods, from `PackTo` and
+///   `UnpackFrom` respectively, that use that byte order. The trait impls
+///   still take any byte order.
+/// - `#[pack(bound = "T: PackTo + Copy")]` replaces the inferred bounds on
+///   type parameters with the given where predicates, for every derive.
+///
+/// Enum attributes:
+///
+/// - `#[pack(tag = u8)]` (or any other integer type) on the enum sets the tag
+///   type, which is otherwise taken from the enum's `#[repr]`.
+/// - Tags are numbered from 0 like discriminants. A variant's tag can be set
+///   with its discriminant or `#[pack(tag = 5)]`. Two variants with the same
+///   tag fail to compile.
+/// - `#[pack(other)]` on a variant makes it catch unknown tags instead of
+///   failing: its first field, which must have the tag type, holds the tag,
+///   and its other fields are unpacked as usual, e.g. a length-prefixed
+///   `Vec<u8>` of raw bytes. Packing it writes the first field as the tag.
+///   It still takes a number when tags are numbered.
+/// - `#[pack(untagged)]` on the enum packs only the variant's fields, for
+///   formats that tell records apart by their contents. Unpacking tries each
+///   variant in order and returns the first that unpacks and passes its
+///   assertions, replaying the bytes read by failed attempts, which needs the
+///   `alloc` feature. The matching variant must consume at least as many
+///   bytes as the attempts before it read, so list shorter variants first.
+/// # Example
+/// ```rust
+/// use byteorder_pack::PackTo;
+///
+/// #[derive(PackTo)]
+/// #[pack(endian = "big")]
+/// struct Header {
+///     version: u8,
+///     #[pack(map = "|v: u8| v != 0", unmap = "|b: &bool| *b as u8")]
+///     compressed: bool,
+///     #[pack(endian = "little")]
+///     len: u32,
+///     #[pack(if = "self.version >= 2")]
+///     checksum: Option<u16>,
+/// }
+///
+/// #[derive(PackTo)]
+/// #[pack(tag = u8)]
+/// enum Message {
+///     Ping,
+///     Move { x: i16, y: i16 },
+///     #[pack(tag = 0x10)]
+///     Say(#[pack(len_prefix = u8)] String),
+/// }
+///
+/// #[derive(PackTo)]
+/// struct Packet<T> {
+///     header: Header,
+///     #[pack(len_of = "payload")]
+///     len: u16,
+///     payload: T,
+/// }
+///
+/// let mut buf = vec![];
+/// let header = Header { version: 2, compressed: true, len: 3, checksum: Some(0xabcd) };
+/// header.pack(&mut buf).unwrap();
+/// assert_eq!(buf, [2, 1, 3, 0, 0, 0, 0xab, 0xcd]);
+///
+/// let mut buf = vec![];
+/// Message::Move { x: 1, y: -1 }.pack_to_be(&mut buf).unwrap();
+/// Message::Say("hi".into()).pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [1, 0, 1, 0xff, 0xff, 0x10, 2, b'h', b'i']);
+///
+/// let mut buf = vec![];
+/// let header = Header { version: 1, compressed: false, len: 2, checksum: None };
+/// Packet { header, len: 0, payload: 7u32 }.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [1, 0, 2, 0, 0, 0, 0, 4, 0, 0, 0, 7]);
+/// ```
+///
+/// ```compile_fail
+/// use byteorder_pack::PackTo;
+///
+/// #[derive(PackTo)]
+/// #[pack(tag = u8)]
+/// enum Clash {
+///     A,
+///     #[pack(tag = 0)]
+///     B,
+/// }
+/// ```
+#[proc_macro_derive(PackTo, attributes(pack))]
+pub fn derive_pack_to(input: TokenStream) -> TokenStream {
+    expand(input, pack::expand)
+}
+
+/// Derive `UnpackFrom` for a struct, unpacking each field in declaration
+/// order with the requested byte order.
+///
+/// Every field type must implement `UnpackFrom`, except for skipped fields.
+/// Enums are unpacked by reading the tag and then the fields of the variant
+/// it names; unknown tags fail with `ErrorKind::InvalidData` unless there is
+/// an `other` variant, as do untagged enums that no variant matches. The
+/// attributes are those of `PackTo`.
+///
+/// With the `tracing` feature of `byteorder-pack`, unpacking emits a span
+/// named after the type and an event per field.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{Delta, PackTo, UnpackFrom};
+///
+/// #[derive(Debug, PartialEq, UnpackFrom)]
+/// #[pack(magic = b"HD")]
+/// struct Header {
+///     version: u8,
+///     #[pack(len_prefix = u8)]
+///     flags: Vec<u16>,
+///     #[pack(assert = "len <= 1024")]
+///     len: u32,
+///     #[pack(skip)]
+///     cached_checksum: Option<u32>,
+/// }
+///
+/// #[derive(Debug, PartialEq, UnpackFrom)]
+/// struct Table {
+///     header: Header,
+///     #[pack(count = "header.len")]
+///     rows: Vec<u8>,
+/// }
+///
+/// #[derive(Debug, PartialEq, PackTo, UnpackFrom)]
+/// struct Samples {
+///     n: u8,
+///     #[pack(count = "n")]
+///     times: Vec<Delta<u32>>,
+/// }
+///
+/// #[derive(Debug, PartialEq, PackTo, UnpackFrom)]
+/// struct Telemetry {
+///     #[pack(presence_of = "altitude, speed")]
+///     present: u8,
+///     id: u16,
+///     altitude: Option<u32>,
+///     speed: Option<u16>,
+/// }
+///
+/// #[derive(Debug, PartialEq, UnpackFrom)]
+/// #[repr(u16)]
+/// enum Kind {
+///     Data = 1,
+///     Ack,
+/// }
+///
+/// #[derive(Debug, PartialEq, UnpackFrom)]
+/// #[pack(tag = u8)]
+/// enum Event {
+///     Start,
+///     Stop,
+///     #[pack(other)]
+///     Unknown(u8, #[pack(len_prefix = u8)] Vec<u8>),
+/// }
+///
+/// #[derive(Debug, PartialEq, UnpackFrom)]
+/// #[pack(untagged)]
+/// enum Chunk {
+///     Ack {
+///         #[pack(assert = "magic == *b\"AK\"")]
+///         magic: [u8; 2],
+///     },
+///     Data {
+///         #[pack(assert = "magic == *b\"DT\"")]
+///         magic: [u8; 2],
+///         len: u16,
+///     },
+/// }
+///
+/// let header = Header::unpack_from_be(&mut &b"HD\x01\x01\0\x02\0\0\0\x03"[..]).unwrap();
+/// assert_eq!(header, Header { version: 1, flags: vec![2], len: 3, cached_checksum: None });
+/// assert!(Header::unpack_from_be(&mut &b"HD\x01\x00\xff\0\0\0"[..]).is_err());
+///
+/// let table = Table::unpack_from_be(&mut &b"HD\x01\x00\0\0\0\x02\x07\x08"[..]).unwrap();
+/// assert_eq!(table.rows, [7, 8]);
+///
+/// let samples = Samples { n: 3, times: vec![Delta(100), Delta(105), Delta(103)] };
+/// let mut buf = vec![];
+/// samples.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf[1..], [0, 0, 0, 100, 0, 0, 0, 5, 0xff, 0xff, 0xff, 0xfe]);
+/// assert_eq!(Samples::unpack_from_be(&mut &buf[..]).unwrap(), samples);
+///
+/// let telemetry = Telemetry { present: 0b10, id: 7, altitude: None, speed: Some(3) };
+/// let mut buf = vec![];
+/// telemetry.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0b10, 0, 7, 0, 3]);
+/// assert_eq!(Telemetry::unpack_from_be(&mut &buf[..]).unwrap(), telemetry);
+/// assert!(Telemetry::unpack_from_be(&mut &[0b100, 0, 7][..]).is_err());
+///
+/// assert_eq!(Kind::unpack_from_be(&mut &[0, 2][..]).unwrap(), Kind::Ack);
+/// assert!(Kind::unpack_from_be(&mut &[0, 3][..]).is_err());
+///
+/// let event = Event::unpack_from_be(&mut &[7, 1, 0xff][..]).unwrap();
+/// assert_eq!(event, Event::Unknown(7, vec![0xff]));
+///
+/// let chunk = Chunk::unpack_from_be(&mut &b"DT\0\x05"[..]).unwrap();
+/// assert_eq!(chunk, Chunk::Data { magic: *b"DT", len: 5 });
+/// assert!(Chunk::unpack_from_be(&mut &b"XX\0\x05"[..]).is_err());
+/// ```
+#[proc_macro_derive(UnpackFrom, attributes(pack))]
+pub fn derive_unpack_from(input: TokenStream) -> TokenStream {
+    expand(input, unpack::expand)
+}
+
+/// Derive `Describe` for a struct, describing it as a `Schema::Struct` with
+/// the fields' names (their indices in tuple structs) and schemas in
+/// declaration order. Newtypes are described as the type they wrap.
+///
+/// Every field type must implement `Describe`. Skipped fields are left out,
+/// magic shows up as a `Schema::Bytes` field named `magic` and padding as
+/// `Schema::Bytes` fields named `pad_<offset>`.
+///
+/// Length-prefixed, counted, mapped and conditional fields, including those
+/// named by `presence_of`, have no fixed layout and are rejected. So are
+/// fields with their own byte order from `#[pack(endian = ...)]`, since a
+/// schema is read in a single byte order.
+/// Enums without fields are described as their tag; other enums have no
+/// fixed layout to describe either.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{Describe, Schema};
+///
+/// #[derive(Describe)]
+/// struct Header {
+///     version: u8,
+///     #[pack(align = 4)]
+///     len: u32,
+/// }
+///
+/// #[derive(Describe)]
+/// struct Id(u32);
+///
+/// assert_eq!(
+///     Header::schema(),
+///     Schema::Struct(vec![
+///         ("version".into(), Schema::U8),
+///         ("pad_1".into(), Schema::Bytes(3)),
+///         ("len".into(), Schema::U32),
+///     ]),
+/// );
+/// assert_eq!(Id::schema(), Schema::U32);
+/// ```
+///
+/// ```compile_fail
+/// use byteorder_pack::Describe;
+///
+/// #[derive(Describe)]
+/// struct Mixed {
+///     #[pack(endian = "little")]
+///     len: u32,
+/// }
+/// ```
+#[proc_macro_derive(Describe, attributes(pack))]
+pub fn derive_describe(input: TokenStream) -> TokenStream {
+    expand(input, describe::expand)
+}
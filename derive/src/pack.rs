@@ -0,0 +1,231 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Error;
+
+use crate::input::{bindings, len_target, tag_consts, Field, Input, Shape};
+
+/// Statement packing the field `f`, whose value is behind the reference
+/// `value`, if `cond` holds for fields with a condition, or if it is `Some`
+/// for fields with a presence bit. Alignment and padding around the field
+/// are left out unless `padded`.
+fn pack_field(
+    f: &Field,
+    value: TokenStream2,
+    cond: Option<TokenStream2>,
+    padded: bool,
+) -> TokenStream2 {
+    if f.attrs.skip {
+        return TokenStream2::new();
+    }
+    if let Some(cond) = cond {
+        let pack = pack_field(f, quote!(__v), None, padded);
+        if f.present_bit.is_some() {
+            return quote! {
+                if let ::core::option::Option::Some(__v) = #value {
+                    #pack
+                }
+            };
+        }
+        let msg = format!("field `{}` is `None` but its condition holds", f.name);
+        return quote! {
+            if #cond {
+                match #value {
+                    ::core::option::Option::Some(__v) => { #pack }
+                    ::core::option::Option::None => {
+                        return ::core::result::Result::Err(::byteorder_pack::io::Error::new(
+                            ::byteorder_pack::io::ErrorKind::InvalidInput,
+                            #msg,
+                        ));
+                    }
+                }
+            }
+        };
+    }
+    let order = f.order();
+    let align = f
+        .align()
+        .filter(|_| padded)
+        .map(|align| quote!(__dst.align(#align)?;));
+    let pad = f
+        .pad_after()
+        .filter(|_| padded)
+        .map(|n| quote!(__dst.pad(#n)?;));
+    let ty = &f.ty;
+    let pack = match &f.attrs.len_prefix {
+        Some(len) => quote! {
+            <#ty as ::byteorder_pack::__private::PackPrefixed>::pack_prefixed::<#order, #len, _>(
+                #value, __dst,
+            )?;
+        },
+        None if f.attrs.count.is_some() => quote! {
+            <#ty as ::byteorder_pack::__private::PackPrefixed>::pack_elements::<#order, _>(
+                #value, __dst,
+            )?;
+        },
+        None if f.attrs.map.is_some() || f.attrs.try_map.is_some() => match &f.attrs.unmap {
+            Some(unmap) => quote! {
+                ::byteorder_pack::PackTo::pack_to::<#order, _>(&(#unmap)(#value), __dst)?;
+            },
+            None => {
+                return Error::new_spanned(&f.ty, "mapped fields need `unmap` to be packed")
+                    .to_compile_error()
+            }
+        },
+        None => quote!(::byteorder_pack::PackTo::pack_to::<#order, _>(#value, __dst)?;),
+    };
+    quote! {
+        #align
+        #pack
+        #pad
+    }
+}
+
+/// Statements packing `fields`, whose values are behind the references
+/// `values`. Each `self.field` in conditions is replaced by what `this`
+/// returns for the field's index, if anything. Presence bitmaps are packed
+/// as the fields that are `Some`, whatever their value.
+fn pack_fields(
+    fields: &[Field],
+    values: &[TokenStream2],
+    this: &dyn Fn(usize) -> Option<TokenStream2>,
+) -> TokenStream2 {
+    let cond = |f: &Field| {
+        f.cond(|member| {
+            let i = fields.iter().position(|f| f.member == *member)?;
+            this(i)
+        })
+    };
+    let stmts = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match len_target(fields, i) {
+            Some(j) => {
+                let ty = &f.ty;
+                let target = pack_field(&fields[j], values[j].clone(), cond(&fields[j]), false);
+                let pack = pack_field(f, quote!(&__len), None, true);
+                quote! {{
+                    let __len = ::byteorder_pack::__private::packed_len(|__dst| {
+                        #target
+                        ::core::result::Result::Ok(())
+                    })?;
+                    let __len = <#ty as ::byteorder_pack::LengthPrefix>::from_usize(__len)?;
+                    #pack
+                }}
+            }
+            None => match &f.attrs.presence_of {
+                Some(targets) => {
+                    let ty = &f.ty;
+                    let bits = targets.iter().enumerate().map(|(bit, target)| {
+                        let j = fields.iter().position(|g| g.member == *target).unwrap();
+                        let value = &values[j];
+                        let bit = bit as u32;
+                        quote!(| ((::core::option::Option::is_some(#value) as #ty) << #bit))
+                    });
+                    let count = targets.len() as u32;
+                    let msg = format!("field `{}` has too few bits for its fields", f.name);
+                    let pack = pack_field(f, quote!(&__presence), None, true);
+                    quote! {{
+                        const _: () = ::core::assert!(#count <= <#ty>::BITS, #msg);
+                        let __presence: #ty = 0 #(#bits)*;
+                        #pack
+                    }}
+                }
+                None => pack_field(f, values[i].clone(), cond(f), true),
+            },
+        });
+    quote!(#(#stmts)*)
+}
+
+pub fn expand(input: &Input) -> TokenStream2 {
+    let body = match &input.shape {
+        Shape::Struct(fields) => {
+            let values = fields
+                .iter()
+                .map(|f| {
+                    let member = &f.member;
+                    quote!(&self.#member)
+                })
+                .collect::<Vec<_>>();
+            pack_fields(fields, &values, &|_| None)
+        }
+        Shape::Enum { tag, variants } => {
+            let (tags, consts) = match tag {
+                Some(tag) => tag_consts(tag, variants),
+                None => (Vec::new(), TokenStream2::new()),
+            };
+            let arms = variants.iter().enumerate().map(|(i, v)| {
+                let ident = &v.ident;
+                let members = v.fields.iter().map(|f| &f.member);
+                let bindings = bindings(&v.fields);
+                let values = bindings.iter().map(|b| quote!(#b)).collect::<Vec<_>>();
+                // The first field of an `other` variant is its tag.
+                let first = v.other as usize;
+                let stmts = pack_fields(&v.fields[first..], &values[first..], &|i| {
+                    let binding = &bindings[first + i];
+                    Some(quote!((*#binding)))
+                });
+                let tag = match (tag, v.other) {
+                    (Some(tag), true) => {
+                        let binding = &bindings[0];
+                        Some(quote!(<#tag as ::byteorder_pack::PackTo>::pack_to::<__E, _>(#binding, __dst)?;))
+                    }
+                    _ => tags
+                        .get(i)
+                        .map(|tag| quote!(::byteorder_pack::PackTo::pack_to::<__E, _>(&#tag, __dst)?;)),
+                };
+                quote! {
+                    Self::#ident { #(#members: #bindings),* } => {
+                        #tag
+                        #stmts
+                    }
+                }
+            });
+            quote! {
+                #consts
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    };
+
+    let magic = input
+        .attrs
+        .magic
+        .as_ref()
+        .map(|magic| quote!(::byteorder_pack::io::Write::write_all(__dst, #magic)?;));
+
+    let name = &input.ast.ident;
+    let generics = input.generics(quote!(::byteorder_pack::PackTo));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let inherent = input.attrs.endian.as_ref().map(|order| {
+        let vis = &input.ast.vis;
+        quote! {
+            #[allow(dead_code)]
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Pack `self` to `dst` in the byte order given by
+                /// `#[pack(endian = ...)]`.
+                #vis fn pack<__W: ::byteorder_pack::io::Write + ?::core::marker::Sized>(
+                    &self,
+                    dst: &mut __W,
+                ) -> ::byteorder_pack::io::Result<()> {
+                    ::byteorder_pack::PackTo::pack_to::<#order, __W>(self, dst)
+                }
+            }
+        }
+    });
+    quote! {
+        #inherent
+        impl #impl_generics ::byteorder_pack::PackTo for #name #ty_generics #where_clause {
+            fn pack_to<
+                __E: ::byteorder_pack::byteorder::ByteOrder,
+                __W: ::byteorder_pack::io::Write + ?::core::marker::Sized,
+            >(&self, __dst: &mut __W) -> ::byteorder_pack::io::Result<()> {
+                let __dst = &mut ::byteorder_pack::__private::FieldWriter::new(__dst);
+                #magic
+                #body
+                ::core::result::Result::Ok(())
+            }
+        }
+    }
+}
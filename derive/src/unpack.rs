@@ -0,0 +1,285 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{Error, Member, Type};
+
+use crate::input::{len_source, tag_consts, Field, Input, Shape};
+
+/// Closure passed to `FieldReader::field` to show a field's value of type
+/// `ty` in `tracing` events, whether or not its type implements `Debug`.
+///
+/// Whether a generic type implements `Debug` is not known when the impl is
+/// checked, so values of such types are never shown.
+fn debug_value(input: &Input, ty: &Type) -> TokenStream2 {
+    if input.is_generic(ty) {
+        return quote!(|_| &"<generic>");
+    }
+    quote! {
+        |__v| {
+            #[allow(unused_imports)]
+            use ::byteorder_pack::__private::{ViaDebug, ViaOpaque};
+            (&&::byteorder_pack::__private::MaybeDebug(__v)).as_debug()
+        }
+    }
+}
+
+/// Statements unpacking `fields` into local variables, followed by an
+/// expression building `path` from them. The first field is set to `first`
+/// instead, if given.
+///
+/// Named fields are unpacked into variables of the same name, so that
+/// expressions in later attributes can refer to them.
+fn unpack_fields(
+    input: &Input,
+    path: TokenStream2,
+    fields: &[Field],
+    first: Option<TokenStream2>,
+) -> TokenStream2 {
+    let locals = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match &f.member {
+            Member::Named(ident) => ident.clone(),
+            Member::Unnamed(_) => format_ident!("__f{}", i, span = Span::mixed_site()),
+        })
+        .collect::<Vec<_>>();
+    let members = fields.iter().map(|f| &f.member);
+    let stmts = fields.iter().zip(&locals).enumerate().map(|(i, (f, local))| {
+        let ty = &f.ty;
+        let debug_value = debug_value(input, ty);
+        let name = &f.name;
+        let order = f.order();
+        if let (0, Some(first)) = (i, &first) {
+            return quote!(let #local: #ty = #first;);
+        }
+        if f.attrs.skip {
+            let default = match &f.attrs.default {
+                Some(path) => quote!(#path()),
+                None => quote!(::core::default::Default::default()),
+            };
+            return quote!(let #local: #ty = #default;);
+        }
+        let align = f.align().map(|align| quote!(__fields.align(#align)?;));
+        let pad = f.pad_after().map(|n| quote!(__fields.skip(#n)?;));
+        let unpack = match &f.attrs.len_prefix {
+            Some(len) => quote! {
+                __fields.field_with(#name, #debug_value, |__src| {
+                    <#ty as ::byteorder_pack::__private::UnpackPrefixed>::unpack_prefixed::<#order, #len, _>(
+                        __src,
+                    )
+                })?
+            },
+            None if f.attrs.count.is_some() => {
+                let count = &f.attrs.count;
+                quote! {{
+                    let __count = ::byteorder_pack::LengthPrefix::to_usize(#count)?;
+                    __fields.field_with(#name, #debug_value, |__src| {
+                        <#ty as ::byteorder_pack::__private::UnpackPrefixed>::unpack_elements::<#order, _>(
+                            __src, __count,
+                        )
+                    })?
+                }}
+            }
+            None => match (&f.attrs.map, &f.attrs.try_map) {
+                (Some(map), _) => quote! {
+                    __fields.field_with(#name, #debug_value, |__src| {
+                        let __wire = ::byteorder_pack::UnpackFrom::unpack_from::<#order, _>(__src)?;
+                        ::core::result::Result::Ok::<#ty, _>((#map)(__wire))
+                    })?
+                },
+                (None, Some(try_map)) => {
+                    let msg = format!("field `{}` has an invalid value", name);
+                    quote! {
+                        __fields.field_with(#name, #debug_value, |__src| {
+                            let __wire = ::byteorder_pack::UnpackFrom::unpack_from::<#order, _>(__src)?;
+                            (#try_map)(__wire).map_err(|_| {
+                                ::byteorder_pack::io::Error::new(
+                                    ::byteorder_pack::io::ErrorKind::InvalidData,
+                                    #msg,
+                                )
+                            })
+                        })?
+                    }
+                }
+                (None, None) if f.attrs.unmap.is_some() => {
+                    return Error::new_spanned(
+                        &f.ty,
+                        "fields with `unmap` need `map` or `try_map` to be unpacked",
+                    )
+                    .to_compile_error()
+                }
+                (None, None) => quote!(__fields.field::<#order, #ty>(#name, #debug_value)?),
+            },
+        };
+        let unpack = match len_source(fields, i) {
+            Some(j) => {
+                let len = &locals[j];
+                quote! {
+                    __fields.bounded(
+                        ::byteorder_pack::LengthPrefix::to_usize(#len)?,
+                        |__fields| ::core::result::Result::Ok(#unpack),
+                    )?
+                }
+            }
+            None => unpack,
+        };
+        let cond = f.cond(|member| {
+            let i = fields.iter().position(|f| f.member == *member)?;
+            let local = &locals[i];
+            Some(quote!(#local))
+        });
+        let presence = f.attrs.presence_of.as_ref().map(|targets| {
+            let bits = (0..targets.len() as u32).map(|bit| quote!(| (1 << #bit)));
+            let msg = format!("field `{}` has presence bits set beyond its fields", name);
+            quote! {
+                if #local & !(0 #(#bits)*) != 0 {
+                    return ::core::result::Result::Err(::byteorder_pack::io::Error::new(
+                        ::byteorder_pack::io::ErrorKind::InvalidData,
+                        #msg,
+                    ));
+                }
+            }
+        });
+        let assert = f.attrs.assert.as_ref().map(|(expr, text)| {
+            let msg = format!("field `{}` failed assertion `{}`", name, text);
+            quote! {
+                if !(#expr) {
+                    return ::core::result::Result::Err(::byteorder_pack::io::Error::new(
+                        ::byteorder_pack::io::ErrorKind::InvalidData,
+                        #msg,
+                    ));
+                }
+            }
+        });
+        match cond {
+            Some(cond) => quote! {
+                let #local = if #cond {
+                    #align
+                    let __v = #unpack;
+                    #pad
+                    ::core::option::Option::Some(__v)
+                } else {
+                    ::core::option::Option::None
+                };
+                #assert
+            },
+            None => quote! {
+                #align
+                let #local = #unpack;
+                #presence
+                #assert
+                #pad
+            },
+        }
+    });
+    quote! {
+        #(#stmts)*
+        ::core::result::Result::Ok(#path { #(#members: #locals),* })
+    }
+}
+
+pub fn expand(input: &Input) -> TokenStream2 {
+    let body = match &input.shape {
+        Shape::Struct(fields) => unpack_fields(input, quote!(Self), fields, None),
+        Shape::Enum {
+            tag: None,
+            variants,
+        } => {
+            let attempts = variants.iter().map(|v| {
+                let ident = &v.ident;
+                let name = ident.to_string();
+                let arm = unpack_fields(input, quote!(Self::#ident), &v.fields, None);
+                quote! {
+                    if let ::core::option::Option::Some(__v) =
+                        __replay.attempt(#name, |__fields| { #arm })?
+                    {
+                        return ::core::result::Result::Ok(__v);
+                    }
+                }
+            });
+            quote! {
+                let mut __replay = ::byteorder_pack::__private::Replay::new(__fields);
+                #(#attempts)*
+                ::core::result::Result::Err(__replay.no_match())
+            }
+        }
+        Shape::Enum {
+            tag: Some(tag),
+            variants,
+        } => {
+            let (tags, consts) = tag_consts(tag, variants);
+            let debug_value = debug_value(input, tag);
+            let arms = variants
+                .iter()
+                .zip(&tags)
+                .filter(|(v, _)| !v.other)
+                .map(|(v, tag)| {
+                    let ident = &v.ident;
+                    let unpack = unpack_fields(input, quote!(Self::#ident), &v.fields, None);
+                    quote!(#tag => { #unpack })
+                });
+            // The last arm catches unknown tags, which fail unless there is an
+            // `other` variant to keep them.
+            let unknown = match variants.iter().find(|v| v.other) {
+                Some(v) => {
+                    let ident = &v.ident;
+                    let unpack =
+                        unpack_fields(input, quote!(Self::#ident), &v.fields, Some(quote!(__tag)));
+                    quote!(__tag => { #unpack })
+                }
+                None => quote! {
+                    _ => ::core::result::Result::Err(::byteorder_pack::io::Error::new(
+                        ::byteorder_pack::io::ErrorKind::InvalidData,
+                        "unknown enum tag",
+                    )),
+                },
+            };
+            quote! {
+                #consts
+                match __fields.field::<__E, #tag>("tag", #debug_value)? {
+                    #(#arms)*
+                    #unknown
+                }
+            }
+        }
+    };
+
+    let magic = input
+        .attrs
+        .magic
+        .as_ref()
+        .map(|magic| quote!(__fields.magic(#magic)?;));
+
+    let name = &input.ast.ident;
+    let generics = input.generics(quote!(::byteorder_pack::UnpackFrom));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = name.to_string();
+    let inherent = input.attrs.endian.as_ref().map(|order| {
+        let vis = &input.ast.vis;
+        quote! {
+            #[allow(dead_code)]
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Unpack a value from `src` in the byte order given by
+                /// `#[pack(endian = ...)]`.
+                #vis fn unpack<__R: ::byteorder_pack::io::Read + ?::core::marker::Sized>(
+                    src: &mut __R,
+                ) -> ::byteorder_pack::io::Result<Self> {
+                    <Self as ::byteorder_pack::UnpackFrom>::unpack_from::<#order, __R>(src)
+                }
+            }
+        }
+    });
+    quote! {
+        #inherent
+        impl #impl_generics ::byteorder_pack::UnpackFrom for #name #ty_generics #where_clause {
+            fn unpack_from<
+                __E: ::byteorder_pack::byteorder::ByteOrder,
+                __R: ::byteorder_pack::io::Read + ?::core::marker::Sized,
+            >(__src: &mut __R) -> ::byteorder_pack::io::Result<Self> {
+                ::byteorder_pack::__private::unpack_struct(#struct_name, __src, |__fields| {
+                    #magic
+                    #body
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Format-string macros for [`byteorder-pack`](https://docs.rs/byteorder-pack).
+//!
+//! These are re-exported by `byteorder-pack` when its `macros` feature is
+//! enabled; use them from there.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, Expr, LitStr, Token};
+
+/// One item of a format string.
+enum Field {
+    /// `n` padding bytes (`x`).
+    Pad(usize),
+    /// A single value of the given type.
+    Value(TokenStream2),
+}
+
+/// Parse a Python `struct` format string into its byte order and fields.
+fn parse_format(lit: &LitStr) -> syn::Result<(TokenStream2, Vec<Field>)> {
+    let format = lit.value();
+    let err = |msg: String| Error::new(lit.span(), msg);
+
+    let mut chars = format.chars().filter(|c| !c.is_whitespace());
+    let order = match chars.next() {
+        Some('<') => quote!(::byteorder_pack::byteorder::LittleEndian),
+        Some('>' | '!') => quote!(::byteorder_pack::byteorder::BigEndian),
+        Some('=') => quote!(::byteorder_pack::byteorder::NativeEndian),
+        Some('@') => {
+            return Err(err(
+                "native alignment (`@`) is not supported; use `=` for native byte order without padding".into(),
+            ))
+        }
+        _ => {
+            return Err(err(
+                "format must start with a byte order: `<`, `>`, `!` or `=`".into(),
+            ))
+        }
+    };
+
+    let mut fields = Vec::new();
+    while let Some(c) = chars.next() {
+        let mut count = None;
+        let mut code = c;
+        while let Some(digit) = code.to_digit(10) {
+            count = count
+                .unwrap_or(0usize)
+                .checked_mul(10)
+                .and_then(|n| n.checked_add(digit as usize))
+                .map(Some)
+                .ok_or_else(|| err("repeat count too large".into()))?;
+            code = chars
+                .next()
+                .ok_or_else(|| err("repeat count without a format character".into()))?;
+        }
+        let ty = match code {
+            'x' => {
+                fields.push(Field::Pad(count.unwrap_or(1)));
+                continue;
+            }
+            's' => {
+                let n = count.unwrap_or(1);
+                fields.push(Field::Value(quote!([u8; #n])));
+                continue;
+            }
+            'c' | 'B' => quote!(u8),
+            'b' => quote!(i8),
+            '?' => quote!(bool),
+            'h' => quote!(i16),
+            'H' => quote!(u16),
+            'i' | 'l' => quote!(i32),
+            'I' | 'L' => quote!(u32),
+            'q' => quote!(i64),
+            'Q' => quote!(u64),
+            'f' => quote!(f32),
+            'd' => quote!(f64),
+            other => return Err(err(format!("unsupported format character `{}`", other))),
+        };
+        for _ in 0..count.unwrap_or(1) {
+            fields.push(Field::Value(ty.clone()));
+        }
+    }
+    Ok((order, fields))
+}
+
+struct PackInput {
+    dst: Expr,
+    format: LitStr,
+    args: Vec<Expr>,
+}
+
+impl Parse for PackInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let dst = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let format = input.parse()?;
+        let mut args = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            args.push(input.parse()?);
+        }
+        Ok(Self { dst, format, args })
+    }
+}
+
+struct UnpackInput {
+    src: Expr,
+    format: LitStr,
+}
+
+impl Parse for UnpackInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let src = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let format = input.parse()?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Self { src, format })
+    }
+}
+
+/// Pack values to a writer according to a Python `struct` format string.
+///
+/// `pack!(dst, format, args...)` evaluates to an `io::Result<()>`. `dst` is
+/// anything implementing `Write`, and each argument must have exactly the type
+/// its format character maps to.
+///
+/// The format must start with `<` (little-endian), `>` or `!` (big-endian) or
+/// `=` (native byte order); native alignment (`@`) is not supported. Supported
+/// format characters are `x` (pad byte), `c`/`B` (`u8`), `b` (`i8`), `?`
+/// (`bool`), `h`/`H` (`i16`/`u16`), `i`/`I` and `l`/`L` (`i32`/`u32`),
+/// `q`/`Q` (`i64`/`u64`), `f` (`f32`), `d` (`f64`) and `Ns` (`[u8; N]`). A repeat count before any
+/// other character stands for that many values.
+/// # Example
+/// ```rust
+/// use byteorder_pack::pack;
+///
+/// let mut buf = vec![];
+/// pack!(&mut buf, ">HxI2s", 1u16, 2u32, *b"ab").unwrap();
+///
+/// assert_eq!(buf, b"\x00\x01\x00\x00\x00\x00\x02ab");
+/// ```
+///
+/// Invalid formats, such as a repeat count too large for `usize`, fail to
+/// compile:
+///
+/// ```compile_fail
+/// use byteorder_pack::pack;
+///
+/// pack!(&mut vec![], ">99999999999999999999999x").unwrap();
+/// ```
+#[proc_macro]
+pub fn pack(input: TokenStream) -> TokenStream {
+    let PackInput { dst, format, args } = syn::parse_macro_input!(input);
+    let (order, fields) = match parse_format(&format) {
+        Ok(parsed) => parsed,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let expected = fields
+        .iter()
+        .filter(|f| matches!(f, Field::Value(_)))
+        .count();
+    if expected != args.len() {
+        let msg = format!(
+            "format expects {} argument(s), {} given",
+            expected,
+            args.len()
+        );
+        return Error::new(format.span(), msg).to_compile_error().into();
+    }
+
+    // Arguments are evaluated before packing starts, outside the closure, so
+    // that `?` and `return` in them behave as at the call site.
+    let names = (0..args.len())
+        .map(|i| format_ident!("__a{}", i, span = Span::mixed_site()))
+        .collect::<Vec<_>>();
+    let mut next_name = names.iter();
+    let stmts = fields.iter().map(|field| match field {
+        Field::Pad(n) => quote! {
+            ::byteorder_pack::io::Write::write_all(__dst, &[0u8; #n])?;
+        },
+        Field::Value(ty) => {
+            let name = next_name.next().unwrap();
+            quote! {
+                <#ty as ::byteorder_pack::PackTo>::pack_to::<#order, _>(#name, __dst)?;
+            }
+        }
+    });
+
+    quote! {{
+        let __dst = &mut (#dst);
+        #(let #names = &(#args);)*
+        (|| -> ::byteorder_pack::io::Result<()> {
+            #(#stmts)*
+            ::core::result::Result::Ok(())
+        })()
+    }}
+    .into()
+}
+
+/// Unpack values from a reader according to a Python `struct` format string.
+///
+/// `unpack!(src, format)` evaluates to an `io::Result` of a tuple holding one
+/// value per non-padding format item, like Python's `struct.unpack`. `src` is
+/// anything implementing `Read`. The format is the same as for [`pack!`].
+/// # Example
+/// ```rust
+/// use byteorder_pack::unpack;
+///
+/// let mut src = &b"\x01\x00\x02\x00\x00\x00ab"[..];
+/// let (a, b, c) = unpack!(&mut src, "<HI2s").unwrap();
+///
+/// assert_eq!((a, b, c), (1, 2, *b"ab"));
+/// ```
+#[proc_macro]
+pub fn unpack(input: TokenStream) -> TokenStream {
+    let UnpackInput { src, format } = syn::parse_macro_input!(input);
+    let (order, fields) = match parse_format(&format) {
+        Ok(parsed) => parsed,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let mut types = Vec::new();
+    let mut names = Vec::new();
+    let stmts = fields
+        .iter()
+        .map(|field| match field {
+            Field::Pad(n) => quote! {
+                ::byteorder_pack::io::Read::read_exact(__src, &mut [0u8; #n])?;
+            },
+            Field::Value(ty) => {
+                let name = format_ident!("__v{}", names.len(), span = Span::mixed_site());
+                types.push(ty.clone());
+                names.push(name.clone());
+                quote! {
+                    let #name = <#ty as ::byteorder_pack::UnpackFrom>::unpack_from::<#order, _>(__src)?;
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {{
+        let __src = &mut (#src);
+        (|| -> ::byteorder_pack::io::Result<(#(#types,)*)> {
+            #(#stmts)*
+            ::core::result::Result::Ok((#(#names,)*))
+        })()
+    }}
+    .into()
+}
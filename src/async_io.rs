@@ -0,0 +1,336 @@
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{ready, Context, Poll};
+use std::io::{Error, ErrorKind, Read, Result as IoResult};
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::{PackTo, UnpackFrom};
+
+/// A destination that values can be packed into asynchronously.
+///
+/// This is implemented for every `tokio::io::AsyncWrite`, and for a
+/// `futures-io` `AsyncWrite` wrapped in [`FuturesIo`].
+pub trait AsyncPackSink {
+    /// Attempt to write bytes from `buf`, returning how many were written.
+    fn poll_write_packed(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<IoResult<usize>>;
+
+    /// Attempt to flush everything written so far to its destination.
+    fn poll_flush_packed(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>>;
+}
+
+/// A source that values can be unpacked from asynchronously.
+///
+/// This is implemented for every `tokio::io::AsyncRead`, and for a
+/// `futures-io` `AsyncRead` wrapped in [`FuturesIo`]. Unpacking never reads
+/// past the end of the value, so the rest is left to the next read; wrapping
+/// an unbuffered source in a `BufReader` saves on many small reads.
+pub trait AsyncUnpackSource {
+    /// Attempt to read bytes into `buf`, returning how many were read. Zero
+    /// means the end of the input.
+    fn poll_read_packed(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<IoResult<usize>>;
+}
+
+#[cfg(feature = "tokio")]
+impl<W: tokio::io::AsyncWrite + ?Sized> AsyncPackSink for W {
+    fn poll_write_packed(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<IoResult<usize>> {
+        self.poll_write(cx, buf)
+    }
+
+    fn poll_flush_packed(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncRead + ?Sized> AsyncUnpackSource for R {
+    fn poll_read_packed(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<IoResult<usize>> {
+        let mut buf = tokio::io::ReadBuf::new(buf);
+        ready!(self.poll_read(cx, &mut buf))?;
+        Poll::Ready(Ok(buf.filled().len()))
+    }
+}
+
+/// Adapter that makes a `futures-io` `AsyncWrite` or `AsyncRead`, as used by
+/// smol and async-std, usable with [`AsyncPackTo`] and [`AsyncUnpackFrom`].
+/// # Example
+/// ```rust
+/// use byteorder_pack::{AsyncPackTo, AsyncUnpackFrom, FuturesIo};
+/// use futures_util::FutureExt;
+///
+/// let mut dst = FuturesIo::new(vec![]);
+/// 0x0102u16.pack_to_le_async(&mut dst).now_or_never().unwrap().unwrap();
+///
+/// let mut src = FuturesIo::new(&dst.get_ref()[..]);
+/// let value = u16::unpack_from_le_async(&mut src).now_or_never().unwrap();
+///
+/// assert_eq!(value.unwrap(), 0x0102);
+/// ```
+#[cfg(feature = "futures")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FuturesIo<T>(T);
+
+#[cfg(feature = "futures")]
+impl<T> FuturesIo<T> {
+    /// Wrap a `futures-io` reader or writer.
+    pub fn new(inner: T) -> Self {
+        Self(inner)
+    }
+
+    /// Get a reference to the wrapped reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    /// Get a mutable reference to the wrapped reader or writer.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// Unwrap the reader or writer.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<W: futures_io::AsyncWrite + Unpin> AsyncPackSink for FuturesIo<W> {
+    fn poll_write_packed(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_write(cx, buf)
+    }
+
+    fn poll_flush_packed(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.get_mut().0).poll_flush(cx)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<R: futures_io::AsyncRead + Unpin> AsyncUnpackSource for FuturesIo<R> {
+    fn poll_read_packed(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_read(cx, buf)
+    }
+}
+
+/// Asynchronous counterpart of [`PackTo`], implemented for every `PackTo`
+/// type.
+///
+/// The value is packed into a buffer up front, then written out and flushed,
+/// so the returned future only borrows the destination.
+/// # Example
+/// ```rust
+/// use byteorder_pack::AsyncPackTo;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut dst = vec![];
+/// (1u8, 2u16).pack_to_be_async(&mut dst).await.unwrap();
+///
+/// assert_eq!(dst, [0x01, 0x00, 0x02]);
+/// # });
+/// ```
+pub trait AsyncPackTo: PackTo {
+    /// Pack `self` into `dst` in byte order `E`.
+    fn pack_to_async<'a, E: ByteOrder, W: AsyncPackSink + Unpin + ?Sized>(
+        &self,
+        dst: &'a mut W,
+    ) -> PackFuture<'a, W> {
+        let mut buf = Vec::new();
+        let error = self.pack_to::<E, _>(&mut buf).err();
+        PackFuture {
+            dst,
+            buf,
+            pos: 0,
+            error,
+        }
+    }
+
+    /// Pack `self` into `dst` in [`BigEndian`] order.
+    fn pack_to_be_async<'a, W: AsyncPackSink + Unpin + ?Sized>(
+        &self,
+        dst: &'a mut W,
+    ) -> PackFuture<'a, W> {
+        self.pack_to_async::<BigEndian, _>(dst)
+    }
+
+    /// Pack `self` into `dst` in [`LittleEndian`] order.
+    fn pack_to_le_async<'a, W: AsyncPackSink + Unpin + ?Sized>(
+        &self,
+        dst: &'a mut W,
+    ) -> PackFuture<'a, W> {
+        self.pack_to_async::<LittleEndian, _>(dst)
+    }
+}
+
+impl<T: PackTo> AsyncPackTo for T {}
+
+/// Asynchronous counterpart of [`UnpackFrom`], implemented for every
+/// `UnpackFrom` type.
+///
+/// Bytes are taken from the source only as the value needs them and kept in
+/// a buffer, which is decoded again only once the bytes the last attempt ran
+/// out at have all arrived. Values read in many small pieces, such as long
+/// collections of structs, may still be decoded several times over, so this
+/// suits messages rather than unbounded streams.
+/// # Example
+/// ```rust
+/// use byteorder_pack::AsyncUnpackFrom;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut src = &[0x01, 0x00, 0x02, 0xff][..];
+///
+/// let value = <(u8, u16)>::unpack_from_be_async(&mut src).await.unwrap();
+///
+/// assert_eq!(value, (1, 2));
+/// assert_eq!(src, [0xff]);
+/// # });
+/// ```
+pub trait AsyncUnpackFrom: UnpackFrom {
+    /// Unpack a value from `src` in byte order `E`.
+    fn unpack_from_async<E: ByteOrder, R: AsyncUnpackSource + Unpin + ?Sized>(
+        src: &mut R,
+    ) -> UnpackFuture<'_, Self, E, R> {
+        UnpackFuture {
+            src,
+            acc: Vec::new(),
+            filled: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Unpack a value from `src` in [`BigEndian`] order.
+    fn unpack_from_be_async<R: AsyncUnpackSource + Unpin + ?Sized>(
+        src: &mut R,
+    ) -> UnpackFuture<'_, Self, BigEndian, R> {
+        Self::unpack_from_async::<BigEndian, _>(src)
+    }
+
+    /// Unpack a value from `src` in [`LittleEndian`] order.
+    fn unpack_from_le_async<R: AsyncUnpackSource + Unpin + ?Sized>(
+        src: &mut R,
+    ) -> UnpackFuture<'_, Self, LittleEndian, R> {
+        Self::unpack_from_async::<LittleEndian, _>(src)
+    }
+}
+
+impl<T: UnpackFrom> AsyncUnpackFrom for T {}
+
+/// Future returned by the methods of [`AsyncPackTo`].
+#[must_use = "futures do nothing unless polled"]
+pub struct PackFuture<'a, W: ?Sized> {
+    dst: &'a mut W,
+    buf: Vec<u8>,
+    pos: usize,
+    error: Option<Error>,
+}
+
+impl<W: AsyncPackSink + Unpin + ?Sized> Future for PackFuture<'_, W> {
+    type Output = IoResult<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if let Some(e) = this.error.take() {
+            return Poll::Ready(Err(e));
+        }
+        while this.pos < this.buf.len() {
+            let dst = Pin::new(&mut *this.dst);
+            match ready!(dst.poll_write_packed(cx, &this.buf[this.pos..]))? {
+                0 => return Poll::Ready(Err(ErrorKind::WriteZero.into())),
+                n => this.pos += n,
+            }
+        }
+        Pin::new(&mut *this.dst).poll_flush_packed(cx)
+    }
+}
+
+/// Reader over the bytes of a value received so far, noting how many bytes
+/// in total a read past their end needs.
+struct Probe<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    needed: Option<usize>,
+}
+
+impl Read for Probe<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let n = buf.len().min(self.buf.len() - self.pos);
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        if n == 0 && !buf.is_empty() {
+            self.needed = Some(self.pos + buf.len());
+        }
+        Ok(n)
+    }
+}
+
+/// Future returned by the methods of [`AsyncUnpackFrom`].
+#[must_use = "futures do nothing unless polled"]
+pub struct UnpackFuture<'a, T, E, R: ?Sized> {
+    src: &'a mut R,
+    /// Buffer for the bytes of the value, sized to what the next attempt at
+    /// decoding needs.
+    acc: Vec<u8>,
+    /// Bytes of `acc` read from `src` so far.
+    filled: usize,
+    _marker: PhantomData<fn() -> (T, E)>,
+}
+
+impl<T, E, R> Future for UnpackFuture<'_, T, E, R>
+where
+    T: UnpackFrom,
+    E: ByteOrder,
+    R: AsyncUnpackSource + Unpin + ?Sized,
+{
+    type Output = IoResult<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        loop {
+            while this.filled < this.acc.len() {
+                let buf = &mut this.acc[this.filled..];
+                match ready!(Pin::new(&mut *this.src).poll_read_packed(cx, buf))? {
+                    0 => return Poll::Ready(Err(ErrorKind::UnexpectedEof.into())),
+                    n => this.filled += n,
+                }
+            }
+            let mut probe = Probe {
+                buf: &this.acc,
+                pos: 0,
+                needed: None,
+            };
+            match (T::unpack_from::<E, _>(&mut probe), probe.needed) {
+                (Err(e), Some(needed)) if e.kind() == ErrorKind::UnexpectedEof => {
+                    this.acc.resize(needed, 0);
+                }
+                (result, _) => {
+                    this.acc.clear();
+                    this.filled = 0;
+                    return Poll::Ready(result);
+                }
+            }
+        }
+    }
+}
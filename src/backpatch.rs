@@ -0,0 +1,180 @@
+use std::io::{Error as IoError, ErrorKind, Result as IoResult, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{FixedPackSize, LengthPrefix, PackTo};
+
+/// A slot reserved in a `Write + Seek` destination, to be filled in once the
+/// value is known.
+///
+/// This is typically used for length or count fields that precede a body
+/// whose size is only known after it has been packed.
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder::BigEndian;
+/// use byteorder_pack::{PackTo, Placeholder};
+///
+/// let mut cursor = Cursor::new(vec![]);
+///
+/// let len = Placeholder::<u16>::reserve(&mut cursor).unwrap();
+/// (1u8, 2u32).pack_to_be(&mut cursor).unwrap();
+/// len.patch_len::<BigEndian, _>(&mut cursor).unwrap();
+/// 3u8.pack_to_be(&mut cursor).unwrap();
+///
+/// assert_eq!(cursor.get_ref(), &[0x00, 0x05, 0x01, 0x00, 0x00, 0x00, 0x02, 0x03]);
+///
+/// let len = Placeholder::<u16>::reserve(&mut cursor).unwrap();
+/// cursor.set_position(0);
+/// assert!(len.patch_len::<BigEndian, _>(&mut cursor).is_err());
+/// ```
+#[derive(Debug)]
+#[must_use = "a reserved placeholder must be patched"]
+pub struct Placeholder<L> {
+    offset: u64,
+    _marker: PhantomData<L>,
+}
+
+impl<L: PackTo + FixedPackSize> Placeholder<L> {
+    /// Reserve space for an `L` at the current position of `dst`, filling it
+    /// with zeros for now.
+    pub fn reserve<W: Write + Seek + ?Sized>(dst: &mut W) -> IoResult<Self> {
+        let offset = dst.stream_position()?;
+        dst.write_all(&vec![0; L::PACKED_SIZE])?;
+        Ok(Self {
+            offset,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Offset of the reserved slot in the destination.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Offset just past the reserved slot, where the body starts.
+    pub fn body_offset(&self) -> u64 {
+        self.offset + L::PACKED_SIZE as u64
+    }
+
+    /// Write `value` into the reserved slot, then return to the current
+    /// position of `dst`.
+    pub fn patch<E: ByteOrder, W: Write + Seek + ?Sized>(
+        self,
+        dst: &mut W,
+        value: L,
+    ) -> IoResult<()> {
+        let end = dst.stream_position()?;
+        dst.seek(SeekFrom::Start(self.offset))?;
+        value.pack_to::<E, _>(dst)?;
+        dst.seek(SeekFrom::Start(end))?;
+        Ok(())
+    }
+}
+
+impl<L: PackTo + FixedPackSize + LengthPrefix> Placeholder<L> {
+    /// Fill the reserved slot with the number of bytes written after it, and
+    /// return that number.
+    ///
+    /// Fails with [`ErrorKind::InvalidInput`] if `dst` is positioned before the
+    /// end of the slot.
+    pub fn patch_len<E: ByteOrder, W: Write + Seek + ?Sized>(self, dst: &mut W) -> IoResult<u64> {
+        let len = dst
+            .stream_position()?
+            .checked_sub(self.body_offset())
+            .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "positioned before the slot"))?;
+        let value = L::from_usize(len.to_usize()?)?;
+        self.patch::<E, _>(dst, value)?;
+        Ok(len)
+    }
+}
+
+/// Writes nested sections, each preceded by its byte length as an `L` in
+/// byte order `E`, EBML-style.
+///
+/// Length fields are reserved by [`begin_section`](Self::begin_section) and
+/// backpatched by the matching [`end_section`](Self::end_section), so the
+/// body of a section can be written without knowing its size up front. The
+/// builder implements [`Write`], so values can be packed into it directly.
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder_pack::{PackTo, SectionBuilder};
+///
+/// let mut builder = SectionBuilder::<_, u8>::new(Cursor::new(vec![]));
+///
+/// builder.begin_section().unwrap();
+/// 0x01u8.pack_to_be(&mut builder).unwrap();
+/// builder.begin_section().unwrap();
+/// 0x0203u16.pack_to_be(&mut builder).unwrap();
+/// builder.end_section().unwrap();
+/// builder.end_section().unwrap();
+///
+/// let data = builder.finish().unwrap().into_inner();
+/// assert_eq!(data, vec![0x04, 0x01, 0x02, 0x02, 0x03]);
+/// ```
+#[derive(Debug)]
+pub struct SectionBuilder<W, L = u32, E = BigEndian> {
+    inner: W,
+    open: Vec<Placeholder<L>>,
+    _marker: PhantomData<E>,
+}
+
+impl<W, L, E> SectionBuilder<W, L, E>
+where
+    W: Write + Seek,
+    L: PackTo + FixedPackSize + LengthPrefix,
+    E: ByteOrder,
+{
+    /// Create a builder writing to `inner`, starting at its current position.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            open: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Open a new section nested in the current one.
+    pub fn begin_section(&mut self) -> IoResult<()> {
+        let slot = Placeholder::reserve(&mut self.inner)?;
+        self.open.push(slot);
+        Ok(())
+    }
+
+    /// Close the innermost open section, writing its length header, and
+    /// return that length.
+    pub fn end_section(&mut self) -> IoResult<u64> {
+        let slot = self
+            .open
+            .pop()
+            .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "no open section"))?;
+        slot.patch_len::<E, _>(&mut self.inner)
+    }
+
+    /// Number of currently open sections.
+    pub fn depth(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Finish building, returning the underlying writer.
+    ///
+    /// Fails with [`ErrorKind::InvalidInput`] if any section is still open.
+    pub fn finish(self) -> IoResult<W> {
+        if !self.open.is_empty() {
+            return Err(IoError::new(ErrorKind::InvalidInput, "section left open"));
+        }
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write, L, E> Write for SectionBuilder<W, L, E> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.inner.flush()
+    }
+}
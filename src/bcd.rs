@@ -0,0 +1,116 @@
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// An unsigned integer packed as binary-coded decimal, two digits per byte,
+/// in the same number of bytes as the integer, as used by smart cards,
+/// telecom formats and real-time clock chips.
+///
+/// The digits are packed like an integer of the same width whose hex digits
+/// are the decimal digits, so the most significant pair comes first in
+/// big-endian order and last in little-endian order.
+///
+/// Packing fails with [`ErrorKind::InvalidInput`] if the value has too many
+/// digits, such as 100 for a `u8`, and unpacking fails with
+/// [`ErrorKind::InvalidData`] on a digit greater than 9.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{Bcd, PackTo, UnpackFrom};
+///
+/// let mut buf = vec![];
+/// Bcd(59u8).pack_to_be(&mut buf).unwrap();
+/// Bcd(2024u16).pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0x59, 0x20, 0x24]);
+///
+/// let mut src = &buf[..];
+/// assert_eq!(Bcd::<u8>::unpack_from_be(&mut src).unwrap(), Bcd(59));
+/// assert_eq!(Bcd::<u16>::unpack_from_be(&mut src).unwrap(), Bcd(2024));
+///
+/// assert!(Bcd(100u8).pack_to_be(&mut vec![]).is_err());
+/// assert!(Bcd::<u8>::unpack_from_be(&mut &[0x5a][..]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bcd<T>(pub T);
+
+impl<T> Bcd<T> {
+    /// Unwrap the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Bcd<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+macro_rules! impl_bcd {
+    ($($int:ty => $max:expr),+) => {
+        $(
+            impl Bcd<$int> {
+                /// The largest value that fits, with two digits per byte.
+                pub const MAX: Self = Self($max);
+            }
+
+            #[cfg(feature = "arbitrary")]
+            impl<'a> arbitrary::Arbitrary<'a> for Bcd<$int> {
+                fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                    u.int_in_range(0..=$max).map(Self)
+                }
+
+                fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                    <$int as arbitrary::Arbitrary>::size_hint(depth)
+                }
+            }
+
+            impl PackTo for Bcd<$int> {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    if self.0 > $max {
+                        return Err(IoError::new(
+                            ErrorKind::InvalidInput,
+                            "value has too many digits for BCD",
+                        ));
+                    }
+                    let (mut value, mut bcd, mut shift) = (self.0, 0 as $int, 0);
+                    while value > 0 {
+                        bcd |= (value % 10) << shift;
+                        value /= 10;
+                        shift += 4;
+                    }
+                    bcd.pack_to::<E, _>(dst)
+                }
+            }
+
+            impl UnpackFrom for Bcd<$int> {
+                fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+                    let bcd = <$int>::unpack_from::<E, _>(src)?;
+                    let mut value = 0 as $int;
+                    for shift in (0..<$int>::BITS).step_by(4).rev() {
+                        let digit = (bcd >> shift) & 0xf;
+                        if digit > 9 {
+                            return Err(IoError::new(
+                                ErrorKind::InvalidData,
+                                "BCD digit is greater than 9",
+                            ));
+                        }
+                        value = value * 10 + digit;
+                    }
+                    Ok(Self(value))
+                }
+            }
+
+            impl FixedPackSize for Bcd<$int> {
+                const PACKED_SIZE: usize = <$int>::BITS as usize / 8;
+            }
+        )+
+    };
+}
+
+impl_bcd!(
+    u8 => 99,
+    u16 => 9_999,
+    u32 => 99_999_999,
+    u64 => 9_999_999_999_999_999
+);
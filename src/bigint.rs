@@ -0,0 +1,100 @@
+use byteorder::ByteOrder;
+use num_bigint::{BigInt, BigUint, Sign};
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::len::{read_bytes, PackPrefixed, UnpackPrefixed};
+use crate::{PackTo, UnpackFrom};
+
+/// Packs the magnitude as its big-endian bytes, regardless of byte order,
+/// behind its byte count as a `u32` in the requested byte order. Zero has no
+/// bytes.
+///
+/// Use [`LengthPrefixed`](crate::LengthPrefixed) for a different length type.
+/// # Example
+/// ```rust
+/// use byteorder_pack::num_bigint::BigUint;
+/// use byteorder_pack::{PackTo, UnpackFrom};
+///
+/// let n = BigUint::from(0x0102_0304_05u64);
+/// let mut buf = vec![];
+/// n.pack_to_le(&mut buf).unwrap();
+/// assert_eq!(buf, [5, 0, 0, 0, 0x01, 0x02, 0x03, 0x04, 0x05]);
+///
+/// assert_eq!(BigUint::unpack_from_le(&mut &buf[..]).unwrap(), n);
+/// ```
+impl PackTo for BigUint {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        self.pack_prefixed::<E, u32, _>(dst)
+    }
+}
+
+impl UnpackFrom for BigUint {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        Self::unpack_prefixed::<E, u32, _>(src)
+    }
+}
+
+impl PackPrefixed for BigUint {
+    fn count(&self) -> usize {
+        self.bits().div_ceil(8) as usize
+    }
+
+    fn pack_elements<E, W>(&self, dst: &mut W) -> IoResult<()>
+    where
+        E: ByteOrder,
+        W: Write + ?Sized,
+    {
+        if self.bits() == 0 {
+            return Ok(());
+        }
+        dst.write_all(&self.to_bytes_be())
+    }
+}
+
+impl UnpackPrefixed for BigUint {
+    fn unpack_elements<E, R>(src: &mut R, count: usize) -> IoResult<Self>
+    where
+        E: ByteOrder,
+        R: Read + ?Sized,
+    {
+        Ok(BigUint::from_bytes_be(&read_bytes(src, count)?))
+    }
+}
+
+/// Packs a sign byte, 1 for negative numbers and 0 otherwise, followed by the
+/// magnitude packed as a [`BigUint`].
+/// # Example
+/// ```rust
+/// use byteorder_pack::num_bigint::BigInt;
+/// use byteorder_pack::{PackTo, UnpackFrom};
+///
+/// let n = BigInt::from(-0x1234);
+/// let mut buf = vec![];
+/// n.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [1, 0, 0, 0, 2, 0x12, 0x34]);
+///
+/// assert_eq!(BigInt::unpack_from_be(&mut &buf[..]).unwrap(), n);
+/// ```
+impl PackTo for BigInt {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        u8::from(self.sign() == Sign::Minus).pack_to::<E, _>(dst)?;
+        self.magnitude().pack_to::<E, _>(dst)
+    }
+}
+
+impl UnpackFrom for BigInt {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let sign = match u8::unpack_from::<E, _>(src)? {
+            0 => Sign::Plus,
+            1 => Sign::Minus,
+            _ => {
+                return Err(IoError::new(
+                    ErrorKind::InvalidData,
+                    "sign byte is not 0 or 1",
+                ))
+            }
+        };
+        let magnitude = BigUint::unpack_from::<E, _>(src)?;
+        Ok(BigInt::from_biguint(sign, magnitude))
+    }
+}
@@ -0,0 +1,46 @@
+//! Dump or patch binary files laid out as a Python `struct` format string.
+//!
+//! ```text
+//! byteorder-pack-cli --format FORMAT dump [--offset N] [--count N] FILE
+//! byteorder-pack-cli --format FORMAT patch [--offset N] FILE PATH VALUE
+//! ```
+//!
+//! Fields are named by their position in the format, so `--format '<HI4s'`
+//! has fields `0`, `1` and `2`.
+
+use std::env;
+use std::process::ExitCode;
+
+use byteorder_pack::{cli, Format};
+
+const USAGE: &str = "\
+usage: byteorder-pack-cli --format FORMAT dump [--offset N] [--count N] FILE
+       byteorder-pack-cli --format FORMAT patch [--offset N] FILE PATH VALUE";
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let format = match (args.next().as_deref(), args.next()) {
+        (Some("--format"), Some(format)) => format,
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let format = match format.parse::<Format>() {
+        Ok(format) => format,
+        Err(e) => {
+            eprintln!("error: invalid format: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match cli::run(&format.schema(), format.is_little_endian(), args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            if e.kind() == std::io::ErrorKind::InvalidInput {
+                eprintln!("{}", USAGE);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
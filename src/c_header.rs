@@ -0,0 +1,218 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+use byteorder::ByteOrder;
+
+use crate::schema::{identifier, is_little_endian};
+use crate::Schema;
+
+const C_KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "auto",
+    "bool",
+    "break",
+    "case",
+    "char",
+    "const",
+    "constexpr",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "nullptr",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "struct",
+    "switch",
+    "thread_local",
+    "true",
+    "typedef",
+    "typeof",
+    "typeof_unqual",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+];
+
+/// Generate a C header declaring `struct <name>` with the layout of `schema`
+/// in byte order `E`, so C code can share a single Rust definition.
+///
+/// The structs are declared inside `#pragma pack(push, 1)` so the compiler
+/// inserts no padding, and a `_Static_assert` checks the total size. C has no
+/// byte order annotations, so every multi-byte field is commented with its
+/// offset and byte order and must be converted on hosts of the other order.
+/// Nested structs are declared first as `struct <name>_<field>`, and 128-bit
+/// integers, which C lacks, are declared as 16-byte arrays. Names that are C
+/// keywords get a trailing `_`. C has no zero-sized members either, so empty
+/// fields are left out with a comment in their place, and a schema with no
+/// bytes at all declares no struct.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::{c_header, Schema};
+///
+/// let header = Schema::Struct(vec![
+///     ("magic".into(), Schema::Bytes(4)),
+///     ("counts".into(), Schema::Array(Box::new(Schema::U16), 2)),
+/// ]);
+/// assert_eq!(
+///     c_header::<BigEndian>("header", &header),
+///     "\
+/// #ifndef HEADER_H
+/// #define HEADER_H
+///
+/// #include <stdint.h>
+///
+/// #pragma pack(push, 1)
+///
+/// struct header {
+///     uint8_t magic[4]; /* offset 0 */
+///     uint16_t counts[2]; /* offset 4, big-endian */
+/// };
+///
+/// #pragma pack(pop)
+///
+/// _Static_assert(sizeof(struct header) == 8, \"struct header must be 8 bytes\");
+///
+/// #endif
+/// ",
+/// );
+///
+/// let odd = Schema::Struct(vec![
+///     ("int".into(), Schema::U8),
+///     ("none".into(), Schema::Bytes(0)),
+///     ("unit".into(), Schema::Struct(vec![])),
+/// ]);
+/// let header = c_header::<BigEndian>("odd", &odd);
+/// assert!(header.contains("    uint8_t int_; /* offset 0 */\n    /* none: empty, offset 1 */\n"));
+/// assert!(!header.contains("struct odd_unit"));
+/// assert!(!c_header::<BigEndian>("unit", &Schema::Struct(vec![])).contains("struct unit"));
+/// ```
+pub fn c_header<E: ByteOrder>(name: &str, schema: &Schema) -> String {
+    let name = c_identifier(name);
+    let order = if is_little_endian::<E>() {
+        "little-endian"
+    } else {
+        "big-endian"
+    };
+    let guard = format!("{}_H", name.to_ascii_uppercase());
+    if schema.size() == 0 {
+        return format!(
+            "#ifndef {0}\n#define {0}\n\n/* {1} is empty, which C does not allow. */\n\n#endif\n",
+            guard, name
+        );
+    }
+    let mut structs = Vec::new();
+    declare(&name, schema, order, &mut structs);
+
+    let mut out = format!(
+        "#ifndef {0}\n#define {0}\n\n#include <stdint.h>\n\n#pragma pack(push, 1)\n\n",
+        guard
+    );
+    for decl in structs {
+        out.push_str(&decl);
+        out.push('\n');
+    }
+    let _ = write!(
+        out,
+        "#pragma pack(pop)\n\n\
+         _Static_assert(sizeof(struct {0}) == {1}, \"struct {0} must be {1} bytes\");\n\n\
+         #endif\n",
+        name,
+        schema.size()
+    );
+    out
+}
+
+/// Push the declaration of `struct <name>` and of the structs it contains,
+/// innermost first. Non-struct schemas become a struct with one field named
+/// `value`. `schema` must not be empty.
+fn declare(name: &str, schema: &Schema, order: &str, structs: &mut Vec<String>) {
+    let value = [(String::from("value"), schema.clone())];
+    let fields = match schema {
+        Schema::Struct(fields) => &fields[..],
+        _ => &value[..],
+    };
+    let mut body = format!("struct {} {{\n", name);
+    let mut offset = 0usize;
+    for (field_name, field) in fields {
+        let id = c_identifier(field_name);
+        if field.size() == 0 {
+            let _ = writeln!(body, "    /* {}: empty, offset {} */", id, offset);
+            continue;
+        }
+        let mut elem = field;
+        let mut dims = String::new();
+        while let Schema::Array(inner, n) = elem {
+            let _ = write!(dims, "[{}]", n);
+            elem = inner;
+        }
+        let (ty, multi_byte) = match elem {
+            Schema::U8 => ("uint8_t".into(), false),
+            Schema::I8 => ("int8_t".into(), false),
+            Schema::U16 => ("uint16_t".into(), true),
+            Schema::I16 => ("int16_t".into(), true),
+            Schema::U32 => ("uint32_t".into(), true),
+            Schema::I32 => ("int32_t".into(), true),
+            Schema::U64 => ("uint64_t".into(), true),
+            Schema::I64 => ("int64_t".into(), true),
+            Schema::F32 => ("float".into(), true),
+            Schema::F64 => ("double".into(), true),
+            Schema::U128 | Schema::I128 => {
+                dims.push_str("[16]");
+                ("uint8_t".into(), true)
+            }
+            Schema::Bytes(n) => {
+                let _ = write!(dims, "[{}]", n);
+                ("uint8_t".into(), false)
+            }
+            Schema::Struct(_) | Schema::Array(..) => {
+                let nested = format!("{}_{}", name, id);
+                declare(&nested, elem, order, structs);
+                (format!("struct {}", nested), false)
+            }
+        };
+        let _ = write!(body, "    {} {}{}; /* offset {}", ty, id, dims, offset);
+        if multi_byte {
+            let _ = write!(body, ", {}", order);
+        }
+        if let Schema::U128 | Schema::I128 = elem {
+            let _ = write!(body, ", {}", elem.type_name().unwrap());
+        }
+        body.push_str(" */\n");
+        offset = offset.saturating_add(field.size());
+    }
+    body.push_str("};\n");
+    structs.push(body);
+}
+
+/// `name` as a C identifier, with a trailing `_` if it is a keyword.
+fn c_identifier(name: &str) -> String {
+    let mut id = identifier(name);
+    if C_KEYWORDS.contains(&id.as_str()) {
+        id.push('_');
+    }
+    id
+}
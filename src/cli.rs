@@ -0,0 +1,267 @@
+//! Field-by-field inspection and patching of binary files, enabled by the
+//! `cli` feature.
+//!
+//! [`dump`] and [`patch`] work from any [`Schema`], so they can be driven by
+//! a type's [`Describe`] impl or by a format string. [`main`] wraps them in a
+//! command line interface for a single record type:
+//!
+//! ```text
+//! dump [--le | --be] [--offset N] [--count N] FILE
+//! patch [--le | --be] [--offset N] FILE PATH VALUE
+//! ```
+//!
+//! The `byteorder-pack-cli` binary does the same for a layout given as a
+//! Python `struct` format string with `--format`.
+//! # Example
+//! ```rust,no_run
+//! use std::process::ExitCode;
+//!
+//! fn main() -> ExitCode {
+//!     // `$ header-tool patch --le header.bin 1[0] 513`
+//!     byteorder_pack::cli::main::<(u8, [u16; 2])>()
+//! }
+//! ```
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::process::ExitCode;
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::{Describe, PackTo, Schema, Value};
+
+const USAGE: &str = "\
+usage: dump [--le | --be] [--offset N] [--count N] FILE
+       patch [--le | --be] [--offset N] FILE PATH VALUE";
+
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+fn position(base: u64, offset: u64) -> io::Result<u64> {
+    base.checked_add(offset)
+        .ok_or_else(|| invalid("offset too large"))
+}
+
+/// Every primitive field of `schema` with its path and offset.
+fn fields(schema: &Schema) -> Vec<(String, usize, Schema)> {
+    let mut fields = Vec::new();
+    schema.for_each_field(|path, offset, field| {
+        fields.push((path.to_string(), offset, field.clone()));
+    });
+    fields
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::U8(v) => v.to_string(),
+        Value::I8(v) => v.to_string(),
+        Value::U16(v) => v.to_string(),
+        Value::I16(v) => v.to_string(),
+        Value::U32(v) => v.to_string(),
+        Value::I32(v) => v.to_string(),
+        Value::U64(v) => v.to_string(),
+        Value::I64(v) => v.to_string(),
+        Value::U128(v) => v.to_string(),
+        Value::I128(v) => v.to_string(),
+        Value::F32(v) => v.to_string(),
+        Value::F64(v) => v.to_string(),
+        Value::Bytes(bytes) => bytes.iter().fold(String::new(), |mut s, b| {
+            let _ = write!(s, "{:02x}", b);
+            s
+        }),
+        Value::Array(_) | Value::Struct(_) => format!("{:?}", value),
+    }
+}
+
+fn parse(schema: &Schema, text: &str) -> Option<Value> {
+    Some(match schema {
+        Schema::U8 => Value::U8(text.parse().ok()?),
+        Schema::I8 => Value::I8(text.parse().ok()?),
+        Schema::U16 => Value::U16(text.parse().ok()?),
+        Schema::I16 => Value::I16(text.parse().ok()?),
+        Schema::U32 => Value::U32(text.parse().ok()?),
+        Schema::I32 => Value::I32(text.parse().ok()?),
+        Schema::U64 => Value::U64(text.parse().ok()?),
+        Schema::I64 => Value::I64(text.parse().ok()?),
+        Schema::U128 => Value::U128(text.parse().ok()?),
+        Schema::I128 => Value::I128(text.parse().ok()?),
+        Schema::F32 => Value::F32(text.parse().ok()?),
+        Schema::F64 => Value::F64(text.parse().ok()?),
+        Schema::Bytes(n) => {
+            let digits: Vec<u8> = text.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+            if digits.len() != n.checked_mul(2)? {
+                return None;
+            }
+            let bytes = digits
+                .chunks(2)
+                .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+                .collect::<Option<Vec<u8>>>()?;
+            Value::Bytes(bytes)
+        }
+        Schema::Array(..) | Schema::Struct(_) => return None,
+    })
+}
+
+/// Write one line per primitive field of a record laid out as `schema` and
+/// read from `src`, giving its offset from `base`, path, type and value.
+///
+/// [`Schema::Bytes`] fields are shown as hex.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::{cli, Describe};
+///
+/// let data = [0x01, 0x00, 0x02, 0x00, 0x03];
+/// let mut out = vec![];
+/// cli::dump::<BigEndian, _, _>(&<(u8, [u16; 2])>::schema(), &mut &data[..], 0, &mut out).unwrap();
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     "00000000  0: u8 = 1\n00000001  1[0]: u16 = 2\n00000003  1[1]: u16 = 3\n",
+/// );
+/// ```
+pub fn dump<E, R, W>(schema: &Schema, src: &mut R, base: u64, out: &mut W) -> io::Result<()>
+where
+    E: ByteOrder,
+    R: Read + ?Sized,
+    W: Write + ?Sized,
+{
+    for (path, offset, field) in fields(schema) {
+        let value = field.interpret::<E, _>(src)?;
+        writeln!(
+            out,
+            "{:08x}  {}: {} = {}",
+            position(base, offset as u64)?,
+            path,
+            field.type_name().unwrap_or_default(),
+            display(&value)
+        )?;
+    }
+    Ok(())
+}
+
+/// Overwrite the field at `path` of the record laid out as `schema` at offset
+/// `base` of `dst` with `value`, parsed as the field's type.
+///
+/// Integers and floats are given in decimal and [`Schema::Bytes`] fields as
+/// exactly two hex digits per byte. Nothing is written if the path does not
+/// name a primitive field or the value does not parse.
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder_pack::byteorder::LittleEndian;
+/// use byteorder_pack::{cli, Describe};
+///
+/// let mut file = Cursor::new(vec![0u8; 9]);
+/// let schema = <(u8, [u16; 2])>::schema();
+/// cli::patch::<LittleEndian, _>(&schema, &mut file, 4, "1[1]", "513").unwrap();
+/// assert_eq!(file.get_ref(), &[0, 0, 0, 0, 0, 0, 0, 0x01, 0x02]);
+/// assert!(cli::patch::<LittleEndian, _>(&schema, &mut file, u64::MAX, "1[1]", "1").is_err());
+/// ```
+pub fn patch<E, F>(
+    schema: &Schema,
+    dst: &mut F,
+    base: u64,
+    path: &str,
+    value: &str,
+) -> io::Result<()>
+where
+    E: ByteOrder,
+    F: Write + Seek + ?Sized,
+{
+    let (_, offset, field) = fields(schema)
+        .into_iter()
+        .find(|(p, _, _)| p == path)
+        .ok_or_else(|| invalid("no such field"))?;
+    let value = parse(&field, value).ok_or_else(|| invalid("invalid value for field"))?;
+    dst.seek(SeekFrom::Start(position(base, offset as u64)?))?;
+    value.pack_to::<E, _>(dst)
+}
+
+fn parse_number(text: Option<String>) -> io::Result<u64> {
+    let text = text.ok_or_else(|| invalid("missing number"))?;
+    match text.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => text.parse(),
+    }
+    .map_err(|_| invalid("invalid number"))
+}
+
+/// Run the `dump` or `patch` command described in the [module docs](self)
+/// against records laid out as `schema`, in little-endian byte order by
+/// default if `little_endian` is set and big-endian otherwise.
+///
+/// `args` excludes the program name. `dump` writes to standard output.
+pub fn run<I: IntoIterator<Item = String>>(
+    schema: &Schema,
+    little_endian: bool,
+    args: I,
+) -> io::Result<()> {
+    let mut args = args.into_iter();
+    let command = args.next().ok_or_else(|| invalid("missing command"))?;
+    let mut little_endian = little_endian;
+    let mut offset = 0;
+    let mut count = 1;
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--le" => little_endian = true,
+            "--be" => little_endian = false,
+            "--offset" => offset = parse_number(args.next())?,
+            "--count" => count = parse_number(args.next())?,
+            _ if arg.starts_with("--") => return Err(invalid("unknown option")),
+            _ => positional.push(arg),
+        }
+    }
+
+    match (command.as_str(), &positional[..]) {
+        ("dump", [file]) => {
+            let mut src = BufReader::new(File::open(file)?);
+            src.seek(SeekFrom::Start(offset))?;
+            let size = schema.size() as u64;
+            let mut out = io::stdout().lock();
+            for i in 0..count {
+                if i > 0 {
+                    writeln!(out)?;
+                }
+                let base = i
+                    .checked_mul(size)
+                    .ok_or_else(|| invalid("offset too large"))
+                    .and_then(|start| position(offset, start))?;
+                if little_endian {
+                    dump::<LittleEndian, _, _>(schema, &mut src, base, &mut out)?;
+                } else {
+                    dump::<BigEndian, _, _>(schema, &mut src, base, &mut out)?;
+                }
+            }
+            Ok(())
+        }
+        ("patch", [file, path, value]) => {
+            let mut dst = OpenOptions::new().write(true).open(file)?;
+            if little_endian {
+                patch::<LittleEndian, _>(schema, &mut dst, offset, path, value)
+            } else {
+                patch::<BigEndian, _>(schema, &mut dst, offset, path, value)
+            }
+        }
+        ("dump" | "patch", _) => Err(invalid("wrong number of arguments")),
+        _ => Err(invalid("unknown command")),
+    }
+}
+
+/// Run the command line interface for records of type `T` with the process
+/// arguments, printing errors and usage to standard error.
+pub fn main<T: Describe>() -> ExitCode {
+    match run(&T::schema(), false, env::args().skip(1)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            if e.kind() == io::ErrorKind::InvalidInput {
+                eprintln!("{}", USAGE);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
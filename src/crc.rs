@@ -0,0 +1,88 @@
+use crate::io::{Result as IoResult, Write};
+
+const TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Compute the CRC-32 (IEEE 802.3, as used by zlib and PNG) of `bytes`.
+/// # Example
+/// ```rust
+/// use byteorder_pack::crc32;
+///
+/// assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+/// ```
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(bytes);
+    crc.finish()
+}
+
+/// Incremental CRC-32 (IEEE 802.3) computation.
+///
+/// `Crc32` implements [`Write`], so the checksum of a value's packed form can
+/// be computed without buffering it.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{Crc32, PackTo};
+///
+/// let mut crc = Crc32::new();
+/// (0x3132u16, 0x33u8).pack_to_be(&mut crc).unwrap();
+///
+/// assert_eq!(crc.finish(), byteorder_pack::crc32(b"123"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    /// Start a new checksum.
+    pub const fn new() -> Self {
+        Self { state: !0 }
+    }
+
+    /// Add `bytes` to the checksum.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.state = TABLE[((self.state ^ b as u32) & 0xFF) as usize] ^ (self.state >> 8);
+        }
+    }
+
+    /// The checksum of all bytes added so far.
+    pub fn finish(&self) -> u32 {
+        !self.state
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for Crc32 {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::ffi::CString;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::len::utf8_error;
+use crate::{PackTo, UnpackFrom, DEFAULT_MAX_FRAME_LEN};
+
+/// Read bytes from `src` up to and excluding a NUL, failing if there are more
+/// than `max_len` before it.
+fn read_until_nul<R: Read + ?Sized>(src: &mut R, max_len: usize) -> IoResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    loop {
+        let mut byte = [0];
+        src.read_exact(&mut byte)?;
+        match byte[0] {
+            0 => return Ok(bytes),
+            _ if bytes.len() == max_len => {
+                return Err(IoError::new(
+                    ErrorKind::InvalidData,
+                    "NUL-terminated string exceeds maximum length",
+                ))
+            }
+            b => bytes.push(b),
+        }
+    }
+}
+
+/// Write `bytes` and a NUL to `dst`, failing if `bytes` contain a NUL.
+fn write_with_nul<W: Write + ?Sized>(dst: &mut W, bytes: &[u8]) -> IoResult<()> {
+    if bytes.contains(&0) {
+        return Err(IoError::new(
+            ErrorKind::InvalidInput,
+            "NUL-terminated string contains a NUL byte",
+        ));
+    }
+    dst.write_all(bytes)?;
+    dst.write_all(&[0])
+}
+
+/// Packs the bytes followed by the NUL.
+impl PackTo for CString {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        self.as_c_str().pack_to::<E, _>(dst)
+    }
+}
+
+/// Unpacks bytes up to and including a NUL, of which there may be at most
+/// [`DEFAULT_MAX_FRAME_LEN`] before it. [`NulTerminated`] sets another limit.
+impl UnpackFrom for CString {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        NulTerminated::<CString>::unpack_from::<E, _>(src).map(NulTerminated::into_inner)
+    }
+}
+
+/// A string or byte blob packed followed by a NUL, as C strings are.
+///
+/// Packing fails with [`ErrorKind::InvalidInput`] if the value itself holds a
+/// NUL. Unpacking reads up to the NUL, failing with [`ErrorKind::InvalidData`]
+/// if there are more than `MAX_LEN` bytes before it, or for a `String`, if
+/// they are not UTF-8.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{NulTerminated, PackTo, UnpackFrom};
+///
+/// let name = NulTerminated::<String>::new("eth0".into());
+/// let mut buf = vec![];
+/// name.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, b"eth0\0");
+///
+/// assert_eq!(NulTerminated::unpack_from_be(&mut &buf[..]).unwrap(), name);
+/// assert!(NulTerminated::<String, 3>::unpack_from_be(&mut &buf[..]).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct NulTerminated<T = String, const MAX_LEN: usize = DEFAULT_MAX_FRAME_LEN>(pub T);
+
+impl<T, const MAX_LEN: usize> NulTerminated<T, MAX_LEN> {
+    /// Wrap a string.
+    pub fn new(inner: T) -> Self {
+        Self(inner)
+    }
+
+    /// Unwrap the string.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<const MAX_LEN: usize> PackTo for NulTerminated<String, MAX_LEN> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        write_with_nul(dst, self.0.as_bytes())
+    }
+}
+
+impl<const MAX_LEN: usize> UnpackFrom for NulTerminated<String, MAX_LEN> {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let bytes = read_until_nul(src, MAX_LEN)?;
+        String::from_utf8(bytes)
+            .map(Self)
+            .map_err(|e| utf8_error(e.utf8_error()))
+    }
+}
+
+impl<const MAX_LEN: usize> PackTo for NulTerminated<Vec<u8>, MAX_LEN> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        write_with_nul(dst, &self.0)
+    }
+}
+
+impl<const MAX_LEN: usize> UnpackFrom for NulTerminated<Vec<u8>, MAX_LEN> {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        read_until_nul(src, MAX_LEN).map(Self)
+    }
+}
+
+impl<const MAX_LEN: usize> PackTo for NulTerminated<CString, MAX_LEN> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        self.0.pack_to::<E, _>(dst)
+    }
+}
+
+impl<const MAX_LEN: usize> UnpackFrom for NulTerminated<CString, MAX_LEN> {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let mut bytes = read_until_nul(src, MAX_LEN)?;
+        bytes.push(0);
+        CString::from_vec_with_nul(bytes)
+            .map(Self)
+            .map_err(|_| IoError::new(ErrorKind::InvalidData, "C string has an interior NUL"))
+    }
+}
@@ -0,0 +1,55 @@
+use byteorder::{ByteOrder, LittleEndian};
+use rust_decimal::Decimal;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// The flag bits in use: the sign in bit 31 and the scale in bits 16 to 23.
+const FLAGS_MASK: u32 = 0x80ff_0000;
+
+/// Packs four `u32`s in the requested byte order: the flags, holding the scale
+/// in bits 16 to 23 and the sign in bit 31, then the low, middle and high
+/// words of the 96-bit mantissa.
+///
+/// Little-endian, this is the layout of [`Decimal::serialize`]. Unpacking
+/// fails on unused flag bits or a scale greater than 28.
+/// # Example
+/// ```rust
+/// use byteorder_pack::rust_decimal::Decimal;
+/// use byteorder_pack::{PackTo, UnpackFrom};
+///
+/// let price = Decimal::new(-12345, 2);
+/// let mut buf = vec![];
+/// price.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf[..4], [0x80, 0x02, 0x00, 0x00]);
+/// assert_eq!(buf[4..8], [0x00, 0x00, 0x30, 0x39]);
+///
+/// assert_eq!(Decimal::unpack_from_be(&mut &buf[..]).unwrap(), price);
+/// ```
+impl PackTo for Decimal {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        let mut words = [0u32; 4];
+        LittleEndian::read_u32_into(&self.serialize(), &mut words);
+        words.pack_to::<E, _>(dst)
+    }
+}
+
+impl UnpackFrom for Decimal {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let words = <[u32; 4]>::unpack_from::<E, _>(src)?;
+        let flags = words[0];
+        if flags & !FLAGS_MASK != 0 || (flags >> 16) & 0xff > Decimal::MAX_SCALE {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "decimal has invalid flags",
+            ));
+        }
+        let mut bytes = [0u8; 16];
+        LittleEndian::write_u32_into(&words, &mut bytes);
+        Ok(Decimal::deserialize(bytes))
+    }
+}
+
+impl FixedPackSize for Decimal {
+    const PACKED_SIZE: usize = 16;
+}
@@ -0,0 +1,288 @@
+use std::io::{BufRead, Error as IoError, ErrorKind, Result as IoResult, Write};
+use std::marker::PhantomData;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::frame::DEFAULT_MAX_FRAME_LEN;
+use crate::{PackTo, SliceReader, UnpackFrom};
+
+/// The byte sequence that terminates each frame, and how payload bytes that
+/// could be mistaken for it are escaped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delimiter {
+    bytes: Vec<u8>,
+    escape: Option<(u8, u8)>,
+}
+
+impl Delimiter {
+    /// Frames terminated by `bytes`, without escaping.
+    ///
+    /// Writing a payload that contains the delimiter fails.
+    /// # Panics
+    /// Panics if `bytes` is empty.
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        let bytes = bytes.into();
+        assert!(!bytes.is_empty(), "empty frame delimiter");
+        Self {
+            bytes,
+            escape: None,
+        }
+    }
+
+    /// Escape payload bytes equal to the first delimiter byte or to `escape`
+    /// by writing `escape` followed by the byte XORed with `xor`.
+    /// # Panics
+    /// Panics if `xor` is zero, if `escape` is the first delimiter byte, or if
+    /// `escape ^ xor` is, since escaped payloads could then still contain the
+    /// delimiter or not unescape to the original bytes.
+    /// # Example
+    /// ```rust,should_panic
+    /// use byteorder_pack::Delimiter;
+    ///
+    /// // 0x5E ^ 0x20 is the 0x7E delimiter.
+    /// Delimiter::new([0x7E]).with_escape(0x5E, 0x20);
+    /// ```
+    pub fn with_escape(mut self, escape: u8, xor: u8) -> Self {
+        let delimiter = self.bytes[0];
+        assert!(xor != 0, "zero frame escape XOR");
+        assert!(
+            escape != delimiter && escape ^ xor != delimiter,
+            "frame escape clashes with the delimiter"
+        );
+        self.escape = Some((escape, xor));
+        self
+    }
+
+    /// Frames terminated by `\r\n`.
+    pub fn crlf() -> Self {
+        Self::new(*b"\r\n")
+    }
+
+    /// HDLC-style framing: frames terminated by `0x7E`, with `0x7E` and `0x7D`
+    /// in the payload escaped as `0x7D` followed by the byte XOR `0x20`.
+    pub fn hdlc() -> Self {
+        Self::new([0x7E]).with_escape(0x7D, 0x20)
+    }
+
+    fn needs_escape(&self, b: u8) -> bool {
+        matches!(self.escape, Some((escape, _)) if b == escape || b == self.bytes[0])
+    }
+}
+
+/// Writes messages to `W`, each followed by a [`Delimiter`].
+/// # Example
+/// ```rust
+/// use byteorder_pack::{DelimitedWriter, Delimiter};
+///
+/// let mut frames = DelimitedWriter::<_>::new(vec![], Delimiter::hdlc());
+///
+/// frames.write_frame(&(0x017Eu16, 0x02u8)).unwrap();
+///
+/// assert_eq!(frames.into_inner(), vec![0x01, 0x7D, 0x5E, 0x02, 0x7E]);
+/// ```
+#[derive(Debug)]
+pub struct DelimitedWriter<W, E = BigEndian> {
+    inner: W,
+    delimiter: Delimiter,
+    buf: Vec<u8>,
+    _marker: PhantomData<E>,
+}
+
+impl<W: Write, E: ByteOrder> DelimitedWriter<W, E> {
+    /// Create a writer over `inner` terminating frames with `delimiter`.
+    pub fn new(inner: W, delimiter: Delimiter) -> Self {
+        Self {
+            inner,
+            delimiter,
+            buf: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pack `msg`, escape it if needed and write it followed by the delimiter.
+    pub fn write_frame<T: PackTo>(&mut self, msg: &T) -> IoResult<()> {
+        self.buf.clear();
+        msg.pack_to::<E, _>(&mut self.buf)?;
+        match self.delimiter.escape {
+            Some((escape, xor)) => {
+                let mut escaped = Vec::with_capacity(self.buf.len());
+                for &b in &self.buf {
+                    if self.delimiter.needs_escape(b) {
+                        escaped.extend_from_slice(&[escape, b ^ xor]);
+                    } else {
+                        escaped.push(b);
+                    }
+                }
+                self.buf = escaped;
+            }
+            None => {
+                let delimiter = &self.delimiter.bytes[..];
+                if self.buf.windows(delimiter.len()).any(|w| w == delimiter) {
+                    return Err(IoError::new(
+                        ErrorKind::InvalidInput,
+                        "payload contains the frame delimiter",
+                    ));
+                }
+            }
+        }
+        self.buf.extend_from_slice(&self.delimiter.bytes);
+        self.inner.write_all(&self.buf)
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Reads messages terminated by a [`Delimiter`], as written by a
+/// [`DelimitedWriter`].
+///
+/// Empty frames, such as the opening flag of HDLC or blank lines, are skipped.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{DelimitedReader, Delimiter};
+///
+/// let data = [0x7E, 0x01, 0x7D, 0x5E, 0x02, 0x7E];
+/// let mut frames = DelimitedReader::<_>::new(&data[..], Delimiter::hdlc());
+///
+/// assert_eq!(frames.read_frame::<(u16, u8)>().unwrap(), Some((0x017E, 0x02)));
+/// assert_eq!(frames.read_frame::<(u16, u8)>().unwrap(), None);
+/// ```
+#[derive(Debug)]
+pub struct DelimitedReader<R, E = BigEndian> {
+    inner: R,
+    delimiter: Delimiter,
+    buf: Vec<u8>,
+    max_len: usize,
+    _marker: PhantomData<E>,
+}
+
+impl<R: BufRead, E: ByteOrder> DelimitedReader<R, E> {
+    /// Create a reader over `inner` splitting frames on `delimiter` and
+    /// accepting frames of up to [`DEFAULT_MAX_FRAME_LEN`] bytes.
+    pub fn new(inner: R, delimiter: Delimiter) -> Self {
+        Self {
+            inner,
+            delimiter,
+            buf: Vec::new(),
+            max_len: DEFAULT_MAX_FRAME_LEN,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the maximum accepted size of an escaped frame in bytes, excluding
+    /// the delimiter.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Read and unpack the next frame.
+    ///
+    /// Returns `None` if the source ends cleanly after a delimiter.
+    pub fn read_frame<T: UnpackFrom>(&mut self) -> IoResult<Option<T>> {
+        loop {
+            if !self.fill_frame()? {
+                return Ok(None);
+            }
+            if !self.buf.is_empty() {
+                break;
+            }
+        }
+        if let Some((escape, xor)) = self.delimiter.escape {
+            let mut raw = self.buf.iter();
+            let mut unescaped = Vec::with_capacity(self.buf.len());
+            while let Some(&b) = raw.next() {
+                if b == escape {
+                    let b = raw.next().ok_or_else(|| {
+                        IoError::new(ErrorKind::InvalidData, "dangling escape byte in frame")
+                    })?;
+                    unescaped.push(b ^ xor);
+                } else {
+                    unescaped.push(b);
+                }
+            }
+            self.buf = unescaped;
+        }
+
+        let mut payload = SliceReader::new(&self.buf);
+        let msg = T::unpack_from::<E, _>(&mut payload)?;
+        if !payload.is_empty() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "trailing bytes in frame",
+            ));
+        }
+        Ok(Some(msg))
+    }
+
+    /// Read the next raw frame into `self.buf`, without its delimiter.
+    fn fill_frame(&mut self) -> IoResult<bool> {
+        let delimiter = &self.delimiter.bytes[..];
+        let last = delimiter[delimiter.len() - 1];
+        self.buf.clear();
+        loop {
+            let available = match self.inner.fill_buf() {
+                Ok(available) => available,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if available.is_empty() {
+                if self.buf.is_empty() {
+                    return Ok(false);
+                }
+                return Err(IoError::new(
+                    ErrorKind::UnexpectedEof,
+                    "source ended inside a frame",
+                ));
+            }
+            let (used, done) = match available.iter().position(|&b| b == last) {
+                Some(i) => {
+                    self.buf.extend_from_slice(&available[..=i]);
+                    (i + 1, self.buf.ends_with(delimiter))
+                }
+                None => {
+                    self.buf.extend_from_slice(available);
+                    (available.len(), false)
+                }
+            };
+            self.inner.consume(used);
+            if done {
+                self.buf.truncate(self.buf.len() - delimiter.len());
+                return Ok(true);
+            }
+            if self.buf.len() > self.max_len.saturating_add(delimiter.len()) {
+                return Err(IoError::new(
+                    ErrorKind::InvalidData,
+                    "frame exceeds maximum length",
+                ));
+            }
+        }
+    }
+
+    /// Get a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
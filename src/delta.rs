@@ -0,0 +1,115 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// An integer element of a slice or array that is packed as the difference
+/// from the previous element.
+///
+/// Packing a `&[Delta<T>]` or `[Delta<T>; N]` stores the first value as-is and
+/// each later value as its wrapping difference from the one before, and
+/// unpacking sums the differences back up. Monotonic columns such as
+/// timestamps and offsets become runs of small numbers, which compress well or
+/// can be re-encoded as varints. A lone `Delta` is packed like its value.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{Delta, LengthPrefixed, PackTo, UnpackFrom};
+///
+/// let timestamps = [Delta(1000u32), Delta(1003), Delta(1010)];
+/// let mut buf = vec![];
+/// timestamps.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0, 0, 0x03, 0xe8, 0, 0, 0, 3, 0, 0, 0, 7]);
+///
+/// let unpacked = <[Delta<u32>; 3]>::unpack_from_be(&mut &buf[..]).unwrap();
+/// assert_eq!(unpacked, timestamps);
+///
+/// // Vectors behind a length prefix are delta-encoded alike.
+/// let column = LengthPrefixed::<u16, _>::new((0..1000).map(|i| Delta(i * 3)).collect::<Vec<_>>());
+/// let mut buf = vec![];
+/// column.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf[..6], [0x03, 0xe8, 0, 0, 0, 0]);
+/// assert_eq!(buf[6..10], [0, 0, 0, 3]);
+///
+/// let unpacked = LengthPrefixed::<u16, Vec<Delta<u32>>>::unpack_from_be(&mut &buf[..]).unwrap();
+/// assert_eq!(unpacked, column);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct Delta<T>(pub T);
+
+macro_rules! impl_delta {
+    ($($ty:ty),+) => {
+        $(
+            impl PackTo for Delta<$ty> {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    self.0.pack_to::<E, _>(dst)
+                }
+
+                fn pack_multiple_to<E: ByteOrder, W: Write + ?Sized>(
+                    buf: &[Self],
+                    dst: &mut W,
+                ) -> IoResult<()> {
+                    let mut prev: $ty = 0;
+                    for Delta(value) in buf {
+                        value.wrapping_sub(prev).pack_to::<E, _>(dst)?;
+                        prev = *value;
+                    }
+                    Ok(())
+                }
+            }
+
+            impl UnpackFrom for Delta<$ty> {
+                fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+                    Ok(Self(<$ty>::unpack_from::<E, _>(src)?))
+                }
+
+                fn unpack_multiple_into<E: ByteOrder, R: Read + ?Sized>(
+                    src: &mut R,
+                    dst: &mut [Self],
+                ) -> IoResult<()> {
+                    let mut prev: $ty = 0;
+                    for Delta(value) in dst {
+                        prev = prev.wrapping_add(<$ty>::unpack_from::<E, _>(src)?);
+                        *value = prev;
+                    }
+                    Ok(())
+                }
+
+                #[cfg(feature = "alloc")]
+                fn unpack_multiple_vec<E: ByteOrder, R: Read + ?Sized>(
+                    src: &mut R,
+                    count: usize,
+                ) -> IoResult<Vec<Self>> {
+                    let mut prev: $ty = 0;
+                    let diffs = <$ty>::unpack_multiple_vec::<E, _>(src, count)?;
+                    Ok(diffs
+                        .into_iter()
+                        .map(|diff| {
+                            prev = prev.wrapping_add(diff);
+                            Delta(prev)
+                        })
+                        .collect())
+                }
+            }
+
+            impl FixedPackSize for Delta<$ty> {
+                const PACKED_SIZE: usize = <$ty>::PACKED_SIZE;
+            }
+
+            #[cfg(feature = "alloc")]
+            impl crate::Describe for Delta<$ty> {
+                fn schema() -> crate::Schema {
+                    <$ty as crate::Describe>::schema()
+                }
+            }
+        )+
+    };
+}
+
+impl_delta!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
@@ -0,0 +1,177 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8, Saturating, Wrapping,
+};
+use core::ops::{Range, RangeInclusive};
+
+use crate::Schema;
+
+/// Types whose packed layout can be described as a [`Schema`].
+///
+/// This is the layout metadata used by tools such as
+/// [`PackedDebug::annotated`](crate::PackedDebug::annotated). Tuples are
+/// described as structs with fields named `0`, `1`...
+/// # Example
+/// ```rust
+/// use byteorder_pack::{Describe, Schema};
+///
+/// assert_eq!(
+///     <(u8, [u16; 2])>::schema(),
+///     Schema::Struct(vec![
+///         ("0".into(), Schema::U8),
+///         ("1".into(), Schema::Array(Box::new(Schema::U16), 2)),
+///     ]),
+/// );
+/// ```
+pub trait Describe {
+    /// The packed layout of this type.
+    fn schema() -> Schema;
+}
+
+impl<T: Describe> Describe for &'_ T {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T: Describe> Describe for Wrapping<T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T: Describe> Describe for Saturating<T> {
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl<T: Describe> Describe for Range<T> {
+    fn schema() -> Schema {
+        Schema::Struct(vec![
+            ("start".into(), T::schema()),
+            ("end".into(), T::schema()),
+        ])
+    }
+}
+
+impl<T: Describe> Describe for RangeInclusive<T> {
+    fn schema() -> Schema {
+        Schema::Struct(vec![
+            ("start".into(), T::schema()),
+            ("end".into(), T::schema()),
+        ])
+    }
+}
+
+impl Describe for () {
+    fn schema() -> Schema {
+        Schema::Struct(vec![])
+    }
+}
+
+impl<T: Describe, const N: usize> Describe for [T; N] {
+    fn schema() -> Schema {
+        Schema::Array(Box::new(T::schema()), N)
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty => $schema:ident),+) => {
+        $(
+            impl Describe for $ty {
+                fn schema() -> Schema {
+                    Schema::$schema
+                }
+            }
+        )+
+    };
+}
+
+impl_primitive!(
+    bool => U8, char => U32, u8 => U8, i8 => I8, u16 => U16, i16 => I16, u32 => U32, i32 => I32,
+    u64 => U64, i64 => I64, u128 => U128, i128 => I128, f32 => F32, f64 => F64
+);
+
+impl_primitive!(
+    NonZeroU8 => U8, NonZeroI8 => I8, NonZeroU16 => U16, NonZeroI16 => I16,
+    NonZeroU32 => U32, NonZeroI32 => I32, NonZeroU64 => U64, NonZeroI64 => I64,
+    NonZeroU128 => U128, NonZeroI128 => I128
+);
+
+impl_primitive!(
+    Option<NonZeroU8> => U8, Option<NonZeroI8> => I8, Option<NonZeroU16> => U16,
+    Option<NonZeroI16> => I16, Option<NonZeroU32> => U32, Option<NonZeroI32> => I32,
+    Option<NonZeroU64> => U64, Option<NonZeroI64> => I64, Option<NonZeroU128> => U128,
+    Option<NonZeroI128> => I128
+);
+
+macro_rules! impl_tuple {
+    ($($n:tt => $t:ident),+) => {
+        impl<$($t: Describe),+> Describe for ($($t,)+) {
+            fn schema() -> Schema {
+                Schema::Struct(vec![$((stringify!($n).to_string(), $t::schema())),+])
+            }
+        }
+    };
+}
+
+impl_tuple!(0 => T1);
+impl_tuple!(0 => T1, 1 => T2);
+impl_tuple!(0 => T1, 1 => T2, 2 => T3);
+impl_tuple!(0 => T1, 1 => T2, 2 => T3, 3 => T4);
+impl_tuple!(0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5);
+impl_tuple!(0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6);
+impl_tuple!(0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7);
+impl_tuple!(0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8);
+impl_tuple!(
+    0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6,
+    6 => T7, 7 => T8, 8 => T9
+);
+impl_tuple!(
+    0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6,
+    6 => T7, 7 => T8, 8 => T9, 9 => T10
+);
+impl_tuple!(
+    0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6,
+    6 => T7, 7 => T8, 8 => T9, 9 => T10, 10 => T11
+);
+impl_tuple!(
+    0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6,
+    6 => T7, 7 => T8, 8 => T9, 9 => T10, 10 => T11, 11 => T12
+);
+
+/// Builds the schema of a derived struct field by field, inserting
+/// [`Schema::Bytes`] fields named `pad_<offset>` for its padding.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct StructSchema {
+    fields: Vec<(String, Schema)>,
+    offset: usize,
+}
+
+impl StructSchema {
+    pub fn field(&mut self, name: &str, schema: Schema) {
+        self.offset = self.offset.saturating_add(schema.size());
+        self.fields.push((name.to_string(), schema));
+    }
+
+    pub fn pad(&mut self, n: usize) {
+        if n > 0 {
+            self.field(&format!("pad_{}", self.offset), Schema::Bytes(n));
+        }
+    }
+
+    pub fn align(&mut self, align: usize) {
+        self.pad((align - self.offset % align) % align);
+    }
+
+    pub fn finish(self) -> Schema {
+        Schema::Struct(self.fields)
+    }
+}
@@ -0,0 +1,125 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use byteorder::ByteOrder;
+
+use crate::io::Result as IoResult;
+use crate::{Describe, UnpackFrom, Value};
+
+/// The first difference between two packed buffers, found by [`diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// Path of the differing field, as passed to
+    /// [`Schema::for_each_field`](crate::Schema::for_each_field). Empty if the
+    /// buffers only differ in bytes past the end of the layout.
+    pub path: String,
+    /// Byte range of the field in both buffers.
+    pub range: Range<usize>,
+    /// The field's value in the left buffer.
+    pub left: Value,
+    /// The field's value in the right buffer.
+    pub right: Value,
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "trailing bytes"
+        } else {
+            &self.path
+        };
+        write!(
+            f,
+            "{} at {:#x}..{:#x}: {:?} != {:?}",
+            path, self.range.start, self.range.end, self.left, self.right
+        )
+    }
+}
+
+/// Decode `left` and `right` as `T` in byte order `E` and report the first
+/// field in which they differ, or `None` if they are identical.
+///
+/// Fails if either buffer does not unpack as a `T`. Bytes past the end of the
+/// layout are compared too and reported as [`Value::Bytes`] with an empty
+/// path. A field that runs past the end of a buffer, because `T`'s layout is
+/// longer than what it unpacks, is reported as the [`Value::Bytes`] that
+/// buffer does have.
+/// # Example
+/// ```rust
+/// use std::io::{self, Read};
+///
+/// use byteorder_pack::byteorder::{BigEndian, ByteOrder};
+/// use byteorder_pack::{diff, Describe, Schema, UnpackFrom, Value};
+///
+/// let ours = [0x01, 0x00, 0x02, 0x00, 0x03];
+/// let reference = [0x01, 0x00, 0x02, 0x00, 0x04];
+///
+/// let d = diff::<BigEndian, (u8, [u16; 2])>(&ours, &reference).unwrap().unwrap();
+/// assert_eq!(d.path, "1[1]");
+/// assert_eq!(d.range, 3..5);
+/// assert_eq!(d.to_string(), "1[1] at 0x3..0x5: U16(3) != U16(4)");
+/// assert_eq!((d.left, d.right), (Value::U16(3), Value::U16(4)));
+///
+/// assert!(diff::<BigEndian, (u8, [u16; 2])>(&ours, &ours).unwrap().is_none());
+///
+/// // A layout that claims more bytes than the value packs to.
+/// struct Short(u8);
+///
+/// impl UnpackFrom for Short {
+///     fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> io::Result<Self> {
+///         u8::unpack_from::<E, _>(src).map(Short)
+///     }
+/// }
+///
+/// impl Describe for Short {
+///     fn schema() -> Schema {
+///         Schema::U32
+///     }
+/// }
+///
+/// let d = diff::<BigEndian, Short>(&[1], &[1, 2, 3, 4]).unwrap().unwrap();
+/// assert_eq!(d.left, Value::Bytes(vec![1]));
+/// assert_eq!(d.right, Value::U32(0x01020304));
+/// ```
+pub fn diff<E: ByteOrder, T: UnpackFrom + Describe>(
+    left: &[u8],
+    right: &[u8],
+) -> IoResult<Option<FieldDiff>> {
+    T::unpack_from::<E, _>(&mut &left[..])?;
+    T::unpack_from::<E, _>(&mut &right[..])?;
+
+    let mut fields = Vec::new();
+    let schema = T::schema();
+    schema.for_each_field(|path, offset, field| {
+        let range = offset..offset + field.size();
+        if fields.is_empty() && left.get(range.clone()) != right.get(range.clone()) {
+            fields.push((path.to_string(), range, field.clone()));
+        }
+    });
+    if let Some((path, range, field)) = fields.pop() {
+        let value = |buf: &[u8]| match buf.get(range.clone()) {
+            Some(mut bytes) => field.interpret::<E, _>(&mut bytes),
+            None => Ok(Value::Bytes(buf[range.start.min(buf.len())..].to_vec())),
+        };
+        return Ok(Some(FieldDiff {
+            left: value(left)?,
+            right: value(right)?,
+            path,
+            range,
+        }));
+    }
+
+    let end = schema.size();
+    let (left, right) = (&left[end.min(left.len())..], &right[end.min(right.len())..]);
+    if left == right {
+        return Ok(None);
+    }
+    Ok(Some(FieldDiff {
+        path: String::new(),
+        range: end..end + left.len().max(right.len()),
+        left: Value::Bytes(left.to_vec()),
+        right: Value::Bytes(right.to_vec()),
+    }))
+}
@@ -0,0 +1,72 @@
+use crate::io::{Error, ErrorKind, Read, Result as IoResult, Write};
+
+/// Adapter that makes an [`embedded_io::Read`] or [`embedded_io::Write`]
+/// usable as a source or destination for [`UnpackFrom`](crate::UnpackFrom)
+/// and [`PackTo`](crate::PackTo).
+///
+/// Errors reported by the driver are mapped to the closest [`ErrorKind`].
+/// # Example
+/// ```rust
+/// use byteorder_pack::{EmbeddedIo, UnpackFrom};
+///
+/// let mut uart = EmbeddedIo::new(&[0x01, 0x02, 0x03][..]);
+///
+/// let (a, b) = <(u16, u8)>::unpack_from_be(&mut uart).unwrap();
+///
+/// assert_eq!(a, 0x0102);
+/// assert_eq!(b, 0x03);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbeddedIo<T>(T);
+
+impl<T> EmbeddedIo<T> {
+    /// Wrap an `embedded-io` reader or writer.
+    pub fn new(inner: T) -> Self {
+        Self(inner)
+    }
+
+    /// Get a reference to the wrapped driver.
+    pub fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    /// Get a mutable reference to the wrapped driver.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// Unwrap the driver.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+fn map_err<E: embedded_io::Error>(e: E) -> Error {
+    let kind = match e.kind() {
+        embedded_io::ErrorKind::InvalidInput => ErrorKind::InvalidInput,
+        embedded_io::ErrorKind::InvalidData => ErrorKind::InvalidData,
+        embedded_io::ErrorKind::TimedOut => ErrorKind::TimedOut,
+        embedded_io::ErrorKind::WriteZero => ErrorKind::WriteZero,
+        embedded_io::ErrorKind::Interrupted => ErrorKind::Interrupted,
+        embedded_io::ErrorKind::Unsupported => ErrorKind::Unsupported,
+        embedded_io::ErrorKind::OutOfMemory => ErrorKind::OutOfMemory,
+        _ => ErrorKind::Other,
+    };
+    Error::from(kind)
+}
+
+impl<T: embedded_io::Read> Read for EmbeddedIo<T> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        self.0.read(buf).map_err(map_err)
+    }
+}
+
+impl<T: embedded_io::Write> Write for EmbeddedIo<T> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.0.write(buf).map_err(map_err)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.0.flush().map_err(map_err)
+    }
+}
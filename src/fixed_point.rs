@@ -0,0 +1,101 @@
+use byteorder::ByteOrder;
+
+use crate::io::{Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// A fixed-point number with `FRAC_BITS` fractional bits, packed as its raw
+/// integer `I`, as used for Q-format values in sensor and DSP formats.
+///
+/// The wrapped integer is the raw value, which stands for
+/// `raw / 2^FRAC_BITS`. `FRAC_BITS` must be at most the width of `I`.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{PackTo, UnpackFrom, Q15};
+///
+/// let mut buf = vec![];
+/// Q15::from_f64(0.5).pack_to_be(&mut buf).unwrap();
+/// Q15::from_f64(-1.0).pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0x40, 0x00, 0x80, 0x00]);
+///
+/// let value = Q15::unpack_from_be(&mut &buf[..]).unwrap();
+/// assert_eq!(value.to_bits(), 0x4000);
+/// assert_eq!(value.to_f64(), 0.5);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct FixedPoint<I, const FRAC_BITS: u32>(pub I);
+
+/// A signed Q15 number in the range `-1.0..1.0`, packed as an `i16`.
+pub type Q15 = FixedPoint<i16, 15>;
+
+/// A signed Q31 number in the range `-1.0..1.0`, packed as an `i32`.
+pub type Q31 = FixedPoint<i32, 31>;
+
+impl<I, const FRAC_BITS: u32> FixedPoint<I, FRAC_BITS> {
+    /// Wrap a raw integer.
+    pub const fn from_bits(bits: I) -> Self {
+        Self(bits)
+    }
+
+    /// Unwrap the raw integer.
+    pub fn to_bits(self) -> I {
+        self.0
+    }
+}
+
+macro_rules! impl_fixed_point {
+    ($($int:ty),+) => {
+        $(
+            impl<const FRAC_BITS: u32> FixedPoint<$int, FRAC_BITS> {
+                /// The value of one, `2^FRAC_BITS`, as a float.
+                const SCALE: f64 = (1u128 << FRAC_BITS) as f64;
+
+                /// Convert a float to the nearest fixed-point number.
+                ///
+                /// Values out of range saturate and NaN converts to 0.
+                pub fn from_f64(value: f64) -> Self {
+                    let scaled = value * Self::SCALE;
+                    // Round half away from zero, without `std`.
+                    Self(if scaled >= 0.0 {
+                        (scaled + 0.5) as $int
+                    } else {
+                        (scaled - 0.5) as $int
+                    })
+                }
+
+                /// The number as a float.
+                pub fn to_f64(self) -> f64 {
+                    self.0 as f64 / Self::SCALE
+                }
+            }
+        )+
+    };
+}
+
+impl_fixed_point!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl<I: PackTo, const FRAC_BITS: u32> PackTo for FixedPoint<I, FRAC_BITS> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        self.0.pack_to::<E, _>(dst)
+    }
+}
+
+impl<I: UnpackFrom, const FRAC_BITS: u32> UnpackFrom for FixedPoint<I, FRAC_BITS> {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        I::unpack_from::<E, _>(src).map(Self)
+    }
+}
+
+impl<I: FixedPackSize, const FRAC_BITS: u32> FixedPackSize for FixedPoint<I, FRAC_BITS> {
+    const PACKED_SIZE: usize = I::PACKED_SIZE;
+}
+
+#[cfg(feature = "alloc")]
+impl<I: crate::Describe, const FRAC_BITS: u32> crate::Describe for FixedPoint<I, FRAC_BITS> {
+    fn schema() -> crate::Schema {
+        I::schema()
+    }
+}
@@ -0,0 +1,160 @@
+use std::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// Random access to a file of records that all pack to
+/// [`T::PACKED_SIZE`](FixedPackSize::PACKED_SIZE) bytes in byte order `E`.
+///
+/// Records start at the position of the file when it is wrapped and follow
+/// each other without gaps. Trailing bytes too few to hold a whole record are
+/// ignored.
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder_pack::FixedRecords;
+///
+/// let data = vec![0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04];
+/// let mut records = FixedRecords::<_, (u8, u8)>::new(Cursor::new(data)).unwrap();
+/// assert_eq!(records.len(), 4);
+/// assert_eq!(records.get(2).unwrap(), (0, 3));
+///
+/// records.set(1, &(9, 9)).unwrap();
+/// let values: Vec<_> = records.iter_range(1..3).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(values, [(9, 9), (0, 3)]);
+/// ```
+#[derive(Debug)]
+pub struct FixedRecords<R, T, E = BigEndian> {
+    file: R,
+    start: u64,
+    len: u64,
+    _marker: PhantomData<(T, E)>,
+}
+
+impl<R, T, E> FixedRecords<R, T, E>
+where
+    R: Read + Seek,
+    T: UnpackFrom + FixedPackSize,
+    E: ByteOrder,
+{
+    /// Wrap `file`, whose records start at its current position.
+    ///
+    /// Fails with [`ErrorKind::InvalidInput`] if `T` packs to zero bytes.
+    pub fn new(mut file: R) -> IoResult<Self> {
+        if T::PACKED_SIZE == 0 {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "records must not be empty",
+            ));
+        }
+        let start = file.stream_position()?;
+        let end = file.seek(SeekFrom::End(0))?;
+        Ok(Self {
+            file,
+            start,
+            len: end.saturating_sub(start) / T::PACKED_SIZE as u64,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Number of whole records in the file.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the file has no whole records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Read record `i`.
+    pub fn get(&mut self, i: u64) -> IoResult<T> {
+        self.seek_to(i)?;
+        T::unpack_from::<E, _>(&mut self.file)
+    }
+
+    /// Iterate over the records in `range`, clamped to the number of records,
+    /// reading them sequentially.
+    pub fn iter_range(&mut self, range: Range<u64>) -> FixedRecordsIter<'_, R, T, E> {
+        let end = range.end.min(self.len);
+        FixedRecordsIter {
+            next: range.start.min(end),
+            end,
+            seeked: false,
+            records: self,
+        }
+    }
+
+    /// Unwrap the underlying file.
+    pub fn into_inner(self) -> R {
+        self.file
+    }
+
+    fn seek_to(&mut self, i: u64) -> IoResult<()> {
+        if i >= self.len {
+            return Err(IoError::new(ErrorKind::InvalidInput, "no such record"));
+        }
+        self.file
+            .seek(SeekFrom::Start(self.start + i * T::PACKED_SIZE as u64))?;
+        Ok(())
+    }
+}
+
+impl<R, T, E> FixedRecords<R, T, E>
+where
+    R: Read + Write + Seek,
+    T: PackTo + UnpackFrom + FixedPackSize,
+    E: ByteOrder,
+{
+    /// Overwrite record `i` with `value` in place.
+    pub fn set(&mut self, i: u64, value: &T) -> IoResult<()> {
+        self.seek_to(i)?;
+        value.pack_to::<E, _>(&mut self.file)
+    }
+}
+
+/// Iterator over a range of the records of a [`FixedRecords`], created by
+/// [`FixedRecords::iter_range`].
+#[derive(Debug)]
+pub struct FixedRecordsIter<'a, R, T, E = BigEndian> {
+    records: &'a mut FixedRecords<R, T, E>,
+    next: u64,
+    end: u64,
+    seeked: bool,
+}
+
+impl<R, T, E> Iterator for FixedRecordsIter<'_, R, T, E>
+where
+    R: Read + Seek,
+    T: UnpackFrom + FixedPackSize,
+    E: ByteOrder,
+{
+    type Item = IoResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+        if !self.seeked {
+            if let Err(e) = self.records.seek_to(self.next) {
+                self.next = self.end;
+                return Some(Err(e));
+            }
+            self.seeked = true;
+        }
+        self.next += 1;
+        let result = T::unpack_from::<E, _>(&mut self.records.file);
+        if result.is_err() {
+            self.next = self.end;
+        }
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = usize::try_from(self.end - self.next).ok();
+        (n.unwrap_or(usize::MAX), n)
+    }
+}
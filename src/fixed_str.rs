@@ -0,0 +1,168 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::str;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Read, Result as IoResult, Write};
+use crate::len::utf8_error;
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// How a [`FixedStr`] fills the bytes after its text.
+pub trait Padding {
+    /// The byte written after the text.
+    const BYTE: u8;
+
+    /// The text of a packed field, without its padding.
+    fn trim(bytes: &[u8]) -> &[u8] {
+        let len = bytes
+            .iter()
+            .rposition(|&b| b != Self::BYTE)
+            .map_or(0, |i| i + 1);
+        &bytes[..len]
+    }
+}
+
+/// Pads with NULs. The text ends at the first NUL, if any, as in tar and FAT
+/// directory entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NulPadded;
+
+impl Padding for NulPadded {
+    const BYTE: u8 = 0;
+
+    fn trim(bytes: &[u8]) -> &[u8] {
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        &bytes[..len]
+    }
+}
+
+/// Pads with spaces, which are trimmed from the end, as in ISO 9660.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SpacePadded;
+
+impl Padding for SpacePadded {
+    const BYTE: u8 = b' ';
+}
+
+/// Text packed as exactly `N` bytes, followed by padding chosen by `P` if it is
+/// shorter.
+///
+/// Text longer than `N` bytes is truncated, at a character boundary, when the
+/// `FixedStr` is created, and then cut where unpacking would stop: before
+/// trailing spaces for [`SpacePadded`], or at the first NUL for
+/// [`NulPadded`]. A `FixedStr` so always unpacks to itself. Unpacking trims
+/// the padding and fails with
+/// [`ErrorKind::InvalidData`](crate::io::ErrorKind::InvalidData) if the rest
+/// is not UTF-8.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{FixedStr, PackTo, SpacePadded, UnpackFrom};
+///
+/// let volume = FixedStr::<8, SpacePadded>::new("DISK");
+/// let mut buf = vec![];
+/// volume.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, b"DISK    ");
+///
+/// let volume = FixedStr::<8, SpacePadded>::unpack_from_be(&mut &buf[..]).unwrap();
+/// assert_eq!(volume.as_str(), "DISK");
+///
+/// assert_eq!(FixedStr::<4>::new("overlong").as_str(), "over");
+/// assert_eq!(FixedStr::<8, SpacePadded>::new("A B  ").as_str(), "A B");
+/// assert_eq!(FixedStr::<8>::new("A\0B").as_str(), "A");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedStr<const N: usize, P = NulPadded> {
+    // Bytes after `len` are always zero, so derived comparisons only see the
+    // text.
+    bytes: [u8; N],
+    len: usize,
+    padding: PhantomData<P>,
+}
+
+impl<const N: usize, P: Padding> FixedStr<N, P> {
+    /// Wrap `text`, truncated to at most `N` bytes and cut where the padding
+    /// would end it.
+    pub fn new(text: &str) -> Self {
+        let mut len = text.len().min(N);
+        while !text.is_char_boundary(len) {
+            len -= 1;
+        }
+        // The padding is ASCII, so this stays on a character boundary.
+        let len = P::trim(&text.as_bytes()[..len]).len();
+        let mut bytes = [0; N];
+        bytes[..len].copy_from_slice(&text.as_bytes()[..len]);
+        Self {
+            bytes,
+            len,
+            padding: PhantomData,
+        }
+    }
+}
+
+impl<const N: usize, P> FixedStr<N, P> {
+    /// The text, without padding.
+    pub fn as_str(&self) -> &str {
+        // Only ever set from a `str`, cut at a character boundary.
+        str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl<const N: usize, P: Padding> Default for FixedStr<N, P> {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl<const N: usize, P> fmt::Debug for FixedStr<N, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FixedStr({:?})", self.as_str())
+    }
+}
+
+impl<const N: usize, P> fmt::Display for FixedStr<N, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize, P: Padding> From<&str> for FixedStr<N, P> {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl<const N: usize, P: Padding> PackTo for FixedStr<N, P> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        let mut bytes = self.bytes;
+        bytes[self.len..].fill(P::BYTE);
+        dst.write_all(&bytes)
+    }
+}
+
+impl<const N: usize, P: Padding> UnpackFrom for FixedStr<N, P> {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let mut bytes = [0; N];
+        src.read_exact(&mut bytes)?;
+        let text = str::from_utf8(P::trim(&bytes)).map_err(utf8_error)?;
+        Ok(Self::new(text))
+    }
+}
+
+impl<const N: usize, P> FixedPackSize for FixedStr<N, P> {
+    const PACKED_SIZE: usize = N;
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, P> crate::Describe for FixedStr<N, P> {
+    fn schema() -> crate::Schema {
+        crate::Schema::Bytes(N)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, const N: usize, P: Padding> arbitrary::Arbitrary<'a> for FixedStr<N, P> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::new(u.arbitrary()?))
+    }
+}
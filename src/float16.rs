@@ -0,0 +1,71 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use byteorder::ByteOrder;
+use half::{bf16, f16};
+
+use crate::io::{Read, Result as IoResult, Write};
+use crate::unpack::CHUNK_SIZE;
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+macro_rules! impl_half {
+    ($($ty:ident),+) => {
+        $(
+            /// Packs the 16 bits of the float like a `u16`.
+            impl PackTo for $ty {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    self.to_bits().pack_to::<E, _>(dst)
+                }
+
+                fn pack_multiple_to<E: ByteOrder, W: Write + ?Sized>(
+                    buf: &[Self],
+                    dst: &mut W,
+                ) -> IoResult<()> {
+                    let mut bits = [0u16; CHUNK_SIZE / 2];
+                    for chunk in buf.chunks(bits.len()) {
+                        let bits = &mut bits[..chunk.len()];
+                        for (b, v) in bits.iter_mut().zip(chunk) {
+                            *b = v.to_bits();
+                        }
+                        u16::pack_multiple_to::<E, _>(bits, dst)?;
+                    }
+                    Ok(())
+                }
+            }
+
+            impl UnpackFrom for $ty {
+                fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+                    u16::unpack_from::<E, _>(src).map($ty::from_bits)
+                }
+
+                fn unpack_multiple_into<E: ByteOrder, R: Read + ?Sized>(
+                    src: &mut R,
+                    dst: &mut [Self],
+                ) -> IoResult<()> {
+                    let mut bits = [0u16; CHUNK_SIZE / 2];
+                    for chunk in dst.chunks_mut(bits.len()) {
+                        let bits = &mut bits[..chunk.len()];
+                        u16::unpack_multiple_into::<E, _>(src, bits)?;
+                        for (v, b) in chunk.iter_mut().zip(bits) {
+                            *v = $ty::from_bits(*b);
+                        }
+                    }
+                    Ok(())
+                }
+
+                #[cfg(feature = "alloc")]
+                fn unpack_multiple_vec<E: ByteOrder, R: Read + ?Sized>(
+                    src: &mut R,
+                    count: usize,
+                ) -> IoResult<Vec<Self>> {
+                    crate::len::unpack_chunked::<E, _, _>(src, count)
+                }
+            }
+
+            impl FixedPackSize for $ty {
+                const PACKED_SIZE: usize = 2;
+            }
+        )+
+    };
+}
+
+impl_half!(f16, bf16);
@@ -0,0 +1,427 @@
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write as _;
+use core::str::FromStr;
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian, NativeEndian};
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::len::read_bytes;
+use crate::schema::is_little_endian;
+use crate::{PackTo, Schema, UnpackFrom, Value};
+
+/// Error returned when a format string cannot be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatError {
+    position: usize,
+    message: &'static str,
+}
+
+impl FormatError {
+    /// Byte offset in the format string where the error was found.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.position)
+    }
+}
+
+impl core::error::Error for FormatError {}
+
+const PAD: [u8; 16] = [0; 16];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Order {
+    Little,
+    Big,
+    Native,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Item {
+    Pad(usize),
+    /// A `?`, unpacked as a [`Value::U8`] of 0 or 1.
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+    Bytes(usize),
+}
+
+impl Item {
+    fn size(self) -> usize {
+        match self {
+            Item::Pad(n) | Item::Bytes(n) => n,
+            Item::Bool | Item::U8 | Item::I8 => 1,
+            Item::U16 | Item::I16 => 2,
+            Item::U32 | Item::I32 | Item::F32 => 4,
+            Item::U64 | Item::I64 | Item::F64 => 8,
+        }
+    }
+
+    fn schema(self) -> Schema {
+        match self {
+            Item::Pad(n) | Item::Bytes(n) => Schema::Bytes(n),
+            Item::Bool | Item::U8 => Schema::U8,
+            Item::I8 => Schema::I8,
+            Item::U16 => Schema::U16,
+            Item::I16 => Schema::I16,
+            Item::U32 => Schema::U32,
+            Item::I32 => Schema::I32,
+            Item::U64 => Schema::U64,
+            Item::I64 => Schema::I64,
+            Item::F32 => Schema::F32,
+            Item::F64 => Schema::F64,
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (Item::Bool, Value::U8(0 | 1))
+                | (Item::U8, Value::U8(_))
+                | (Item::I8, Value::I8(_))
+                | (Item::U16, Value::U16(_))
+                | (Item::I16, Value::I16(_))
+                | (Item::U32, Value::U32(_))
+                | (Item::I32, Value::I32(_))
+                | (Item::U64, Value::U64(_))
+                | (Item::I64, Value::I64(_))
+                | (Item::F32, Value::F32(_))
+                | (Item::F64, Value::F64(_))
+        ) || matches!((self, value), (Item::Bytes(n), Value::Bytes(b)) if b.len() == n)
+    }
+
+    fn unpack<E: ByteOrder, R: Read + ?Sized>(self, src: &mut R) -> IoResult<Option<Value>> {
+        Ok(Some(match self {
+            Item::Pad(mut n) => {
+                let mut skip = PAD;
+                while n > 0 {
+                    let len = n.min(skip.len());
+                    src.read_exact(&mut skip[..len])?;
+                    n -= len;
+                }
+                return Ok(None);
+            }
+            Item::Bool => Value::U8(bool::unpack_from::<E, _>(src)?.into()),
+            Item::U8 => Value::U8(u8::unpack_from::<E, _>(src)?),
+            Item::I8 => Value::I8(i8::unpack_from::<E, _>(src)?),
+            Item::U16 => Value::U16(u16::unpack_from::<E, _>(src)?),
+            Item::I16 => Value::I16(i16::unpack_from::<E, _>(src)?),
+            Item::U32 => Value::U32(u32::unpack_from::<E, _>(src)?),
+            Item::I32 => Value::I32(i32::unpack_from::<E, _>(src)?),
+            Item::U64 => Value::U64(u64::unpack_from::<E, _>(src)?),
+            Item::I64 => Value::I64(i64::unpack_from::<E, _>(src)?),
+            Item::F32 => Value::F32(f32::unpack_from::<E, _>(src)?),
+            Item::F64 => Value::F64(f64::unpack_from::<E, _>(src)?),
+            Item::Bytes(n) => Value::Bytes(read_bytes(src, n)?),
+        }))
+    }
+}
+
+/// A binary layout parsed at runtime from a Python `struct` format string,
+/// like Python's `struct.Struct`.
+///
+/// The format must start with `<` (little-endian), `>` or `!` (big-endian) or
+/// `=` (native byte order); native alignment (`@`) is not supported. Supported
+/// format characters are `x` (pad byte), `c`/`B` ([`Value::U8`]), `b`
+/// ([`Value::I8`]), `?` (a [`Value::U8`] that must be 0 or 1), `h`/`H` (16-bit), `i`/`I` and `l`/`L` (32-bit), `q`/`Q`
+/// (64-bit), `f` ([`Value::F32`]), `d` ([`Value::F64`]) and `Ns`
+/// ([`Value::Bytes`] of `N` bytes). A repeat count before any other character
+/// stands for that many values. Whitespace is ignored.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{Format, Value};
+///
+/// let format: Format = ">HxI2s".parse().unwrap();
+/// assert_eq!(format.size(), 9);
+///
+/// let values = vec![Value::U16(1), Value::U32(2), Value::Bytes(b"ab".to_vec())];
+/// let mut buf = vec![];
+/// format.pack_to(&values, &mut buf).unwrap();
+/// assert_eq!(buf, b"\x00\x01\x00\x00\x00\x00\x02ab");
+///
+/// assert_eq!(format.unpack_from(&mut &buf[..]).unwrap(), values);
+///
+/// let flag: Format = "<?".parse().unwrap();
+/// assert_eq!(flag.unpack_from(&mut &[1][..]).unwrap(), [Value::U8(1)]);
+/// assert!(flag.unpack_from(&mut &[2][..]).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    order: Order,
+    /// Items with their repeat counts; `Pad` and `Bytes` always have a count
+    /// of 1 since they carry their own length.
+    items: Vec<(Item, usize)>,
+}
+
+impl Format {
+    /// Parse a format string.
+    pub fn parse(format: &str) -> Result<Self, FormatError> {
+        let err = |position, message| FormatError { position, message };
+        let mut chars = format.char_indices().filter(|(_, c)| !c.is_whitespace());
+        let order = match chars.next() {
+            Some((_, '<')) => Order::Little,
+            Some((_, '>' | '!')) => Order::Big,
+            Some((_, '=')) => Order::Native,
+            Some((i, '@')) => return Err(err(i, "native alignment is not supported")),
+            Some((i, _)) => return Err(err(i, "missing byte order")),
+            None => return Err(err(0, "missing byte order")),
+        };
+
+        let mut items = Vec::new();
+        while let Some((mut pos, mut code)) = chars.next() {
+            let mut count: Option<usize> = None;
+            while let Some(digit) = code.to_digit(10) {
+                count = count
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(digit as usize))
+                    .map(Some)
+                    .ok_or(err(pos, "repeat count too large"))?;
+                (pos, code) = chars
+                    .next()
+                    .ok_or(err(format.len(), "repeat count without a format character"))?;
+            }
+            let item = match code {
+                'x' => Item::Pad(count.take().unwrap_or(1)),
+                's' => Item::Bytes(count.take().unwrap_or(1)),
+                'c' | 'B' => Item::U8,
+                'b' => Item::I8,
+                '?' => Item::Bool,
+                'h' => Item::I16,
+                'H' => Item::U16,
+                'i' | 'l' => Item::I32,
+                'I' | 'L' => Item::U32,
+                'q' => Item::I64,
+                'Q' => Item::U64,
+                'f' => Item::F32,
+                'd' => Item::F64,
+                _ => return Err(err(pos, "unsupported format character")),
+            };
+            items.push((item, count.unwrap_or(1)));
+        }
+        Ok(Self { order, items })
+    }
+
+    /// Packed size in bytes, like Python's `struct.calcsize`, saturating at
+    /// `usize::MAX`.
+    pub fn size(&self) -> usize {
+        self.items.iter().fold(0usize, |size, (item, count)| {
+            size.saturating_add(item.size().saturating_mul(*count))
+        })
+    }
+
+    /// Number of values packed or unpacked, excluding padding.
+    pub fn value_count(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|(item, _)| !matches!(item, Item::Pad(_)))
+            .fold(0usize, |n, (_, count)| n.saturating_add(*count))
+    }
+
+    /// Whether values are packed little-endian, resolving native byte order
+    /// (`=`) for the target.
+    pub fn is_little_endian(&self) -> bool {
+        match self.order {
+            Order::Little => true,
+            Order::Big => false,
+            Order::Native => cfg!(target_endian = "little"),
+        }
+    }
+
+    /// The layout as a [`Schema`], with one field per format item named by
+    /// its position (`"0"`, `"1"`...).
+    ///
+    /// Repeated items become [`Schema::Array`] fields and padding becomes
+    /// [`Schema::Bytes`] fields, so the schema covers every byte.
+    /// # Example
+    /// ```rust
+    /// use byteorder_pack::{Format, Schema};
+    ///
+    /// let format: Format = "<Hx3I".parse().unwrap();
+    /// assert_eq!(
+    ///     format.schema(),
+    ///     Schema::Struct(vec![
+    ///         ("0".into(), Schema::U16),
+    ///         ("1".into(), Schema::Bytes(1)),
+    ///         ("2".into(), Schema::Array(Box::new(Schema::U32), 3)),
+    ///     ]),
+    /// );
+    /// assert_eq!(format.schema().size(), format.size());
+    /// ```
+    pub fn schema(&self) -> Schema {
+        Schema::Struct(
+            self.items
+                .iter()
+                .enumerate()
+                .map(|(i, &(item, count))| {
+                    let schema = item.schema();
+                    let schema = if count == 1 {
+                        schema
+                    } else {
+                        Schema::Array(Box::new(schema), count)
+                    };
+                    (i.to_string(), schema)
+                })
+                .collect(),
+        )
+    }
+
+    /// All non-padding items, one per value.
+    fn fields(&self) -> impl Iterator<Item = Item> + '_ {
+        self.items
+            .iter()
+            .filter(|(item, _)| !matches!(item, Item::Pad(_)))
+            .flat_map(|&(item, count)| core::iter::repeat_n(item, count))
+    }
+
+    /// Pack `values` to `dst`.
+    ///
+    /// Fails with [`ErrorKind::InvalidInput`] before writing anything if the
+    /// number of values or any of their types does not match the format.
+    pub fn pack_to<W: Write + ?Sized>(&self, values: &[Value], dst: &mut W) -> IoResult<()> {
+        match self.order {
+            Order::Little => self.pack_with::<LittleEndian, _>(values, dst),
+            Order::Big => self.pack_with::<BigEndian, _>(values, dst),
+            Order::Native => self.pack_with::<NativeEndian, _>(values, dst),
+        }
+    }
+
+    /// Unpack one value per non-padding item from `src`.
+    pub fn unpack_from<R: Read + ?Sized>(&self, src: &mut R) -> IoResult<Vec<Value>> {
+        match self.order {
+            Order::Little => self.unpack_with::<LittleEndian, _>(src),
+            Order::Big => self.unpack_with::<BigEndian, _>(src),
+            Order::Native => self.unpack_with::<NativeEndian, _>(src),
+        }
+    }
+
+    fn pack_with<E: ByteOrder, W: Write + ?Sized>(
+        &self,
+        values: &[Value],
+        dst: &mut W,
+    ) -> IoResult<()> {
+        let mut fields = self.fields();
+        if !values
+            .iter()
+            .all(|value| fields.next().is_some_and(|item| item.matches(value)))
+            || fields.next().is_some()
+        {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "values do not match format",
+            ));
+        }
+        let mut values = values.iter();
+        for &(item, count) in &self.items {
+            match item {
+                Item::Pad(mut n) => {
+                    while n > 0 {
+                        let len = n.min(PAD.len());
+                        dst.write_all(&PAD[..len])?;
+                        n -= len;
+                    }
+                }
+                _ => {
+                    for value in values.by_ref().take(count) {
+                        value.pack_to::<E, _>(dst)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn unpack_with<E: ByteOrder, R: Read + ?Sized>(&self, src: &mut R) -> IoResult<Vec<Value>> {
+        let mut values = Vec::new();
+        for &(item, count) in &self.items {
+            for _ in 0..count {
+                values.extend(item.unpack::<E, _>(src)?);
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// The Python `struct` format string for `schema` in byte order `E`, such as
+/// `">BBHH4s"`, or `None` if it contains 128-bit integers, which Python's
+/// `struct` module lacks.
+///
+/// Nested structs are flattened and arrays of primitives use repeat counts.
+/// The result parses back into a [`Format`] of the same size.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::{python_format, Describe, Format};
+///
+/// let fmt = python_format::<BigEndian>(&<(u8, i8, [u16; 2], [u8; 4])>::schema()).unwrap();
+/// assert_eq!(fmt, ">Bb2H4B");
+/// assert_eq!(fmt.parse::<Format>().unwrap().size(), 10);
+///
+/// assert_eq!(python_format::<BigEndian>(&u128::schema()), None);
+/// ```
+pub fn python_format<E: ByteOrder>(schema: &Schema) -> Option<String> {
+    let mut out = String::from(if is_little_endian::<E>() { "<" } else { ">" });
+    python_codes(schema, &mut out)?;
+    Some(out)
+}
+
+fn python_codes(schema: &Schema, out: &mut String) -> Option<()> {
+    let code = match schema {
+        Schema::U8 => 'B',
+        Schema::I8 => 'b',
+        Schema::U16 => 'H',
+        Schema::I16 => 'h',
+        Schema::U32 => 'I',
+        Schema::I32 => 'i',
+        Schema::U64 => 'Q',
+        Schema::I64 => 'q',
+        Schema::F32 => 'f',
+        Schema::F64 => 'd',
+        Schema::U128 | Schema::I128 => return None,
+        Schema::Bytes(n) => {
+            let _ = write!(out, "{}s", n);
+            return Some(());
+        }
+        Schema::Array(elem, n) => {
+            if elem.type_name().is_some() && !matches!(**elem, Schema::Bytes(_)) {
+                let _ = write!(out, "{}", n);
+                return python_codes(elem, out);
+            }
+            for _ in 0..*n {
+                python_codes(elem, out)?;
+            }
+            return Some(());
+        }
+        Schema::Struct(fields) => {
+            for (_, field) in fields {
+                python_codes(field, out)?;
+            }
+            return Some(());
+        }
+    };
+    out.push(code);
+    Some(())
+}
+
+impl FromStr for Format {
+    type Err = FormatError;
+
+    fn from_str(format: &str) -> Result<Self, FormatError> {
+        Self::parse(format)
+    }
+}
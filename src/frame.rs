@@ -0,0 +1,291 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::{crc32, FixedPackSize, LengthPrefix, PackTo, SliceReader, UnpackFrom};
+
+/// Default maximum payload size accepted by a [`FrameReader`], 1 MiB (or
+/// `u16::MAX` bytes on 16-bit targets).
+pub const DEFAULT_MAX_FRAME_LEN: usize = if usize::BITS > 16 {
+    (1u32 << 20) as usize
+} else {
+    u16::MAX as usize
+};
+
+/// Read a fixed-size frame header, or `None` if `src` ends before its first
+/// byte.
+pub(crate) fn read_header<E, H, R>(src: &mut R) -> IoResult<Option<H>>
+where
+    E: ByteOrder,
+    H: UnpackFrom + FixedPackSize,
+    R: Read + ?Sized,
+{
+    let mut header = vec![0; H::PACKED_SIZE];
+    let n = loop {
+        match src.read(&mut header) {
+            Ok(n) => break n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    };
+    if n == 0 && !header.is_empty() {
+        return Ok(None);
+    }
+    src.read_exact(&mut header[n..])?;
+    H::unpack_from::<E, _>(&mut &header[..]).map(Some)
+}
+
+/// Read a frame payload of `len` bytes into `buf` and unpack exactly one `T`
+/// from it.
+pub(crate) fn read_payload<E, L, T, R>(
+    src: &mut R,
+    len: L,
+    max_len: usize,
+    buf: &mut Vec<u8>,
+) -> IoResult<T>
+where
+    E: ByteOrder,
+    L: LengthPrefix,
+    T: UnpackFrom,
+    R: Read + ?Sized,
+{
+    let len = len.to_usize()?;
+    if len > max_len {
+        return Err(IoError::new(
+            ErrorKind::InvalidData,
+            "frame exceeds maximum length",
+        ));
+    }
+    buf.clear();
+    buf.resize(len, 0);
+    src.read_exact(buf)?;
+
+    let mut payload = SliceReader::new(buf);
+    let msg = T::unpack_from::<E, _>(&mut payload)?;
+    if !payload.is_empty() {
+        return Err(IoError::new(
+            ErrorKind::InvalidData,
+            "trailing bytes in frame",
+        ));
+    }
+    Ok(msg)
+}
+
+/// Writes messages to `W`, each preceded by its packed length as an `L` in
+/// byte order `E`.
+/// # Example
+/// ```rust
+/// use byteorder_pack::FrameWriter;
+///
+/// let mut frames = FrameWriter::<_, u16>::new(vec![]);
+///
+/// frames.write_frame(&(1u8, 2u32)).unwrap();
+///
+/// assert_eq!(frames.into_inner(), vec![0x00, 0x05, 0x01, 0x00, 0x00, 0x00, 0x02]);
+/// ```
+#[derive(Debug)]
+pub struct FrameWriter<W, L = u32, E = BigEndian> {
+    inner: W,
+    buf: Vec<u8>,
+    _marker: PhantomData<(L, E)>,
+}
+
+impl<W: Write, L: LengthPrefix + PackTo, E: ByteOrder> FrameWriter<W, L, E> {
+    /// Create a frame writer over `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pack `msg` and write it as a single frame.
+    ///
+    /// Fails with [`LengthOverflow`](crate::LengthOverflow) if the packed
+    /// message is too long for `L`, in which case nothing is written.
+    pub fn write_frame<T: PackTo>(&mut self, msg: &T) -> IoResult<()> {
+        self.buf.clear();
+        msg.pack_to::<E, _>(&mut self.buf)?;
+        let len = L::from_usize(self.buf.len())?;
+        len.pack_to::<E, _>(&mut self.inner)?;
+        self.inner.write_all(&self.buf)
+    }
+
+    /// Get a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Reads messages written by a [`FrameWriter`] with the same `L` and `E`.
+///
+/// Frames longer than the maximum length are rejected with
+/// [`ErrorKind::InvalidData`] before their payload is read, and a frame whose
+/// payload is not consumed exactly by the message is rejected as well.
+/// # Example
+/// ```rust
+/// use byteorder_pack::FrameReader;
+///
+/// let data = [0x00, 0x05, 0x01, 0x00, 0x00, 0x00, 0x02];
+/// let mut frames = FrameReader::<_, u16>::new(&data[..]);
+///
+/// assert_eq!(frames.read_frame::<(u8, u32)>().unwrap(), Some((1, 2)));
+/// assert_eq!(frames.read_frame::<(u8, u32)>().unwrap(), None);
+/// ```
+#[derive(Debug)]
+pub struct FrameReader<R, L = u32, E = BigEndian> {
+    inner: R,
+    buf: Vec<u8>,
+    max_len: usize,
+    _marker: PhantomData<(L, E)>,
+}
+
+impl<R: Read, L, E> FrameReader<R, L, E>
+where
+    L: LengthPrefix + UnpackFrom + FixedPackSize,
+    E: ByteOrder,
+{
+    /// Create a frame reader over `inner` that accepts payloads of up to
+    /// [`DEFAULT_MAX_FRAME_LEN`] bytes.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            max_len: DEFAULT_MAX_FRAME_LEN,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the maximum accepted payload size in bytes.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Read and unpack the next frame.
+    ///
+    /// Returns `None` if the source ends cleanly before a new frame starts;
+    /// ending anywhere inside a frame is an [`ErrorKind::UnexpectedEof`] error.
+    /// Reads interrupted with [`ErrorKind::Interrupted`] are retried.
+    pub fn read_frame<T: UnpackFrom>(&mut self) -> IoResult<Option<T>> {
+        let Some(len) = read_header::<E, L, _>(&mut self.inner)? else {
+            return Ok(None);
+        };
+        read_payload::<E, L, _, _>(&mut self.inner, len, self.max_len, &mut self.buf).map(Some)
+    }
+
+    /// Get a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// A message framed as its packed length (an `L`), the packed message and the
+/// [`crc32`] of the packed message (a `u32`), all in the byte order used to
+/// pack or unpack the frame.
+///
+/// Unpacking rejects frames longer than `MAX_LEN` bytes, frames whose
+/// checksum does not match and frames whose payload is not consumed exactly by
+/// the message, all with [`ErrorKind::InvalidData`].
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder_pack::{CrcFrame, PackTo, UnpackFrom};
+///
+/// let mut cursor = Cursor::new(vec![]);
+/// CrcFrame::<_, u16>::new(0x31323334u32).pack_to_be(&mut cursor).unwrap();
+///
+/// let data = cursor.into_inner();
+/// assert_eq!(&data[..6], &[0x00, 0x04, 0x31, 0x32, 0x33, 0x34]);
+/// assert_eq!(&data[6..], &byteorder_pack::crc32(b"1234").to_be_bytes());
+///
+/// let frame = CrcFrame::<u32, u16>::unpack_from_be(&mut &data[..]).unwrap();
+/// assert_eq!(frame.0, 0x31323334);
+///
+/// let mut corrupt = data.clone();
+/// corrupt[3] ^= 0xFF;
+/// assert!(CrcFrame::<u32, u16>::unpack_from_be(&mut &corrupt[..]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct CrcFrame<T, L = u32, const MAX_LEN: usize = DEFAULT_MAX_FRAME_LEN>(
+    pub T,
+    PhantomData<L>,
+);
+
+impl<T, L, const MAX_LEN: usize> CrcFrame<T, L, MAX_LEN> {
+    /// Wrap `msg` in a frame.
+    pub fn new(msg: T) -> Self {
+        Self(msg, PhantomData)
+    }
+
+    /// Unwrap the message.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: PackTo, L: LengthPrefix + PackTo, const MAX_LEN: usize> PackTo for CrcFrame<T, L, MAX_LEN> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        let mut payload = Vec::new();
+        self.0.pack_to::<E, _>(&mut payload)?;
+        L::from_usize(payload.len())?.pack_to::<E, _>(dst)?;
+        dst.write_all(&payload)?;
+        crc32(&payload).pack_to::<E, _>(dst)
+    }
+}
+
+impl<T: UnpackFrom, L: LengthPrefix + UnpackFrom, const MAX_LEN: usize> UnpackFrom
+    for CrcFrame<T, L, MAX_LEN>
+{
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let len = L::unpack_from::<E, _>(src)?.to_usize()?;
+        if len > MAX_LEN {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "frame exceeds maximum length",
+            ));
+        }
+        let mut payload = vec![0; len];
+        src.read_exact(&mut payload)?;
+        if u32::unpack_from::<E, _>(src)? != crc32(&payload) {
+            return Err(IoError::new(ErrorKind::InvalidData, "frame CRC mismatch"));
+        }
+        let mut reader = SliceReader::new(&payload);
+        let msg = T::unpack_from::<E, _>(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "trailing bytes in frame",
+            ));
+        }
+        Ok(Self::new(msg))
+    }
+}
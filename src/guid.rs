@@ -0,0 +1,86 @@
+use byteorder::ByteOrder;
+use uuid::Uuid;
+
+use crate::io::{Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// Packs the sixteen bytes in RFC 4122 order, regardless of byte order. Use
+/// [`Guid`] for the Microsoft layout.
+impl PackTo for Uuid {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(self.as_bytes())
+    }
+}
+
+impl UnpackFrom for Uuid {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        <[u8; 16]>::unpack_from::<E, _>(src).map(Uuid::from_bytes)
+    }
+}
+
+impl FixedPackSize for Uuid {
+    const PACKED_SIZE: usize = 16;
+}
+
+/// A UUID packed in the mixed-endian layout of Microsoft GUIDs, as found in
+/// GPT partition tables, COM and many Windows file formats.
+///
+/// The first three fields are packed little-endian and the last eight bytes
+/// as they are, regardless of the requested byte order.
+/// # Example
+/// ```rust
+/// use byteorder_pack::uuid::Uuid;
+/// use byteorder_pack::{Guid, PackTo, UnpackFrom};
+///
+/// let id = Guid(Uuid::from_u128(0x00112233_4455_6677_8899_aabbccddeeff));
+/// let mut buf = vec![];
+/// id.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf[..8], [0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66]);
+/// assert_eq!(buf[8..], [0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff]);
+///
+/// assert_eq!(Guid::unpack_from_be(&mut &buf[..]).unwrap(), id);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Guid(pub Uuid);
+
+impl From<Uuid> for Guid {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+impl From<Guid> for Uuid {
+    fn from(guid: Guid) -> Self {
+        guid.0
+    }
+}
+
+impl PackTo for Guid {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(&self.0.to_bytes_le())
+    }
+}
+
+impl UnpackFrom for Guid {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        <[u8; 16]>::unpack_from::<E, _>(src).map(|bytes| Self(Uuid::from_bytes_le(bytes)))
+    }
+}
+
+impl FixedPackSize for Guid {
+    const PACKED_SIZE: usize = 16;
+}
+
+#[cfg(feature = "alloc")]
+impl crate::Describe for Uuid {
+    fn schema() -> crate::Schema {
+        crate::Schema::Bytes(16)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl crate::Describe for Guid {
+    fn schema() -> crate::Schema {
+        crate::Schema::Bytes(16)
+    }
+}
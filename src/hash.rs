@@ -0,0 +1,108 @@
+use core::hash::Hasher;
+
+#[cfg(feature = "digest")]
+use byteorder::ByteOrder;
+
+use crate::io::{Result as IoResult, Write};
+#[cfg(feature = "digest")]
+use crate::PackTo;
+
+/// Adapter that feeds everything written to it into a [`Hasher`], so packed
+/// values can be hashed without an intermediate buffer.
+/// # Example
+/// ```rust
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::hash::Hasher;
+/// use byteorder_pack::{HasherWriter, PackTo};
+///
+/// let mut hasher = HasherWriter::new(DefaultHasher::new());
+/// (1u16, 2u32).pack_to_le(&mut hasher).unwrap();
+///
+/// let mut expected = DefaultHasher::new();
+/// expected.write(&[0x01, 0x00, 0x02, 0x00, 0x00, 0x00]);
+/// assert_eq!(hasher.finish(), expected.finish());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HasherWriter<H>(H);
+
+impl<H: Hasher> HasherWriter<H> {
+    /// Wrap a hasher.
+    pub fn new(hasher: H) -> Self {
+        Self(hasher)
+    }
+
+    /// The hash of all bytes written so far.
+    pub fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+
+    /// Unwrap the hasher.
+    pub fn into_inner(self) -> H {
+        self.0
+    }
+}
+
+impl<H: Hasher> Write for HasherWriter<H> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// Adapter that feeds everything written to it into a [`digest`] hash
+/// function, enabled by the `digest` feature.
+#[cfg(feature = "digest")]
+#[derive(Debug, Clone, Default)]
+pub struct DigestWriter<D>(D);
+
+#[cfg(feature = "digest")]
+impl<D: digest::Update> DigestWriter<D> {
+    /// Wrap a hash function.
+    pub fn new(digest: D) -> Self {
+        Self(digest)
+    }
+
+    /// Unwrap the hash function.
+    pub fn into_inner(self) -> D {
+        self.0
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<D: digest::Update> Write for DigestWriter<D> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// Hash the packed representation of `value` in byte order `E` with the
+/// [`digest`] hash function `D`, enabled by the `digest` feature.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::pack_hash;
+/// use sha2::{Digest, Sha256};
+///
+/// let hash = pack_hash::<BigEndian, Sha256, _>(&(1u8, 2u16)).unwrap();
+/// assert_eq!(hash, Sha256::digest([0x01, 0x00, 0x02]));
+/// ```
+#[cfg(feature = "digest")]
+pub fn pack_hash<E, D, T>(value: &T) -> IoResult<digest::Output<D>>
+where
+    E: ByteOrder,
+    D: digest::Digest + digest::Update,
+    T: PackTo,
+{
+    let mut writer = DigestWriter(D::new());
+    value.pack_to::<E, _>(&mut writer)?;
+    Ok(writer.0.finalize())
+}
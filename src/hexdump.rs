@@ -0,0 +1,139 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{Describe, PackTo, Schema};
+
+const BYTES_PER_LINE: usize = 16;
+
+/// Formats a value as a hexdump of its packed bytes in byte order `E`, for
+/// use with `println!`, `tracing` and the like while debugging wire issues.
+///
+/// [`new`](Self::new) produces a classic offset/hex/ASCII dump.
+/// [`annotated`](Self::annotated) uses the value's [`Describe`] layout to
+/// print one line per field instead, with its offset, bytes, path and type.
+/// `Debug` and `Display` produce the same output.
+/// # Example
+/// ```rust
+/// use byteorder_pack::PackedDebug;
+///
+/// let value = (1u8, [2u16, 3u16]);
+///
+/// assert_eq!(
+///     PackedDebug::<_>::new(&value).to_string(),
+///     "0000  01 00 02 00 03                                   |.....|\n",
+/// );
+/// assert_eq!(
+///     PackedDebug::<_>::annotated(&value).to_string(),
+///     "0000  01     0: u8\n\
+///      0001  00 02  1[0]: u16\n\
+///      0003  00 03  1[1]: u16\n",
+/// );
+/// ```
+pub struct PackedDebug<'a, T, E = BigEndian> {
+    value: &'a T,
+    schema: Option<Schema>,
+    _marker: PhantomData<E>,
+}
+
+impl<'a, T: PackTo, E: ByteOrder> PackedDebug<'a, T, E> {
+    /// Dump the packed bytes of `value` without field information.
+    pub fn new(value: &'a T) -> Self {
+        Self {
+            value,
+            schema: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: PackTo + Describe, E: ByteOrder> PackedDebug<'a, T, E> {
+    /// Dump the packed bytes of `value` one field at a time.
+    pub fn annotated(value: &'a T) -> Self {
+        Self {
+            value,
+            schema: Some(T::schema()),
+            _marker: PhantomData,
+        }
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8], width: usize) -> fmt::Result {
+    for (i, b) in bytes.iter().enumerate() {
+        if i > 0 {
+            f.write_str(" ")?;
+        }
+        write!(f, "{:02x}", b)?;
+    }
+    let len = (bytes.len() * 3).saturating_sub(1);
+    write!(f, "{:1$}", "", width.saturating_sub(len))
+}
+
+fn write_plain(f: &mut fmt::Formatter<'_>, bytes: &[u8], start: usize) -> fmt::Result {
+    for (i, line) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        write!(f, "{:04x}  ", start + i * BYTES_PER_LINE)?;
+        write_hex(f, line, BYTES_PER_LINE * 3 - 1)?;
+        f.write_str("  |")?;
+        for &b in line {
+            let c = if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            };
+            write!(f, "{}", c)?;
+        }
+        f.write_str("|\n")?;
+    }
+    Ok(())
+}
+
+impl<T: PackTo, E: ByteOrder> fmt::Display for PackedDebug<'_, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = Vec::new();
+        if let Err(e) = self.value.pack_to::<E, _>(&mut bytes) {
+            return write!(f, "<failed to pack: {}>", e);
+        }
+        let Some(schema) = &self.schema else {
+            return write_plain(f, &bytes, 0);
+        };
+
+        let mut fields = Vec::new();
+        schema.for_each_field(|path, offset, field| {
+            let size = field.size();
+            let name = field.type_name().unwrap_or_default();
+            fields.push((path.to_string(), offset, size, name));
+        });
+        let width = fields
+            .iter()
+            .map(|(_, _, size, _)| ((*size).min(BYTES_PER_LINE) * 3).saturating_sub(1))
+            .max()
+            .unwrap_or(0);
+
+        let mut end = 0;
+        for (path, offset, size, name) in &fields {
+            let Some(field) = bytes.get(*offset..offset.saturating_add(*size)) else {
+                break;
+            };
+            for (i, line) in field.chunks(BYTES_PER_LINE).enumerate() {
+                write!(f, "{:04x}  ", offset + i * BYTES_PER_LINE)?;
+                write_hex(f, line, width)?;
+                match (i, path.is_empty()) {
+                    (0, true) => writeln!(f, "  {}", name)?,
+                    (0, false) => writeln!(f, "  {}: {}", path, name)?,
+                    _ => writeln!(f)?,
+                }
+            }
+            end = offset + size;
+        }
+        write_plain(f, &bytes[end.min(bytes.len())..], end)
+    }
+}
+
+impl<T: PackTo, E: ByteOrder> fmt::Debug for PackedDebug<'_, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
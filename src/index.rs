@@ -0,0 +1,210 @@
+use alloc::vec::Vec;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Read, Result as IoResult, Write};
+use crate::{FixedPackSize, LengthPrefix, PackTo, UnpackFrom};
+
+/// The position of one record in a [`RecordIndex`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct IndexEntry {
+    /// Byte offset of the start of the record.
+    pub offset: u64,
+    /// Packed length of the record in bytes.
+    pub len: u64,
+}
+
+impl PackTo for IndexEntry {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (self.offset, self.len).pack_to::<E, _>(dst)
+    }
+}
+
+impl UnpackFrom for IndexEntry {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let (offset, len) = <(u64, u64)>::unpack_from::<E, _>(src)?;
+        Ok(Self { offset, len })
+    }
+}
+
+impl FixedPackSize for IndexEntry {
+    const PACKED_SIZE: usize = 16;
+}
+
+/// The start offset and length of each record in a file, for random access
+/// with [`unpack_at`](Self::unpack_at).
+///
+/// An index is built while streaming records through an [`IndexingReader`],
+/// and can itself be packed (as a `u64` count followed by the entries) to be
+/// stored alongside the data.
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::{IndexingReader, PackTo, RecordIndex, UnpackFrom};
+///
+/// let data = [0x01, 0x00, 0x02, 0x03, 0x00, 0x04];
+/// let mut reader = IndexingReader::new(&data[..]);
+/// while !reader.get_ref().is_empty() {
+///     reader.unpack::<BigEndian, (u8, u16)>().unwrap();
+/// }
+/// let index = reader.into_index();
+/// assert_eq!(index.len(), 2);
+///
+/// let mut stored = vec![];
+/// index.pack_to_be(&mut stored).unwrap();
+/// let index = RecordIndex::unpack_from_be(&mut &stored[..]).unwrap();
+///
+/// let mut file = Cursor::new(&data[..]);
+/// assert_eq!(index.unpack_at::<BigEndian, (u8, u16), _>(&mut file, 1).unwrap(), (3, 4));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RecordIndex {
+    entries: Vec<IndexEntry>,
+}
+
+impl RecordIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an entry.
+    pub fn push(&mut self, entry: IndexEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Number of records in the index.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the index has no records.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The entry for record `i`.
+    pub fn get(&self, i: usize) -> Option<IndexEntry> {
+        self.entries.get(i).copied()
+    }
+
+    /// All entries, in record order.
+    pub fn entries(&self) -> &[IndexEntry] {
+        &self.entries
+    }
+
+    /// Seek to record `i` in `src` and unpack it, checking that it occupies
+    /// exactly its indexed length.
+    #[cfg(feature = "std")]
+    pub fn unpack_at<E, T, R>(&self, src: &mut R, i: usize) -> IoResult<T>
+    where
+        E: ByteOrder,
+        T: UnpackFrom,
+        R: Read + std::io::Seek + ?Sized,
+    {
+        use std::io::{Error as IoError, ErrorKind, SeekFrom};
+
+        let entry = self
+            .get(i)
+            .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "no such record"))?;
+        src.seek(SeekFrom::Start(entry.offset))?;
+        let mut record = src.take(entry.len);
+        let value = T::unpack_from::<E, _>(&mut record)?;
+        if record.limit() != 0 {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "record shorter than its indexed length",
+            ));
+        }
+        Ok(value)
+    }
+}
+
+impl PackTo for RecordIndex {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (self.entries.len() as u64).pack_to::<E, _>(dst)?;
+        IndexEntry::pack_multiple_to::<E, _>(&self.entries, dst)
+    }
+}
+
+impl UnpackFrom for RecordIndex {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let count = u64::unpack_from::<E, _>(src)?.to_usize()?;
+        let entries = crate::unpack_vec::<E, _, _>(src, count)?;
+        Ok(Self { entries })
+    }
+}
+
+/// A reader that records the offset and length of each record unpacked
+/// through it into a [`RecordIndex`].
+#[derive(Debug)]
+pub struct IndexingReader<R> {
+    inner: R,
+    position: u64,
+    index: RecordIndex,
+}
+
+impl<R: Read> IndexingReader<R> {
+    /// Index records read from `inner`, with offsets counted from 0.
+    pub fn new(inner: R) -> Self {
+        Self::with_offset(inner, 0)
+    }
+
+    /// Index records read from `inner`, whose current position is `offset`.
+    pub fn with_offset(inner: R, offset: u64) -> Self {
+        Self {
+            inner,
+            position: offset,
+            index: RecordIndex::new(),
+        }
+    }
+
+    /// Unpack one record and add it to the index.
+    pub fn unpack<E: ByteOrder, T: UnpackFrom>(&mut self) -> IoResult<T> {
+        let offset = self.position;
+        let value = T::unpack_from::<E, _>(self)?;
+        self.index.push(IndexEntry {
+            offset,
+            len: self.position - offset,
+        });
+        Ok(value)
+    }
+
+    /// The offset of the next byte to be read.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The index built so far.
+    pub fn index(&self) -> &RecordIndex {
+        &self.index
+    }
+
+    /// Get a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Finish indexing, returning the index.
+    pub fn into_index(self) -> RecordIndex {
+        self.index
+    }
+
+    /// Unwrap the underlying reader and the index.
+    pub fn into_parts(self) -> (R, RecordIndex) {
+        (self.inner, self.index)
+    }
+}
+
+impl<R: Read> Read for IndexingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let n = self.inner.read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
@@ -0,0 +1,138 @@
+use core::fmt;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+macro_rules! impl_int {
+    ($($(#[$doc:meta])* $name:ident($int:ty, $bytes:expr, $min:expr, $max:expr, $write:expr, $read:expr);)+) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct $name($int);
+
+            impl $name {
+                /// The smallest value.
+                pub const MIN: Self = Self($min);
+                /// The largest value.
+                pub const MAX: Self = Self($max);
+
+                /// Wrap `value`, or `None` if it is out of range.
+                pub const fn new(value: $int) -> Option<Self> {
+                    if value >= Self::MIN.0 && value <= Self::MAX.0 {
+                        Some(Self(value))
+                    } else {
+                        None
+                    }
+                }
+
+                /// The wrapped value.
+                pub const fn get(self) -> $int {
+                    self.0
+                }
+            }
+
+            impl From<$name> for $int {
+                fn from(value: $name) -> Self {
+                    value.0
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.0, f)
+                }
+            }
+
+            impl PackTo for $name {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    let mut buf = [0; $bytes];
+                    ($write)(&mut buf, self.0);
+                    dst.write_all(&buf)
+                }
+            }
+
+            impl UnpackFrom for $name {
+                fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+                    let mut buf = [0; $bytes];
+                    src.read_exact(&mut buf)?;
+                    Ok(Self(($read)(&buf)))
+                }
+            }
+
+            impl FixedPackSize for $name {
+                const PACKED_SIZE: usize = $bytes;
+            }
+
+            #[cfg(feature = "arbitrary")]
+            impl<'a> arbitrary::Arbitrary<'a> for $name {
+                fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                    u.int_in_range(Self::MIN.0..=Self::MAX.0).map(Self)
+                }
+
+                fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                    <$int as arbitrary::Arbitrary>::size_hint(depth)
+                }
+            }
+        )+
+    };
+}
+
+impl_int! {
+    /// An unsigned 24-bit integer, packed as 3 bytes, as used for sample
+    /// values in audio formats.
+    /// # Example
+    /// ```rust
+    /// use byteorder_pack::{PackTo, UnpackFrom, U24};
+    ///
+    /// let value = U24::new(0x010203).unwrap();
+    /// let mut buf = vec![];
+    /// value.pack_to_le(&mut buf).unwrap();
+    /// assert_eq!(buf, [0x03, 0x02, 0x01]);
+    ///
+    /// assert_eq!(U24::unpack_from_le(&mut &buf[..]).unwrap(), value);
+    /// assert!(U24::new(1 << 24).is_none());
+    /// ```
+    U24(u32, 3, 0, (1 << 24) - 1, E::write_u24, E::read_u24);
+    /// A signed 24-bit integer, packed as 3 bytes in two's complement.
+    /// # Example
+    /// ```rust
+    /// use byteorder_pack::{I24, PackTo, UnpackFrom};
+    ///
+    /// let value = I24::new(-2).unwrap();
+    /// let mut buf = vec![];
+    /// value.pack_to_be(&mut buf).unwrap();
+    /// assert_eq!(buf, [0xff, 0xff, 0xfe]);
+    ///
+    /// assert_eq!(I24::unpack_from_be(&mut &buf[..]).unwrap(), value);
+    /// ```
+    I24(i32, 3, -(1 << 23), (1 << 23) - 1, E::write_i24, E::read_i24);
+    /// An unsigned 40-bit integer, packed as 5 bytes.
+    /// # Example
+    /// ```rust
+    /// use byteorder_pack::{PackTo, UnpackFrom, U40};
+    ///
+    /// let value = U40::new(0x01_0000_0002).unwrap();
+    /// let mut buf = vec![];
+    /// value.pack_to_be(&mut buf).unwrap();
+    /// assert_eq!(buf, [0x01, 0x00, 0x00, 0x00, 0x02]);
+    ///
+    /// assert_eq!(U40::unpack_from_be(&mut &buf[..]).unwrap(), value);
+    /// ```
+    U40(u64, 5, 0, (1 << 40) - 1, |buf: &mut [u8], n| E::write_uint(buf, n, 5), |buf: &[u8]| E::read_uint(buf, 5));
+    /// An unsigned 48-bit integer, packed as 6 bytes, as used for sequence
+    /// numbers and timestamps in several network protocols.
+    /// # Example
+    /// ```rust
+    /// use byteorder_pack::{PackTo, UnpackFrom, U48};
+    ///
+    /// let value = U48::MAX;
+    /// let mut buf = vec![];
+    /// value.pack_to_le(&mut buf).unwrap();
+    /// assert_eq!(buf, [0xff; 6]);
+    ///
+    /// assert_eq!(U48::unpack_from_le(&mut &buf[..]).unwrap(), value);
+    /// ```
+    U48(u64, 6, 0, (1 << 48) - 1, E::write_u48, E::read_u48);
+}
@@ -0,0 +1,142 @@
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::{PackTo, UnpackFrom};
+
+/// A struct of arrays to be packed as interleaved records by
+/// [`pack_interleaved`].
+///
+/// Implemented for tuples of up to 12 slices, whose elements at index `i`
+/// together form record `i`.
+pub trait Columns {
+    /// Number of records, or `None` if the columns differ in length.
+    fn rows(&self) -> Option<usize>;
+
+    /// Pack record `i`.
+    fn pack_row<E: ByteOrder, W: Write + ?Sized>(&self, i: usize, dst: &mut W) -> IoResult<()>;
+}
+
+/// A struct of arrays to be filled from interleaved records by
+/// [`unpack_interleaved`].
+///
+/// Implemented for tuples of up to 12 mutable slices.
+pub trait ColumnsMut {
+    /// Number of records, or `None` if the columns differ in length.
+    fn rows(&self) -> Option<usize>;
+
+    /// Unpack record `i` into the columns.
+    fn unpack_row<E: ByteOrder, R: Read + ?Sized>(&mut self, i: usize, src: &mut R)
+        -> IoResult<()>;
+}
+
+macro_rules! impl_tuple {
+    ($first:tt $(, $idx:tt)* => $($t:ident),+) => {
+        impl<$($t: PackTo),+> Columns for ($(&[$t],)+) {
+            fn rows(&self) -> Option<usize> {
+                let rows = self.$first.len();
+                (true $(&& self.$idx.len() == rows)*).then_some(rows)
+            }
+
+            fn pack_row<E: ByteOrder, W: Write + ?Sized>(
+                &self,
+                i: usize,
+                dst: &mut W,
+            ) -> IoResult<()> {
+                self.$first[i].pack_to::<E, _>(dst)?;
+                $(self.$idx[i].pack_to::<E, _>(dst)?;)*
+                Ok(())
+            }
+        }
+
+        impl<$($t: UnpackFrom),+> ColumnsMut for ($(&mut [$t],)+) {
+            fn rows(&self) -> Option<usize> {
+                let rows = self.$first.len();
+                (true $(&& self.$idx.len() == rows)*).then_some(rows)
+            }
+
+            fn unpack_row<E: ByteOrder, R: Read + ?Sized>(
+                &mut self,
+                i: usize,
+                src: &mut R,
+            ) -> IoResult<()> {
+                self.$first[i] = UnpackFrom::unpack_from::<E, _>(src)?;
+                $(self.$idx[i] = UnpackFrom::unpack_from::<E, _>(src)?;)*
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_tuple!(0 => T1);
+impl_tuple!(0, 1 => T1, T2);
+impl_tuple!(0, 1, 2 => T1, T2, T3);
+impl_tuple!(0, 1, 2, 3 => T1, T2, T3, T4);
+impl_tuple!(0, 1, 2, 3, 4 => T1, T2, T3, T4, T5);
+impl_tuple!(0, 1, 2, 3, 4, 5 => T1, T2, T3, T4, T5, T6);
+impl_tuple!(0, 1, 2, 3, 4, 5, 6 => T1, T2, T3, T4, T5, T6, T7);
+impl_tuple!(0, 1, 2, 3, 4, 5, 6, 7 => T1, T2, T3, T4, T5, T6, T7, T8);
+impl_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8 => T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_tuple!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9 => T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_tuple!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10 => T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11
+);
+impl_tuple!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11 => T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12
+);
+
+fn mismatched() -> IoError {
+    IoError::new(ErrorKind::InvalidInput, "columns have different lengths")
+}
+
+/// Pack `columns`, a tuple of equally long slices, as one record per index
+/// holding the element of each column in turn, without building the records
+/// in memory.
+///
+/// Fails with [`ErrorKind::InvalidInput`] before writing anything if the
+/// columns differ in length.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::{pack_interleaved, unpack_interleaved};
+///
+/// let ids = [1u8, 2, 3];
+/// let readings = [10u16, 20, 30];
+/// let mut buf = vec![];
+/// pack_interleaved::<BigEndian, _, _>(&(&ids[..], &readings[..]), &mut buf).unwrap();
+/// assert_eq!(buf, [1, 0, 10, 2, 0, 20, 3, 0, 30]);
+///
+/// let (mut ids, mut readings) = ([0u8; 3], [0u16; 3]);
+/// unpack_interleaved::<BigEndian, _, _>(&mut &buf[..], &mut (&mut ids[..], &mut readings[..]))
+///     .unwrap();
+/// assert_eq!((ids, readings), ([1, 2, 3], [10, 20, 30]));
+/// ```
+pub fn pack_interleaved<E, C, W>(columns: &C, dst: &mut W) -> IoResult<()>
+where
+    E: ByteOrder,
+    C: Columns + ?Sized,
+    W: Write + ?Sized,
+{
+    let rows = columns.rows().ok_or_else(mismatched)?;
+    for i in 0..rows {
+        columns.pack_row::<E, _>(i, dst)?;
+    }
+    Ok(())
+}
+
+/// Unpack interleaved records from `src` into `columns`, a tuple of equally
+/// long mutable slices, reading one record per index.
+///
+/// Fails with [`ErrorKind::InvalidInput`] before reading anything if the
+/// columns differ in length.
+pub fn unpack_interleaved<E, R, C>(src: &mut R, columns: &mut C) -> IoResult<()>
+where
+    E: ByteOrder,
+    R: Read + ?Sized,
+    C: ColumnsMut + ?Sized,
+{
+    let rows = columns.rows().ok_or_else(mismatched)?;
+    for i in 0..rows {
+        columns.unpack_row::<E, _>(i, src)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,225 @@
+//! The read/write abstractions the packing traits are built on.
+//!
+//! With the `std` feature (enabled by default) this module simply re-exports
+//! [`std::io::Read`], [`std::io::Write`] and their error types, so any standard
+//! reader or writer can be used as a source or destination.
+//!
+//! Without `std`, the crate provides its own minimal `Read` and `Write` traits
+//! and error type with the same names and semantics. They are implemented for
+//! `&[u8]`, `&mut [u8]` and, with the `alloc` feature, `Vec<u8>`.
+//!
+//! Either way, [`PackTo`](crate::PackTo) and [`UnpackFrom`](crate::UnpackFrom)
+//! are bound by these `Write` and `Read` traits, so other backends, such as
+//! the `EmbeddedIo` adapter, only need to implement them.
+
+#[cfg(feature = "std")]
+pub use std::io::{Error, ErrorKind, Read, Result, Write};
+
+#[cfg(not(feature = "std"))]
+pub use self::core_io::{Error, ErrorKind, Read, Result, Write};
+
+#[cfg(not(feature = "std"))]
+mod core_io {
+    use core::fmt;
+
+    /// A specialized [`Result`](core::result::Result) type for I/O operations.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A list specifying general categories of I/O error.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum ErrorKind {
+        /// A parameter was incorrect.
+        InvalidInput,
+        /// Data not valid for the operation were encountered.
+        InvalidData,
+        /// The I/O operation's timeout expired.
+        TimedOut,
+        /// A write returned `Ok(0)`, or the destination is full.
+        WriteZero,
+        /// The operation was interrupted and can typically be retried.
+        Interrupted,
+        /// The operation is not supported by the source or destination.
+        Unsupported,
+        /// The source ended before the requested data could be read.
+        UnexpectedEof,
+        /// An allocation failed.
+        OutOfMemory,
+        /// Any other error.
+        Other,
+    }
+
+    /// The error type for I/O operations without `std`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Error {
+        kind: ErrorKind,
+        message: &'static str,
+    }
+
+    impl Error {
+        /// Create a new error from a kind and a static message.
+        pub const fn new(kind: ErrorKind, message: &'static str) -> Self {
+            Self { kind, message }
+        }
+
+        /// The general category of this error.
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+    }
+
+    impl From<ErrorKind> for Error {
+        fn from(kind: ErrorKind) -> Self {
+            Self::new(kind, "")
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            if self.message.is_empty() {
+                write!(f, "{:?}", self.kind)
+            } else {
+                f.write_str(self.message)
+            }
+        }
+    }
+
+    impl core::error::Error for Error {}
+
+    /// A source of bytes.
+    pub trait Read {
+        /// Pull some bytes into `buf`, returning how many were read.
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+
+        /// Read exactly enough bytes to fill `buf`.
+        fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.read(buf) {
+                    Ok(0) => {
+                        return Err(Error::new(
+                            ErrorKind::UnexpectedEof,
+                            "failed to fill whole buffer",
+                        ))
+                    }
+                    Ok(n) => buf = &mut buf[n..],
+                    Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// A sink of bytes.
+    pub trait Write {
+        /// Write some bytes from `buf`, returning how many were written.
+        fn write(&mut self, buf: &[u8]) -> Result<usize>;
+
+        /// Flush any buffered output.
+        fn flush(&mut self) -> Result<()>;
+
+        /// Write all of `buf`.
+        fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.write(buf) {
+                    Ok(0) => {
+                        return Err(Error::new(
+                            ErrorKind::WriteZero,
+                            "failed to write whole buffer",
+                        ))
+                    }
+                    Ok(n) => buf = &buf[n..],
+                    Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl<R: Read + ?Sized> Read for &mut R {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            (**self).read(buf)
+        }
+
+        #[inline]
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+            (**self).read_exact(buf)
+        }
+    }
+
+    impl<W: Write + ?Sized> Write for &mut W {
+        #[inline]
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            (**self).write(buf)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<()> {
+            (**self).flush()
+        }
+
+        #[inline]
+        fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+            (**self).write_all(buf)
+        }
+    }
+
+    impl Read for &[u8] {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let n = buf.len().min(self.len());
+            let (head, tail) = self.split_at(n);
+            buf[..n].copy_from_slice(head);
+            *self = tail;
+            Ok(n)
+        }
+    }
+
+    impl Write for &mut [u8] {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            let n = buf.len().min(self.len());
+            let (head, tail) = core::mem::take(self).split_at_mut(n);
+            head.copy_from_slice(&buf[..n]);
+            *self = tail;
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl Write for alloc::vec::Vec<u8> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<R: Read + ?Sized> Read for alloc::boxed::Box<R> {
+        #[inline]
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            (**self).read(buf)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<W: Write + ?Sized> Write for alloc::boxed::Box<W> {
+        #[inline]
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            (**self).write(buf)
+        }
+
+        #[inline]
+        fn flush(&mut self) -> Result<()> {
+            (**self).flush()
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+use byteorder::ByteOrder;
+
+use crate::schema::{identifier, is_little_endian};
+use crate::Schema;
+
+/// Export `schema` as a Kaitai Struct (`.ksy`) definition with id `id` and
+/// byte order `E`, for exploring the format in Kaitai's tools.
+///
+/// Field names become lowercase identifiers (tuple fields `0`, `1`... become
+/// `f0`, `f1`...), nested structs and nested arrays become entries under
+/// `types`, and 128-bit integers, which Kaitai lacks, become 16-byte raw
+/// fields. Call it from a `build.rs` or a test to keep a checked-in `.ksy`
+/// file up to date.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::LittleEndian;
+/// use byteorder_pack::{kaitai_yaml, Schema};
+///
+/// let header = Schema::Struct(vec![
+///     ("magic".into(), Schema::Bytes(4)),
+///     ("counts".into(), Schema::Array(Box::new(Schema::U16), 2)),
+/// ]);
+/// assert_eq!(
+///     kaitai_yaml::<LittleEndian>("header", &header),
+///     "\
+/// meta:
+///   id: header
+///   endian: le
+/// seq:
+///   - id: magic
+///     size: 4
+///   - id: counts
+///     type: u2
+///     repeat: expr
+///     repeat-expr: 2
+/// ",
+/// );
+/// ```
+pub fn kaitai_yaml<E: ByteOrder>(id: &str, schema: &Schema) -> String {
+    let mut types = Vec::new();
+    let mut out = format!(
+        "meta:\n  id: {}\n  endian: {}\n",
+        identifier(id),
+        if is_little_endian::<E>() { "le" } else { "be" }
+    );
+    out.push_str(&seq(schema, "", &mut types));
+    if !types.is_empty() {
+        out.push_str("types:\n");
+        for (name, body) in types {
+            let _ = writeln!(out, "  {}:", name);
+            for line in body.lines() {
+                let _ = writeln!(out, "    {}", line);
+            }
+        }
+    }
+    out
+}
+
+/// A `seq:` block for `schema`, wrapping non-struct schemas in a single field
+/// named `value`.
+fn seq(schema: &Schema, prefix: &str, types: &mut Vec<(String, String)>) -> String {
+    let mut out = String::from("seq:\n");
+    match schema {
+        Schema::Struct(fields) => {
+            for (name, field) in fields {
+                attrs(&mut out, &identifier(name), field, prefix, types);
+            }
+        }
+        _ => attrs(&mut out, "value", schema, prefix, types),
+    }
+    out
+}
+
+/// Append a seq entry for field `id` laid out as `schema`.
+fn attrs(
+    out: &mut String,
+    id: &str,
+    schema: &Schema,
+    prefix: &str,
+    types: &mut Vec<(String, String)>,
+) {
+    let _ = writeln!(out, "  - id: {}", id);
+    let (elem, count) = match schema {
+        Schema::Array(elem, n) => (&**elem, Some(*n)),
+        _ => (schema, None),
+    };
+    let type_name = format!("{}{}", prefix, id);
+    match elem {
+        Schema::U8 => out.push_str("    type: u1\n"),
+        Schema::I8 => out.push_str("    type: s1\n"),
+        Schema::U16 => out.push_str("    type: u2\n"),
+        Schema::I16 => out.push_str("    type: s2\n"),
+        Schema::U32 => out.push_str("    type: u4\n"),
+        Schema::I32 => out.push_str("    type: s4\n"),
+        Schema::U64 => out.push_str("    type: u8\n"),
+        Schema::I64 => out.push_str("    type: s8\n"),
+        Schema::F32 => out.push_str("    type: f4\n"),
+        Schema::F64 => out.push_str("    type: f8\n"),
+        Schema::U128 | Schema::I128 => {
+            let _ = writeln!(out, "    size: 16\n    doc: {}", elem.type_name().unwrap());
+        }
+        Schema::Bytes(n) => {
+            let _ = writeln!(out, "    size: {}", n);
+        }
+        Schema::Array(..) | Schema::Struct(_) => {
+            let body = seq(elem, &format!("{}_", type_name), types);
+            types.push((type_name.clone(), body));
+            let _ = writeln!(out, "    type: {}", type_name);
+        }
+    }
+    if let Some(n) = count {
+        let _ = writeln!(out, "    repeat: expr\n    repeat-expr: {}", n);
+    }
+}
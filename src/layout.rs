@@ -0,0 +1,98 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Write as _;
+
+use byteorder::ByteOrder;
+
+use crate::schema::is_little_endian;
+use crate::Schema;
+
+/// A Markdown table of the fields of `schema` in byte order `E`, giving each
+/// field's path, offset, size, type and byte order, for protocol
+/// documentation.
+///
+/// Arrays of primitives are listed as a single row, while the elements of
+/// arrays of structs are listed one by one. Single bytes have no byte order.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::{layout_table, Schema};
+///
+/// let header = Schema::Struct(vec![
+///     ("magic".into(), Schema::Bytes(4)),
+///     ("counts".into(), Schema::Array(Box::new(Schema::U16), 2)),
+/// ]);
+/// assert_eq!(
+///     layout_table::<BigEndian>(&header),
+///     "\
+/// | Field | Offset | Size | Type | Byte order |
+/// |-------|--------|------|------|------------|
+/// | magic | 0 | 4 | [u8; 4] | - |
+/// | counts | 4 | 4 | [u16; 2] | big-endian |
+/// ",
+/// );
+/// ```
+pub fn layout_table<E: ByteOrder>(schema: &Schema) -> String {
+    let order = if is_little_endian::<E>() {
+        "little-endian"
+    } else {
+        "big-endian"
+    };
+    let mut out = String::from(
+        "| Field | Offset | Size | Type | Byte order |\n\
+         |-------|--------|------|------|------------|\n",
+    );
+    rows(schema, &mut String::new(), 0, order, &mut out);
+    out
+}
+
+/// Append the rows for `schema` at `offset`, returning the offset past it.
+fn rows(schema: &Schema, path: &mut String, offset: usize, order: &str, out: &mut String) -> usize {
+    let len = path.len();
+    match schema {
+        Schema::Struct(fields) => {
+            let mut offset = offset;
+            for (name, field) in fields {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(name);
+                offset = rows(field, path, offset, order, out);
+                path.truncate(len);
+            }
+            offset
+        }
+        Schema::Array(elem, n) if elem.type_name().is_none() => {
+            let mut offset = offset;
+            for i in 0..*n {
+                let _ = write!(path, "[{}]", i);
+                offset = rows(elem, path, offset, order, out);
+                path.truncate(len);
+            }
+            offset
+        }
+        _ => {
+            let (type_name, elem) = match schema {
+                Schema::Array(elem, n) => (
+                    format!("[{}; {}]", elem.type_name().unwrap_or_default(), n),
+                    &**elem,
+                ),
+                _ => (schema.type_name().unwrap_or_default(), schema),
+            };
+            let byte_order = match elem {
+                Schema::U8 | Schema::I8 | Schema::Bytes(_) => "-",
+                _ => order,
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                path,
+                offset,
+                schema.size(),
+                type_name,
+                byte_order
+            );
+            offset.saturating_add(schema.size())
+        }
+    }
+}
@@ -0,0 +1,614 @@
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use core::mem::size_of;
+use core::str::{self, Utf8Error};
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    string::String,
+    vec::Vec,
+};
+#[cfg(feature = "alloc")]
+use byteorder::ByteOrder;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "alloc")]
+use crate::io::Write;
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult};
+#[cfg(feature = "alloc")]
+use crate::unpack::CHUNK_SIZE;
+#[cfg(feature = "alloc")]
+use crate::{PackTo, UnpackFrom};
+
+/// Error returned when a length or count does not fit in the integer type
+/// it is being converted to.
+///
+/// This is raised instead of silently truncating, e.g. when a `u64` count
+/// read from the wire exceeds `usize::MAX` on a 32-bit target, or when a
+/// collection is too long for its `u8` length prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthOverflow {
+    len: u128,
+}
+
+impl LengthOverflow {
+    pub(crate) fn new(len: u128) -> Self {
+        Self { len }
+    }
+
+    /// The length that failed to convert.
+    pub fn length(&self) -> u128 {
+        self.len
+    }
+}
+
+impl fmt::Display for LengthOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "length {} does not fit in the target integer type", self.len)
+    }
+}
+
+impl core::error::Error for LengthOverflow {}
+
+impl From<LengthOverflow> for IoError {
+    #[cfg(feature = "std")]
+    fn from(e: LengthOverflow) -> Self {
+        IoError::new(ErrorKind::InvalidData, e)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn from(_: LengthOverflow) -> Self {
+        IoError::new(ErrorKind::InvalidData, "length overflow")
+    }
+}
+
+/// The largest length [`LengthPrefix::to_usize`] accepts.
+///
+/// This is `usize::MAX`, except when the crate is built with
+/// `--cfg byteorder_pack_usize_bits="16"` (or `"32"`), which emulates a
+/// narrower `usize` so that the overflow paths taken on 16- and 32-bit targets
+/// can be tested on a 64-bit host.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{LengthPrefix, MAX_LEN};
+///
+/// assert_eq!((MAX_LEN as u128).to_usize().unwrap(), MAX_LEN);
+///
+/// let err = (MAX_LEN as u128 + 1).to_usize().unwrap_err();
+/// assert_eq!(err.length(), MAX_LEN as u128 + 1);
+///
+/// // Only fails with a 16-bit `usize`.
+/// assert_eq!(0x1_0000u32.to_usize().is_err(), MAX_LEN <= 0xFFFF);
+/// ```
+pub const MAX_LEN: usize = {
+    #[cfg(byteorder_pack_usize_bits = "16")]
+    let max = u16::MAX as usize;
+    #[cfg(byteorder_pack_usize_bits = "32")]
+    let max = u32::MAX as usize;
+    #[cfg(not(any(byteorder_pack_usize_bits = "16", byteorder_pack_usize_bits = "32")))]
+    let max = usize::MAX;
+    max
+};
+
+/// An integer type that can be used to encode a length or element count,
+/// such as `u32` or [`VarU32`](crate::VarU32).
+///
+/// All conversions are checked, so a wire length is never truncated when
+/// converted to `usize` and a `usize` length is never truncated when packed.
+/// `usize` itself is deliberately not a `LengthPrefix`, since its width
+/// depends on the target. Use the width the format specifies instead: `u64`
+/// for data packed with a `usize` prefix on a 64-bit host, `u32` for a 32-bit
+/// one, or a varint such as [`VarU64`](crate::VarU64) when the format leaves
+/// the width open.
+/// # Example
+/// ```rust
+/// use byteorder_pack::LengthPrefix;
+///
+/// assert_eq!(255u8.to_usize().unwrap(), 255);
+/// assert!(u8::from_usize(256).is_err());
+/// ```
+pub trait LengthPrefix: Copy {
+    /// Convert a wire length into a `usize`.
+    fn to_usize(self) -> Result<usize, LengthOverflow>;
+
+    /// Convert a `usize` length into this type.
+    fn from_usize(len: usize) -> Result<Self, LengthOverflow>;
+}
+
+macro_rules! impl_length_prefix {
+    ($($ty:ty),+) => {
+        $(
+            impl LengthPrefix for $ty {
+                #[inline]
+                fn to_usize(self) -> Result<usize, LengthOverflow> {
+                    usize::try_from(self)
+                        .ok()
+                        .filter(|&len| len <= MAX_LEN)
+                        .ok_or(LengthOverflow { len: self as u128 })
+                }
+
+                #[inline]
+                fn from_usize(len: usize) -> Result<Self, LengthOverflow> {
+                    <$ty>::try_from(len).map_err(|_| LengthOverflow { len: len as u128 })
+                }
+            }
+        )+
+    };
+}
+
+impl_length_prefix!(u8, u16, u32, u64, u128);
+
+/// Unpack `count` values of type `T` from `src`, such as those packed from a
+/// `Vec<T>`, whose count is not part of the packed form.
+///
+/// The vector grows as values arrive instead of reserving `count` up front,
+/// so a bogus count read from the wire fails at the end of the input rather
+/// than exhausting memory.
+/// # Example
+/// ```rust
+/// use byteorder::BigEndian;
+/// use byteorder_pack::{unpack_vec, UnpackFrom};
+///
+/// let mut src = &[0, 2, 0, 7, 0, 9][..];
+/// let count = u16::unpack_from_be(&mut src).unwrap();
+/// let values = unpack_vec::<BigEndian, u16, _>(&mut src, count.into()).unwrap();
+///
+/// assert_eq!(values, [7, 9]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn unpack_vec<E, T, R>(src: &mut R, count: usize) -> IoResult<Vec<T>>
+where
+    E: ByteOrder,
+    T: UnpackFrom,
+    R: Read + ?Sized,
+{
+    Vec::unpack_elements::<E, _>(src, count)
+}
+
+/// The capacity to reserve for `count` values of type `T` read from the
+/// wire: at most a chunk's worth, so that a bogus count fails at the end of
+/// the input rather than exhausting memory, and the collection grows as
+/// values actually arrive.
+#[cfg(feature = "alloc")]
+pub(crate) fn capped_capacity<T>(count: usize) -> usize {
+    count.min(CHUNK_SIZE / size_of::<T>().max(1))
+}
+
+/// Unpack `count` values of type `T` from `src` one at a time, passing each
+/// to `insert`, which may reject it.
+#[cfg(feature = "alloc")]
+pub(crate) fn unpack_each<E, T, R>(
+    src: &mut R,
+    count: usize,
+    mut insert: impl FnMut(T) -> IoResult<()>,
+) -> IoResult<()>
+where
+    E: ByteOrder,
+    T: UnpackFrom,
+    R: Read + ?Sized,
+{
+    for _ in 0..count {
+        insert(T::unpack_from::<E, _>(src)?)?;
+    }
+    Ok(())
+}
+
+/// Unpack `count` values of type `T` from `src` through
+/// `T::unpack_multiple_into`, a chunk of [`capped_capacity`] values at a time.
+#[cfg(feature = "alloc")]
+pub(crate) fn unpack_chunked<E, T, R>(src: &mut R, count: usize) -> IoResult<Vec<T>>
+where
+    E: ByteOrder,
+    T: UnpackFrom + Default + Copy,
+    R: Read + ?Sized,
+{
+    let chunk = capped_capacity::<T>(usize::MAX);
+    let mut items = Vec::with_capacity(capped_capacity::<T>(count));
+    while items.len() < count {
+        let start = items.len();
+        items.resize(start + chunk.min(count - start), T::default());
+        T::unpack_multiple_into::<E, _>(src, &mut items[start..])?;
+    }
+    Ok(items)
+}
+
+/// Error for bytes that are not UTF-8, saying where the invalid sequence is
+/// when `std` is available.
+pub(crate) fn utf8_error(e: Utf8Error) -> IoError {
+    #[cfg(feature = "std")]
+    return IoError::new(ErrorKind::InvalidData, e);
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = e;
+        IoError::new(ErrorKind::InvalidData, "string is not valid UTF-8")
+    }
+}
+
+/// Unpack a string of `buf.len()` bytes from `src` into `buf`, such as one
+/// packed from a `&str`, without allocating.
+///
+/// Fails with [`ErrorKind::InvalidData`] if the bytes are not UTF-8.
+/// # Example
+/// ```rust
+/// use byteorder_pack::unpack_str;
+///
+/// let mut buf = [0; 5];
+///
+/// assert_eq!(unpack_str(&mut &b"hello"[..], &mut buf).unwrap(), "hello");
+/// assert!(unpack_str(&mut &b"he\xffl"[..], &mut buf[..4]).is_err());
+/// ```
+pub fn unpack_str<'a, R: Read + ?Sized>(src: &mut R, buf: &'a mut [u8]) -> IoResult<&'a str> {
+    src.read_exact(buf)?;
+    str::from_utf8(buf).map_err(utf8_error)
+}
+
+/// Unpack a string of `len` bytes from `src`, such as one packed from a
+/// `String`, whose length is not part of the packed form.
+///
+/// Fails with [`ErrorKind::InvalidData`] if the bytes are not UTF-8.
+/// # Example
+/// ```rust
+/// use byteorder_pack::unpack_string;
+///
+/// let mut src = &b"hello"[..];
+///
+/// assert_eq!(unpack_string(&mut src, 5).unwrap(), "hello");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn unpack_string<R: Read + ?Sized>(src: &mut R, len: usize) -> IoResult<String> {
+    // Strings are bytes, which have no byte order.
+    String::unpack_elements::<byteorder::BigEndian, _>(src, len)
+}
+
+/// A `Vec<T>`, `VecDeque<T>`, byte blob, `String`, set or map packed after its
+/// element count (its byte length for a string) as an `L`.
+///
+/// Elements are packed in iteration order: front to back for a `VecDeque`,
+/// sorted for a `BTreeSet` or `BTreeMap` and unspecified for a `HashSet` or
+/// `HashMap`. Maps are packed as their key-value pairs.
+///
+/// Unpacking reads the count and then exactly that many elements, failing
+/// with [`LengthOverflow`] if a collection is too long for its prefix, and
+/// with [`ErrorKind::InvalidData`] if a set or map has an element or key more
+/// than once.
+/// # Example
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use byteorder_pack::{LengthPrefixed, PackTo, UnpackFrom};
+///
+/// let name = LengthPrefixed::<u16, _>::new(String::from("abc"));
+/// let mut buf = vec![];
+/// name.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0, 3, b'a', b'b', b'c']);
+///
+/// assert_eq!(LengthPrefixed::unpack_from_be(&mut &buf[..]).unwrap(), name);
+/// assert!(LengthPrefixed::<u8, _>::new(vec![0u8; 256]).pack_to_be(&mut vec![]).is_err());
+///
+/// let map = LengthPrefixed::<u8, _>::new(BTreeMap::from([(2u8, 20u16), (1, 10)]));
+/// let mut buf = vec![];
+/// map.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [2, 1, 0, 10, 2, 0, 20]);
+///
+/// assert_eq!(LengthPrefixed::unpack_from_be(&mut &buf[..]).unwrap(), map);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct LengthPrefixed<L, T>(pub T, PhantomData<L>);
+
+#[cfg(feature = "alloc")]
+impl<L, T> LengthPrefixed<L, T> {
+    /// Wrap a collection.
+    pub fn new(inner: T) -> Self {
+        Self(inner, PhantomData)
+    }
+
+    /// Unwrap the collection.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L, T> PackTo for LengthPrefixed<L, T>
+where
+    L: LengthPrefix + PackTo,
+    T: PackPrefixed,
+{
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        self.0.pack_prefixed::<E, L, _>(dst)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L, T> UnpackFrom for LengthPrefixed<L, T>
+where
+    L: LengthPrefix + UnpackFrom,
+    T: UnpackPrefixed,
+{
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        T::unpack_prefixed::<E, L, _>(src).map(Self::new)
+    }
+}
+
+/// Collections that derived impls can pack behind a `#[pack(len_prefix = L)]`
+/// element count, or with their count in another field.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub trait PackPrefixed {
+    /// Number of elements, or of bytes for a string.
+    fn count(&self) -> usize;
+
+    fn pack_elements<E, W>(&self, dst: &mut W) -> IoResult<()>
+    where
+        E: ByteOrder,
+        W: Write + ?Sized;
+
+    fn pack_prefixed<E, L, W>(&self, dst: &mut W) -> IoResult<()>
+    where
+        E: ByteOrder,
+        L: LengthPrefix + PackTo,
+        W: Write + ?Sized,
+    {
+        L::from_usize(self.count())?.pack_to::<E, _>(dst)?;
+        self.pack_elements::<E, _>(dst)
+    }
+}
+
+/// Collections that derived impls can unpack behind a
+/// `#[pack(len_prefix = L)]` element count, or with their count in another
+/// field.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub trait UnpackPrefixed: Sized {
+    fn unpack_elements<E, R>(src: &mut R, count: usize) -> IoResult<Self>
+    where
+        E: ByteOrder,
+        R: Read + ?Sized;
+
+    fn unpack_prefixed<E, L, R>(src: &mut R) -> IoResult<Self>
+    where
+        E: ByteOrder,
+        L: LengthPrefix + UnpackFrom,
+        R: Read + ?Sized,
+    {
+        let count = L::unpack_from::<E, _>(src)?.to_usize()?;
+        Self::unpack_elements::<E, _>(src, count)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: PackTo> PackPrefixed for Vec<T> {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn pack_elements<E, W>(&self, dst: &mut W) -> IoResult<()>
+    where
+        E: ByteOrder,
+        W: Write + ?Sized,
+    {
+        T::pack_multiple_to::<E, _>(self, dst)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: UnpackFrom> UnpackPrefixed for Vec<T> {
+    fn unpack_elements<E, R>(src: &mut R, count: usize) -> IoResult<Self>
+    where
+        E: ByteOrder,
+        R: Read + ?Sized,
+    {
+        T::unpack_multiple_vec::<E, _>(src, count)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PackPrefixed for String {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn pack_elements<E, W>(&self, dst: &mut W) -> IoResult<()>
+    where
+        E: ByteOrder,
+        W: Write + ?Sized,
+    {
+        dst.write_all(self.as_bytes())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl UnpackPrefixed for String {
+    fn unpack_elements<E, R>(src: &mut R, count: usize) -> IoResult<Self>
+    where
+        E: ByteOrder,
+        R: Read + ?Sized,
+    {
+        String::from_utf8(read_bytes(src, count)?).map_err(|e| utf8_error(e.utf8_error()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Read exactly `n` bytes, growing the buffer as data arrives so that a huge
+/// length from untrusted input cannot allocate more than the source holds.
+pub(crate) fn read_bytes<R: Read + ?Sized>(src: &mut R, n: usize) -> IoResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut chunk = [0; 256];
+    while bytes.len() < n {
+        let len = (n - bytes.len()).min(chunk.len());
+        src.read_exact(&mut chunk[..len])?;
+        bytes.extend_from_slice(&chunk[..len]);
+    }
+    Ok(bytes)
+}
+
+#[cfg(feature = "alloc")]
+fn duplicate_key() -> IoError {
+    IoError::new(ErrorKind::InvalidData, "map has a duplicate key")
+}
+
+#[cfg(feature = "alloc")]
+fn duplicate_element() -> IoError {
+    IoError::new(ErrorKind::InvalidData, "set has a duplicate element")
+}
+
+/// Implement `PackPrefixed` for a collection packed as its elements in
+/// iteration order.
+macro_rules! impl_pack_iter {
+    ($(#[$attr:meta])* impl<$($param:ident),*> for $ty:ty) => {
+        $(#[$attr])*
+        impl<$($param),*> PackPrefixed for $ty
+        where
+            T: PackTo,
+        {
+            fn count(&self) -> usize {
+                self.len()
+            }
+
+            fn pack_elements<E, W>(&self, dst: &mut W) -> IoResult<()>
+            where
+                E: ByteOrder,
+                W: Write + ?Sized,
+            {
+                for item in self {
+                    item.pack_to::<E, _>(dst)?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_pack_iter!(#[cfg(feature = "alloc")] impl<T> for VecDeque<T>);
+impl_pack_iter!(#[cfg(feature = "alloc")] impl<T> for BTreeSet<T>);
+impl_pack_iter!(#[cfg(feature = "std")] impl<T, S> for HashSet<T, S>);
+
+#[cfg(feature = "alloc")]
+impl<T: UnpackFrom> UnpackPrefixed for VecDeque<T> {
+    fn unpack_elements<E, R>(src: &mut R, count: usize) -> IoResult<Self>
+    where
+        E: ByteOrder,
+        R: Read + ?Sized,
+    {
+        Vec::unpack_elements::<E, _>(src, count).map(VecDeque::from)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: UnpackFrom + Ord> UnpackPrefixed for BTreeSet<T> {
+    fn unpack_elements<E, R>(src: &mut R, count: usize) -> IoResult<Self>
+    where
+        E: ByteOrder,
+        R: Read + ?Sized,
+    {
+        let mut set = BTreeSet::new();
+        unpack_each::<E, T, _>(src, count, |item| match set.insert(item) {
+            true => Ok(()),
+            false => Err(duplicate_element()),
+        })?;
+        Ok(set)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> UnpackPrefixed for HashSet<T, S>
+where
+    T: UnpackFrom + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn unpack_elements<E, R>(src: &mut R, count: usize) -> IoResult<Self>
+    where
+        E: ByteOrder,
+        R: Read + ?Sized,
+    {
+        let mut set = HashSet::default();
+        unpack_each::<E, T, _>(src, count, |item| match set.insert(item) {
+            true => Ok(()),
+            false => Err(duplicate_element()),
+        })?;
+        Ok(set)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: PackTo, V: PackTo> PackPrefixed for BTreeMap<K, V> {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn pack_elements<E, W>(&self, dst: &mut W) -> IoResult<()>
+    where
+        E: ByteOrder,
+        W: Write + ?Sized,
+    {
+        for entry in self {
+            entry.pack_to::<E, _>(dst)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: UnpackFrom + Ord, V: UnpackFrom> UnpackPrefixed for BTreeMap<K, V> {
+    fn unpack_elements<E, R>(src: &mut R, count: usize) -> IoResult<Self>
+    where
+        E: ByteOrder,
+        R: Read + ?Sized,
+    {
+        let mut map = BTreeMap::new();
+        unpack_each::<E, (K, V), _>(src, count, |(key, value)| match map.insert(key, value) {
+            None => Ok(()),
+            Some(_) => Err(duplicate_key()),
+        })?;
+        Ok(map)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: PackTo, V: PackTo, S> PackPrefixed for HashMap<K, V, S> {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn pack_elements<E, W>(&self, dst: &mut W) -> IoResult<()>
+    where
+        E: ByteOrder,
+        W: Write + ?Sized,
+    {
+        for entry in self {
+            entry.pack_to::<E, _>(dst)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> UnpackPrefixed for HashMap<K, V, S>
+where
+    K: UnpackFrom + Eq + Hash,
+    V: UnpackFrom,
+    S: BuildHasher + Default,
+{
+    fn unpack_elements<E, R>(src: &mut R, count: usize) -> IoResult<Self>
+    where
+        E: ByteOrder,
+        R: Read + ?Sized,
+    {
+        let mut map = HashMap::default();
+        unpack_each::<E, (K, V), _>(src, count, |(key, value)| match map.insert(key, value) {
+            None => Ok(()),
+            Some(_) => Err(duplicate_key()),
+        })?;
+        Ok(map)
+    }
+}
@@ -17,10 +17,358 @@
 //! assert_eq!(b, 2);
 //! assert_eq!(cd, [3, 4]);
 //! ```
+//!
+//! ## Format strings
+//!
+//! With the `macros` feature, `pack!` and `unpack!` accept Python `struct`
+//! format strings such as `">HHI4s"`, checked and expanded at compile time
+//! into calls to [`PackTo`] and [`UnpackFrom`].
+//!
+//! ## Derive
+//!
+//! With the `derive` feature, `#[derive(PackTo, UnpackFrom, Describe)]` on a
+//! struct packs and unpacks the fields in declaration order and describes
+//! them by name, so protocol structs and newtypes need no hand-written impls.
+//! Enums are packed as an integer tag, chosen with `#[pack(tag = u8)]` or the
+//! enum's `#[repr]`, followed by the fields of the variant, or with
+//! `#[pack(untagged)]` as just the fields, told apart by their contents.
+//!
+//! ## Untrusted input
+//!
+//! Unpacking never panics, whatever bytes the source yields: malformed or
+//! truncated input is always reported through the returned [`io::Error`].
+//! Lengths read from the wire are converted with [`LengthPrefix`], which fails
+//! with [`LengthOverflow`] instead of truncating, including on targets where
+//! `usize` is only 16 or 32 bits wide (see [`MAX_LEN`]).
+//!
+//! The `arbitrary` feature, which needs `std`, implements
+//! `arbitrary::Arbitrary` for the crate's wrapper types so fuzz targets can
+//! generate structured values directly.
+//! Likewise, the `proptest` feature provides strategies and a round-trip check
+//! in the `strategy` module for property-testing custom impls.
+//!
+//! ## Optional features
+//!
+//! With the `tracing` feature, decoding structured layouts emits a `debug`
+//! span per struct and an event per field with its name, offset and value, so
+//! misbehaving decodes of nested formats can be diagnosed from logs.
+//!
+//! Values can be hashed in their packed form without an intermediate buffer
+//! by packing into a [`HasherWriter`], or with the `digest` feature, into any
+//! `digest` hash function with `pack_hash`.
+//!
+//! The `half` feature implements the traits for the 16-bit floats `half::f16`
+//! and `half::bf16`, including bulk packing and unpacking of slices.
+//!
+//! With the `ethnum` feature, the 256-bit integers `ethnum::U256` and
+//! `ethnum::I256` are packed as 32 bytes in the requested byte order, like the
+//! primitive integers.
+//!
+//! The `num-bigint` feature packs `BigUint` as its big-endian magnitude behind
+//! a `u32` byte count, as in key material and DER-style integers, and `BigInt`
+//! as a sign byte followed by its magnitude.
+//!
+//! The `rust_decimal` feature packs `rust_decimal::Decimal` exactly, as 16
+//! bytes of flags and mantissa, for financial records.
+//!
+//! The `uuid` feature packs `uuid::Uuid` as its sixteen bytes, and `Guid`
+//! as a Microsoft GUID with its first fields little-endian.
+//!
+//! The `chrono` and `time` features pack `chrono::DateTime<Utc>` and
+//! `time::OffsetDateTime` like `SystemTime`, as seconds and nanoseconds since
+//! the Unix epoch.
+//!
+//! With the `embedded-io` feature, `EmbeddedIo` adapts `embedded_io` drivers
+//! (UARTs, SPI and the like) so values can be packed to and unpacked from them
+//! directly.
+//!
+//! With the `tokio` feature, `AsyncPackTo` and `AsyncUnpackFrom` pack to
+//! any `AsyncWrite` and unpack from any `AsyncRead`, with the same byte
+//! order helpers as the blocking traits. The `futures` feature does the same
+//! for the `futures-io` traits used by smol and async-std, through the
+//! `FuturesIo` adapter.
+//!
+//! On nightly, the `portable-simd` feature implements the traits for
+//! `std::simd::Simd`, packing the lanes in order.
+//!
+//! The `cli` feature adds the `cli` module, which dumps and patches binary
+//! files field by field from a type's `Describe` schema, and the
+//! `byteorder-pack-cli` binary, which does the same for a format string.
+//!
+//! A `Schema` can also be exported for use outside Rust: `kaitai_yaml` writes
+//! a Kaitai Struct definition for Kaitai's visualizer and code generators,
+//! `c_header` declares matching packed C structs and `python_format` gives
+//! the equivalent Python `struct` format string. For humans, `layout_table`
+//! renders a Markdown table of field offsets, sizes and types, and
+//! `wireshark_dissector` generates a Lua dissector for inspecting captures.
+//!
+//! ## `no_std`
+//!
+//! The `std` feature is enabled by default. Without it the crate is `no_std`
+//! and the traits work over the minimal [`io::Read`] and [`io::Write`] traits
+//! defined in the [`io`] module, which are implemented for byte slices. The
+//! `alloc` feature additionally enables support for `Vec` and other heap types.
+//! The derives work without it, except for `len_prefix` and `count` fields and
+//! untagged enums, which need `alloc`.
+//! For bare-metal code that only deals with byte buffers, [`pack_to_slice`]
+//! and [`unpack_from_slice`] avoid the I/O traits entirely, while
+//! [`SliceWriter`] and [`SliceReader`] provide position-tracked sinks and
+//! sources over plain byte slices.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub use byteorder;
 
+#[cfg(feature = "arbitrary")]
+pub use arbitrary;
+#[cfg(feature = "digest")]
+pub use digest;
+#[cfg(feature = "uuid")]
+pub use uuid;
+#[cfg(feature = "chrono")]
+pub use chrono;
+#[cfg(feature = "time")]
+pub use time;
+#[cfg(feature = "half")]
+pub use half;
+#[cfg(feature = "ethnum")]
+pub use ethnum;
+#[cfg(feature = "num-bigint")]
+pub use num_bigint;
+#[cfg(feature = "rust_decimal")]
+pub use rust_decimal;
+#[cfg(feature = "macros")]
+pub use byteorder_pack_macros::{pack, unpack};
+#[cfg(feature = "derive")]
+pub use byteorder_pack_derive::{Describe, PackTo, UnpackFrom};
+
+pub mod io;
+pub mod network;
+
+mod trace;
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub use crate::describe::StructSchema;
+    #[cfg(feature = "alloc")]
+    pub use crate::len::{PackPrefixed, UnpackPrefixed};
+    pub use crate::trace::*;
+}
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "alloc")]
+pub mod testutil;
+
 mod pack;
 pub use pack::PackTo;
 
 mod unpack;
 pub use unpack::UnpackFrom;
+
+mod bcd;
+pub use bcd::Bcd;
+
+mod crc;
+pub use crc::{crc32, Crc32};
+
+mod delta;
+pub use delta::Delta;
+
+mod hash;
+#[cfg(feature = "digest")]
+pub use hash::{pack_hash, DigestWriter};
+pub use hash::HasherWriter;
+
+mod int;
+pub use int::{I24, U24, U40, U48};
+
+mod interleave;
+pub use interleave::{pack_interleaved, unpack_interleaved, Columns, ColumnsMut};
+
+mod len;
+pub use len::{unpack_str, LengthOverflow, LengthPrefix, MAX_LEN};
+#[cfg(feature = "alloc")]
+pub use len::{unpack_string, unpack_vec, LengthPrefixed};
+
+mod fixed_point;
+pub use fixed_point::{FixedPoint, Q15, Q31};
+
+mod fixed_str;
+pub use fixed_str::{FixedStr, NulPadded, Padding, SpacePadded};
+
+mod varint;
+pub use varint::{SevenBitEncoded, VarI64, VarU32, VarU64, VariableByteInteger, ZigZag};
+
+mod size;
+pub use size::FixedPackSize;
+
+#[cfg(feature = "half")]
+mod float16;
+
+#[cfg(feature = "ethnum")]
+mod wide;
+
+#[cfg(feature = "num-bigint")]
+mod bigint;
+
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+
+#[cfg(feature = "portable-simd")]
+mod simd;
+
+mod quantize;
+pub use quantize::{NormRange, SNorm16, SNorm8, SignedUnit, UNorm16, UNorm8, Unit};
+
+mod timestamp;
+
+mod slice;
+pub use slice::{pack_to_slice, unpack_from_slice, SliceError, SliceReader, SliceWriter};
+
+mod net;
+pub use net::MacAddr;
+
+mod presence;
+pub use presence::{Maybe, OptionSentinel, OptionalFields, Presence, SentinelInt};
+
+mod progress;
+pub use progress::{Progress, ProgressReader};
+
+#[cfg(any(feature = "tokio", feature = "futures"))]
+mod async_io;
+#[cfg(feature = "futures")]
+pub use async_io::FuturesIo;
+#[cfg(any(feature = "tokio", feature = "futures"))]
+pub use async_io::{
+    AsyncPackSink, AsyncPackTo, AsyncUnpackFrom, AsyncUnpackSource, PackFuture, UnpackFuture,
+};
+
+#[cfg(feature = "alloc")]
+mod frame;
+#[cfg(feature = "alloc")]
+pub use frame::{CrcFrame, FrameReader, FrameWriter, DEFAULT_MAX_FRAME_LEN};
+
+#[cfg(feature = "alloc")]
+mod c_header;
+#[cfg(feature = "alloc")]
+pub use c_header::c_header;
+
+#[cfg(feature = "alloc")]
+mod cstr;
+#[cfg(feature = "alloc")]
+pub use cstr::NulTerminated;
+
+#[cfg(feature = "alloc")]
+mod describe;
+#[cfg(feature = "alloc")]
+pub use describe::Describe;
+
+#[cfg(feature = "alloc")]
+mod diff;
+#[cfg(feature = "alloc")]
+pub use diff::{diff, FieldDiff};
+
+#[cfg(feature = "alloc")]
+mod format;
+#[cfg(feature = "alloc")]
+pub use format::{python_format, Format, FormatError};
+
+#[cfg(feature = "alloc")]
+mod rle;
+#[cfg(feature = "alloc")]
+pub use rle::Rle;
+
+#[cfg(feature = "alloc")]
+mod schema;
+#[cfg(feature = "alloc")]
+pub use schema::Schema;
+
+#[cfg(feature = "alloc")]
+mod value;
+#[cfg(feature = "alloc")]
+pub use value::Value;
+
+#[cfg(feature = "alloc")]
+mod wireshark;
+#[cfg(feature = "alloc")]
+pub use wireshark::wireshark_dissector;
+
+#[cfg(feature = "alloc")]
+mod hexdump;
+#[cfg(feature = "alloc")]
+pub use hexdump::PackedDebug;
+
+#[cfg(feature = "alloc")]
+mod index;
+#[cfg(feature = "alloc")]
+pub use index::{IndexEntry, IndexingReader, RecordIndex};
+
+#[cfg(feature = "alloc")]
+mod kaitai;
+#[cfg(feature = "alloc")]
+pub use kaitai::kaitai_yaml;
+
+#[cfg(feature = "alloc")]
+mod layout;
+#[cfg(feature = "alloc")]
+pub use layout::layout_table;
+
+#[cfg(feature = "alloc")]
+mod mux;
+#[cfg(feature = "alloc")]
+pub use mux::{ChannelWriter, DemuxReader, MuxWriter};
+
+#[cfg(feature = "uuid")]
+mod guid;
+#[cfg(feature = "uuid")]
+pub use guid::Guid;
+
+#[cfg(feature = "embedded-io")]
+mod embedded;
+#[cfg(feature = "embedded-io")]
+pub use embedded::EmbeddedIo;
+
+#[cfg(feature = "std")]
+mod backpatch;
+#[cfg(feature = "std")]
+pub use backpatch::{Placeholder, SectionBuilder};
+
+#[cfg(feature = "std")]
+mod delimit;
+#[cfg(feature = "std")]
+pub use delimit::{DelimitedReader, DelimitedWriter, Delimiter};
+
+#[cfg(feature = "std")]
+mod fixed_records;
+#[cfg(feature = "std")]
+pub use fixed_records::{FixedRecords, FixedRecordsIter};
+
+#[cfg(feature = "std")]
+mod log;
+#[cfg(feature = "std")]
+pub use log::{RecordLog, RecordLogIter, Truncate};
+
+#[cfg(feature = "std")]
+mod record_file;
+#[cfg(feature = "std")]
+pub use record_file::RecordFile;
+
+#[cfg(feature = "std")]
+mod riff;
+#[cfg(feature = "std")]
+pub use riff::{Chunk, ChunkReader, ChunkWriter, FourCc};
+
+#[cfg(feature = "std")]
+mod trailer;
+#[cfg(feature = "std")]
+pub use trailer::{find_trailing_magic, unpack_trailer};
+
+#[cfg(feature = "std")]
+mod resync;
+#[cfg(feature = "std")]
+pub use resync::{Recovered, Resync, SyncPattern};
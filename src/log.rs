@@ -0,0 +1,272 @@
+use std::fs::File;
+use std::io::{
+    Cursor, Error as IoError, ErrorKind, Read, Result as IoResult, Seek, SeekFrom, Write,
+};
+use std::marker::PhantomData;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{Crc32, LengthPrefix, PackTo, SliceReader, UnpackFrom};
+
+/// A destination that can be cut short, used by [`RecordLog`] to drop a torn
+/// final record.
+pub trait Truncate {
+    /// Truncate or extend the underlying storage to `len` bytes.
+    fn set_len(&mut self, len: u64) -> IoResult<()>;
+}
+
+impl Truncate for File {
+    fn set_len(&mut self, len: u64) -> IoResult<()> {
+        File::set_len(self, len)
+    }
+}
+
+impl Truncate for Cursor<Vec<u8>> {
+    fn set_len(&mut self, len: u64) -> IoResult<()> {
+        self.get_mut().resize(len.to_usize()?, 0);
+        Ok(())
+    }
+}
+
+impl<T: Truncate + ?Sized> Truncate for &mut T {
+    fn set_len(&mut self, len: u64) -> IoResult<()> {
+        (**self).set_len(len)
+    }
+}
+
+/// Size of the sequence number, length and CRC surrounding each payload.
+const OVERHEAD: u64 = 8 + 4 + 4;
+
+/// An append-only log of packed records.
+///
+/// Each record is stored as a `u64` sequence number, a `u32` payload length,
+/// the packed payload and a [`Crc32`] of everything before it, in byte order
+/// `E`. Sequence numbers start at 0 and increase by one per record.
+///
+/// [`RecordLog::open`] validates the existing records and truncates the log if
+/// its last record is torn or corrupt, so a crash during an append loses at
+/// most that record. A record counts as the torn last one only if fewer than
+/// its 12 header bytes remain, or if its length ends it exactly at the end of
+/// the file. Any other damage, including to the length of an earlier record,
+/// fails with [`ErrorKind::InvalidData`] instead, leaving the file as it is. The offsets
+/// of all valid records are kept in memory for random access with
+/// [`get`](Self::get).
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder_pack::RecordLog;
+///
+/// let mut log = RecordLog::<_>::open(Cursor::new(vec![])).unwrap();
+/// log.append(&(1u8, 2u16)).unwrap();
+/// log.append(&(3u8, 4u16)).unwrap();
+///
+/// // Simulate a crash in the middle of a third append.
+/// let mut file = log.into_inner();
+/// file.get_mut().extend_from_slice(&[0x00, 0x00, 0x00]);
+///
+/// let mut log = RecordLog::<_>::open(file).unwrap();
+/// assert_eq!(log.len(), 2);
+/// assert_eq!(log.get::<(u8, u16)>(1).unwrap(), (3, 4));
+///
+/// let records: Vec<(u64, (u8, u16))> = log.iter().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(records, vec![(0, (1, 2)), (1, (3, 4))]);
+///
+/// // Corrupt the payload of the first record, which is followed by another.
+/// let mut file = log.into_inner();
+/// file.get_mut()[13] ^= 0xff;
+/// assert!(RecordLog::<_>::open(&mut file).is_err());
+/// assert_eq!(file.get_ref().len(), 38);
+///
+/// // Corrupt its length instead, which no longer points at the next record.
+/// file.get_mut()[13] ^= 0xff;
+/// file.get_mut()[8] ^= 0x01;
+/// assert!(RecordLog::<_>::open(&mut file).is_err());
+/// assert_eq!(file.get_ref().len(), 38);
+/// ```
+#[derive(Debug)]
+pub struct RecordLog<F, E = BigEndian> {
+    file: F,
+    offsets: Vec<u64>,
+    end: u64,
+    truncated: u64,
+    buf: Vec<u8>,
+    _marker: PhantomData<E>,
+}
+
+impl<F: Read + Write + Seek + Truncate, E: ByteOrder> RecordLog<F, E> {
+    /// Open a log stored in `file`, validating existing records and truncating
+    /// a torn or corrupt last record.
+    ///
+    /// Fails with [`ErrorKind::InvalidData`] if a record before the last is
+    /// damaged, since truncating there would drop the valid records after it.
+    pub fn open(mut file: F) -> IoResult<Self> {
+        let len = file.seek(SeekFrom::End(0))?;
+        let mut log = Self {
+            file,
+            offsets: Vec::new(),
+            end: 0,
+            truncated: 0,
+            buf: Vec::new(),
+            _marker: PhantomData,
+        };
+        while log.end < len {
+            let seq = log.offsets.len() as u64;
+            match log.read_entry(log.end, seq) {
+                Ok(next) => {
+                    log.offsets.push(log.end);
+                    log.end = next;
+                }
+                Err(e) if matches!(e.kind(), ErrorKind::InvalidData | ErrorKind::UnexpectedEof) => {
+                    if !log.runs_to_end(log.end, len)? {
+                        return Err(IoError::new(
+                            ErrorKind::InvalidData,
+                            "corrupt record before the end of the log",
+                        ));
+                    }
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if log.end < len {
+            log.file.set_len(log.end)?;
+            log.truncated = len - log.end;
+        }
+        Ok(log)
+    }
+
+    /// Number of records in the log.
+    pub fn len(&self) -> u64 {
+        self.offsets.len() as u64
+    }
+
+    /// Whether the log has no records.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Number of bytes dropped from the end of the log when it was opened.
+    pub fn truncated(&self) -> u64 {
+        self.truncated
+    }
+
+    /// Append `value` as a new record, returning its sequence number.
+    pub fn append<T: PackTo>(&mut self, value: &T) -> IoResult<u64> {
+        let seq = self.len();
+        let mut record = Vec::new();
+        seq.pack_to::<E, _>(&mut record)?;
+        0u32.pack_to::<E, _>(&mut record)?;
+        value.pack_to::<E, _>(&mut record)?;
+        let len = u32::from_usize(record.len() - 12)?;
+        E::write_u32(&mut record[8..12], len);
+        let mut crc = Crc32::new();
+        crc.update(&record);
+        crc.finish().pack_to::<E, _>(&mut record)?;
+
+        self.file.seek(SeekFrom::Start(self.end))?;
+        self.file.write_all(&record)?;
+        self.file.flush()?;
+        self.offsets.push(self.end);
+        self.end += record.len() as u64;
+        Ok(seq)
+    }
+
+    /// Read the record with sequence number `seq`.
+    pub fn get<T: UnpackFrom>(&mut self, seq: u64) -> IoResult<T> {
+        let offset = *usize::try_from(seq)
+            .ok()
+            .and_then(|i| self.offsets.get(i))
+            .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "no such record"))?;
+        self.read_entry(offset, seq)?;
+        let mut payload = SliceReader::new(&self.buf);
+        let value = T::unpack_from::<E, _>(&mut payload)?;
+        if !payload.is_empty() {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "trailing bytes in record",
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Iterate over all records in order, yielding their sequence numbers and
+    /// values.
+    pub fn iter<T: UnpackFrom>(&mut self) -> RecordLogIter<'_, F, T, E> {
+        RecordLogIter {
+            log: self,
+            seq: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Unwrap the underlying storage.
+    pub fn into_inner(self) -> F {
+        self.file
+    }
+
+    /// Whether the record at `offset` is the last in a file of `len` bytes,
+    /// as one torn by a crash during an append would be: either its header
+    /// is cut short or its length ends it exactly at the end of the file.
+    fn runs_to_end(&mut self, offset: u64, len: u64) -> IoResult<bool> {
+        if len - offset < 12 {
+            return Ok(true);
+        }
+        self.file.seek(SeekFrom::Start(offset + 8))?;
+        let payload_len = u32::unpack_from::<E, _>(&mut self.file)?;
+        Ok(offset + OVERHEAD + u64::from(payload_len) == len)
+    }
+
+    /// Validate the record at `offset` and load its payload into `self.buf`,
+    /// returning the offset just past it.
+    fn read_entry(&mut self, offset: u64, expected_seq: u64) -> IoResult<u64> {
+        self.file.seek(SeekFrom::Start(offset))?;
+        let (seq, len) = <(u64, u32)>::unpack_from::<E, _>(&mut self.file)?;
+        if seq != expected_seq {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "unexpected record sequence number",
+            ));
+        }
+        self.buf.clear();
+        let read = (&mut self.file)
+            .take(len.into())
+            .read_to_end(&mut self.buf)?;
+        if read != len.to_usize()? {
+            return Err(IoError::new(ErrorKind::UnexpectedEof, "torn record"));
+        }
+        let mut crc = Crc32::new();
+        (seq, len).pack_to::<E, _>(&mut crc)?;
+        crc.update(&self.buf);
+        if u32::unpack_from::<E, _>(&mut self.file)? != crc.finish() {
+            return Err(IoError::new(ErrorKind::InvalidData, "record CRC mismatch"));
+        }
+        Ok(offset + OVERHEAD + u64::from(len))
+    }
+}
+
+/// Iterator over the records of a [`RecordLog`], created by
+/// [`RecordLog::iter`].
+#[derive(Debug)]
+pub struct RecordLogIter<'a, F, T, E = BigEndian> {
+    log: &'a mut RecordLog<F, E>,
+    seq: u64,
+    _marker: PhantomData<T>,
+}
+
+impl<F, T, E> Iterator for RecordLogIter<'_, F, T, E>
+where
+    F: Read + Write + Seek + Truncate,
+    T: UnpackFrom,
+    E: ByteOrder,
+{
+    type Item = IoResult<(u64, T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.seq >= self.log.len() {
+            return None;
+        }
+        let seq = self.seq;
+        self.seq += 1;
+        Some(self.log.get(seq).map(|value| (seq, value)))
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::marker::PhantomData;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::frame::{read_header, read_payload};
+use crate::io::{Read, Result as IoResult, Write};
+use crate::{FixedPackSize, LengthPrefix, PackTo, UnpackFrom, DEFAULT_MAX_FRAME_LEN};
+
+/// Interleaves messages from several channels onto one writer.
+///
+/// Each message is written as a frame of the channel id (a `C`), the packed
+/// length of the message (an `L`) and the packed message, all in byte order
+/// `E`. Frames are written whole, so messages from different
+/// [`ChannelWriter`]s never interleave mid-frame.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{DemuxReader, MuxWriter};
+///
+/// let mux = MuxWriter::<_, u8, u16>::new(vec![]);
+/// let (control, data) = (mux.channel(0), mux.channel(1));
+///
+/// data.write(&0x0102u16).unwrap();
+/// control.write(&0x03u8).unwrap();
+///
+/// let bytes = mux.into_inner();
+/// assert_eq!(bytes, vec![0x01, 0x00, 0x02, 0x01, 0x02, 0x00, 0x00, 0x01, 0x03]);
+///
+/// let mut demux = DemuxReader::<_, u8, u16>::new(&bytes[..]);
+/// assert_eq!(demux.read::<u16>().unwrap(), Some((1, 0x0102)));
+/// ```
+#[derive(Debug)]
+pub struct MuxWriter<W, C = u8, L = u32, E = BigEndian> {
+    inner: RefCell<(W, Vec<u8>)>,
+    _marker: PhantomData<(C, L, E)>,
+}
+
+impl<W, C, L, E> MuxWriter<W, C, L, E>
+where
+    W: Write,
+    C: PackTo + Copy,
+    L: LengthPrefix + PackTo,
+    E: ByteOrder,
+{
+    /// Create a multiplexer writing to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: RefCell::new((inner, Vec::new())),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get a writer handle for channel `id`.
+    pub fn channel(&self, id: C) -> ChannelWriter<'_, W, C, L, E> {
+        ChannelWriter { mux: self, id }
+    }
+
+    /// Write `msg` as a frame on channel `id`.
+    pub fn write_to<T: PackTo>(&self, id: C, msg: &T) -> IoResult<()> {
+        let mut inner = self.inner.borrow_mut();
+        let (dst, buf) = &mut *inner;
+        buf.clear();
+        id.pack_to::<E, _>(buf)?;
+        let header_len = buf.len();
+        msg.pack_to::<E, _>(buf)?;
+        let len = L::from_usize(buf.len() - header_len)?;
+        dst.write_all(&buf[..header_len])?;
+        len.pack_to::<E, _>(dst)?;
+        dst.write_all(&buf[header_len..])
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner().0
+    }
+}
+
+/// A handle writing messages to a single channel of a [`MuxWriter`].
+#[derive(Debug)]
+pub struct ChannelWriter<'a, W, C = u8, L = u32, E = BigEndian> {
+    mux: &'a MuxWriter<W, C, L, E>,
+    id: C,
+}
+
+impl<W, C, L, E> ChannelWriter<'_, W, C, L, E>
+where
+    W: Write,
+    C: PackTo + Copy,
+    L: LengthPrefix + PackTo,
+    E: ByteOrder,
+{
+    /// The channel this handle writes to.
+    pub fn id(&self) -> C {
+        self.id
+    }
+
+    /// Write `msg` as a frame on this channel.
+    pub fn write<T: PackTo>(&self, msg: &T) -> IoResult<()> {
+        self.mux.write_to(self.id, msg)
+    }
+}
+
+/// Reads the frames written by a [`MuxWriter`] with the same `C`, `L` and
+/// `E`, yielding each message together with its channel id.
+#[derive(Debug)]
+pub struct DemuxReader<R, C = u8, L = u32, E = BigEndian> {
+    inner: R,
+    buf: Vec<u8>,
+    max_len: usize,
+    _marker: PhantomData<(C, L, E)>,
+}
+
+impl<R, C, L, E> DemuxReader<R, C, L, E>
+where
+    R: Read,
+    C: UnpackFrom + FixedPackSize,
+    L: LengthPrefix + UnpackFrom + FixedPackSize,
+    E: ByteOrder,
+{
+    /// Create a demultiplexer over `inner` that accepts messages of up to
+    /// [`DEFAULT_MAX_FRAME_LEN`] bytes.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            max_len: DEFAULT_MAX_FRAME_LEN,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the maximum accepted message size in bytes.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Read the next frame, returning its channel id and message.
+    ///
+    /// Returns `None` if the source ends cleanly between frames.
+    pub fn read<T: UnpackFrom>(&mut self) -> IoResult<Option<(C, T)>> {
+        let Some((id, len)) = read_header::<E, (C, L), _>(&mut self.inner)? else {
+            return Ok(None);
+        };
+        let msg = read_payload::<E, _, _, _>(&mut self.inner, len, self.max_len, &mut self.buf)?;
+        Ok(Some((id, msg)))
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
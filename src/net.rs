@@ -0,0 +1,233 @@
+use core::fmt;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// An Ethernet MAC address (EUI-48), packed as its six octets regardless of
+/// byte order.
+///
+/// Displayed as colon-separated lowercase hex digits.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{MacAddr, PackTo, UnpackFrom};
+///
+/// let mac = MacAddr([0x02, 0x00, 0x5e, 0x10, 0x00, 0x01]);
+/// let mut buf = vec![];
+/// mac.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0x02, 0x00, 0x5e, 0x10, 0x00, 0x01]);
+///
+/// assert_eq!(MacAddr::unpack_from_be(&mut &buf[..]).unwrap(), mac);
+/// assert_eq!(mac.to_string(), "02:00:5e:10:00:01");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl MacAddr {
+    /// The broadcast address, `ff:ff:ff:ff:ff:ff`.
+    pub const BROADCAST: Self = Self([0xff; 6]);
+
+    /// Whether this is a multicast (or broadcast) address.
+    pub fn is_multicast(&self) -> bool {
+        self.0[0] & 1 != 0
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{a:02x}:{b:02x}:{c:02x}:{d:02x}:{e:02x}:{g:02x}")
+    }
+}
+
+impl From<[u8; 6]> for MacAddr {
+    fn from(octets: [u8; 6]) -> Self {
+        Self(octets)
+    }
+}
+
+impl PackTo for MacAddr {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(&self.0)
+    }
+}
+
+impl UnpackFrom for MacAddr {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        <[u8; 6]>::unpack_from::<E, _>(src).map(Self)
+    }
+}
+
+impl FixedPackSize for MacAddr {
+    const PACKED_SIZE: usize = 6;
+}
+
+/// Packs the four octets in network order, regardless of byte order.
+impl PackTo for Ipv4Addr {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(&self.octets())
+    }
+}
+
+impl UnpackFrom for Ipv4Addr {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        <[u8; 4]>::unpack_from::<E, _>(src).map(Self::from)
+    }
+}
+
+impl FixedPackSize for Ipv4Addr {
+    const PACKED_SIZE: usize = 4;
+}
+
+/// Packs the sixteen octets in network order, regardless of byte order.
+impl PackTo for Ipv6Addr {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(&self.octets())
+    }
+}
+
+impl UnpackFrom for Ipv6Addr {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        <[u8; 16]>::unpack_from::<E, _>(src).map(Self::from)
+    }
+}
+
+impl FixedPackSize for Ipv6Addr {
+    const PACKED_SIZE: usize = 16;
+}
+
+/// Packs a family byte, 4 or 6, followed by the address's octets.
+///
+/// Unpacking fails with [`ErrorKind::InvalidData`] for any other family.
+/// # Example
+/// ```rust
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use byteorder_pack::{PackTo, UnpackFrom};
+///
+/// let addr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+/// let mut buf = vec![];
+/// addr.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [4, 192, 0, 2, 1]);
+///
+/// assert_eq!(IpAddr::unpack_from_be(&mut &buf[..]).unwrap(), addr);
+/// ```
+impl PackTo for IpAddr {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        match self {
+            IpAddr::V4(addr) => (4u8, addr).pack_to::<E, _>(dst),
+            IpAddr::V6(addr) => (6u8, addr).pack_to::<E, _>(dst),
+        }
+    }
+}
+
+impl UnpackFrom for IpAddr {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        match u8::unpack_from::<E, _>(src)? {
+            4 => Ipv4Addr::unpack_from::<E, _>(src).map(IpAddr::V4),
+            6 => Ipv6Addr::unpack_from::<E, _>(src).map(IpAddr::V6),
+            _ => Err(IoError::new(
+                ErrorKind::InvalidData,
+                "unknown IP address family",
+            )),
+        }
+    }
+}
+
+/// Packs the address followed by the port, which is in the requested byte
+/// order.
+/// # Example
+/// ```rust
+/// use std::net::{Ipv4Addr, SocketAddrV4};
+/// use byteorder_pack::{PackTo, UnpackFrom};
+///
+/// let addr = SocketAddrV4::new(Ipv4Addr::new(192, 0, 2, 1), 1080);
+/// let mut buf = vec![];
+/// addr.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [192, 0, 2, 1, 0x04, 0x38]);
+///
+/// assert_eq!(SocketAddrV4::unpack_from_be(&mut &buf[..]).unwrap(), addr);
+/// ```
+impl PackTo for SocketAddrV4 {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (self.ip(), self.port()).pack_to::<E, _>(dst)
+    }
+}
+
+impl UnpackFrom for SocketAddrV4 {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let (ip, port) = UnpackFrom::unpack_from::<E, _>(src)?;
+        Ok(Self::new(ip, port))
+    }
+}
+
+impl FixedPackSize for SocketAddrV4 {
+    const PACKED_SIZE: usize = <(Ipv4Addr, u16)>::PACKED_SIZE;
+}
+
+/// Packs the address followed by the port, flow information and scope ID,
+/// which are in the requested byte order.
+impl PackTo for SocketAddrV6 {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (self.ip(), self.port(), self.flowinfo(), self.scope_id()).pack_to::<E, _>(dst)
+    }
+}
+
+impl UnpackFrom for SocketAddrV6 {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let (ip, port, flowinfo, scope_id) = UnpackFrom::unpack_from::<E, _>(src)?;
+        Ok(Self::new(ip, port, flowinfo, scope_id))
+    }
+}
+
+impl FixedPackSize for SocketAddrV6 {
+    const PACKED_SIZE: usize = <(Ipv6Addr, u16, u32, u32)>::PACKED_SIZE;
+}
+
+#[cfg(feature = "alloc")]
+impl crate::Describe for MacAddr {
+    fn schema() -> crate::Schema {
+        crate::Schema::Bytes(6)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl crate::Describe for Ipv4Addr {
+    fn schema() -> crate::Schema {
+        crate::Schema::Bytes(4)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl crate::Describe for Ipv6Addr {
+    fn schema() -> crate::Schema {
+        crate::Schema::Bytes(16)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl crate::Describe for SocketAddrV4 {
+    fn schema() -> crate::Schema {
+        crate::Schema::Struct(alloc::vec![
+            ("ip".into(), crate::Schema::Bytes(4)),
+            ("port".into(), crate::Schema::U16),
+        ])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl crate::Describe for SocketAddrV6 {
+    fn schema() -> crate::Schema {
+        crate::Schema::Struct(alloc::vec![
+            ("ip".into(), crate::Schema::Bytes(16)),
+            ("port".into(), crate::Schema::U16),
+            ("flowinfo".into(), crate::Schema::U32),
+            ("scope_id".into(), crate::Schema::U32),
+        ])
+    }
+}
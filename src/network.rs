@@ -0,0 +1,56 @@
+//! Variants of [`PackTo`](crate::PackTo) and [`UnpackFrom`](crate::UnpackFrom)
+//! that default to network byte order ([`BigEndian`]).
+//!
+//! Import these traits instead of the ones at the crate root to write the
+//! common case without a turbofish; [`PackTo::pack_to_with`] and
+//! [`UnpackFrom::unpack_from_with`] still take an explicit byte order. Both
+//! are implemented for every type implementing the crate-root traits, so custom
+//! impls only need to implement those.
+//! # Example
+//! ```rust
+//! use byteorder_pack::byteorder::LittleEndian;
+//! use byteorder_pack::network::{PackTo, UnpackFrom};
+//!
+//! let mut buf = vec![];
+//! (1u8, 2u16).pack_to(&mut buf).unwrap();
+//! 3u16.pack_to_with::<LittleEndian, _>(&mut buf).unwrap();
+//!
+//! assert_eq!(buf, [0x01, 0x00, 0x02, 0x03, 0x00]);
+//! assert_eq!(<(u8, u16)>::unpack_from(&mut &buf[..]).unwrap(), (1, 2));
+//! ```
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::io::{Read, Result as IoResult, Write};
+
+/// Write a value into a [`Write`], in [`BigEndian`] order unless specified
+/// otherwise.
+pub trait PackTo: crate::PackTo {
+    /// Pack binary data into `dst` in [`BigEndian`] order.
+    fn pack_to<W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        crate::PackTo::pack_to::<BigEndian, _>(self, dst)
+    }
+
+    /// Pack binary data into `dst` in byte order `E`.
+    fn pack_to_with<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        crate::PackTo::pack_to::<E, _>(self, dst)
+    }
+}
+
+impl<T: crate::PackTo> PackTo for T {}
+
+/// Read a value from a [`Read`], in [`BigEndian`] order unless specified
+/// otherwise.
+pub trait UnpackFrom: crate::UnpackFrom {
+    /// Unpack binary data from `src` in [`BigEndian`] order.
+    fn unpack_from<R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        <Self as crate::UnpackFrom>::unpack_from::<BigEndian, _>(src)
+    }
+
+    /// Unpack binary data from `src` in byte order `E`.
+    fn unpack_from_with<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        <Self as crate::UnpackFrom>::unpack_from::<E, _>(src)
+    }
+}
+
+impl<T: crate::UnpackFrom> UnpackFrom for T {}
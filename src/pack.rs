@@ -1,6 +1,18 @@
-use std::io::{Result as IoResult, Write};
+use core::ffi::CStr;
+use core::mem::size_of;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8, Saturating, Wrapping,
+};
+use core::ops::{Range, RangeInclusive};
 
-use byteorder::{BigEndian, ByteOrder, LittleEndian, WriteBytesExt};
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, string::String, vec::Vec};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::io::{Result as IoResult, Write};
 
 /// Write a value into a [`Write`].
 pub trait PackTo: Sized {
@@ -51,6 +63,38 @@ pub trait PackTo: Sized {
     }
 
     /// Pack multiple values into `dest`.
+    ///
+    /// Like all packing, this writes through [`Write::write_all`], so writes
+    /// failing with `ErrorKind::Interrupted` are retried and short writes are
+    /// resumed where they stopped.
+    /// # Example
+    /// ```rust
+    /// use std::io::{self, ErrorKind, Write};
+    /// use byteorder::BigEndian;
+    /// use byteorder_pack::PackTo;
+    ///
+    /// /// Accepts one byte per call and is interrupted every other call.
+    /// struct Flaky(Vec<u8>, bool);
+    ///
+    /// impl Write for Flaky {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         self.1 = !self.1;
+    ///         if self.1 {
+    ///             return Err(ErrorKind::Interrupted.into());
+    ///         }
+    ///         self.0.push(buf[0]);
+    ///         Ok(1)
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut dst = Flaky(vec![], false);
+    /// u16::pack_multiple_to::<BigEndian, _>(&[1, 2, 3], &mut dst).unwrap();
+    /// assert_eq!(dst.0, [0, 1, 0, 2, 0, 3]);
+    /// ```
     fn pack_multiple_to<E: ByteOrder, W: Write + ?Sized>(
         buf: &[Self],
         dst: &mut W,
@@ -117,8 +161,10 @@ macro_rules! impl_primitive {
     ($($name:ident => $ty:ty),+) => {
         $(
             impl PackTo for $ty {
-                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, src: &mut W) -> IoResult<()> {
-                    src.$name::<E>(*self)
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    let mut buf = [0; size_of::<$ty>()];
+                    E::$name(&mut buf, *self);
+                    dst.write_all(&buf)
                 }
             }
         )+
@@ -133,7 +179,7 @@ impl_primitive!(
 
 impl PackTo for u8 {
     fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
-        dst.write_u8(*self)
+        dst.write_all(&[*self])
     }
 
     fn pack_multiple_to<E: ByteOrder, W: Write + ?Sized>(
@@ -147,7 +193,81 @@ impl PackTo for u8 {
 
 impl PackTo for i8 {
     fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
-        dst.write_i8(*self)
+        dst.write_all(&[*self as u8])
+    }
+}
+
+/// Packs `true` as 1 and `false` as 0, in a single byte.
+impl PackTo for bool {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(&[*self as u8])
+    }
+}
+
+/// Packs the code point as a `u32`.
+impl PackTo for char {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        u32::from(*self).pack_to::<E, _>(dst)
+    }
+}
+
+macro_rules! impl_nonzero {
+    ($($ty:ty),+) => {
+        $(
+            impl PackTo for $ty {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    self.get().pack_to::<E, _>(dst)
+                }
+            }
+
+            /// Packs `None` as zero.
+            impl PackTo for Option<$ty> {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    self.map_or(0, |value| value.get()).pack_to::<E, _>(dst)
+                }
+            }
+        )+
+    };
+}
+
+impl_nonzero!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128
+);
+
+impl<T: PackTo> PackTo for Wrapping<T> {
+    #[inline]
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        self.0.pack_to::<E, _>(dst)
+    }
+}
+
+impl<T: PackTo> PackTo for Saturating<T> {
+    #[inline]
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        self.0.pack_to::<E, _>(dst)
+    }
+}
+
+/// Packs the start followed by the end.
+impl<T: PackTo> PackTo for Range<T> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (&self.start, &self.end).pack_to::<E, _>(dst)
+    }
+}
+
+/// Packs the start followed by the (inclusive) end.
+impl<T: PackTo> PackTo for RangeInclusive<T> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (self.start(), self.end()).pack_to::<E, _>(dst)
     }
 }
 
@@ -164,3 +284,92 @@ impl<T: PackTo + Copy> PackTo for &[T] {
         Ok(())
     }
 }
+
+/// Packs the elements only, without their count. Readers need the count from
+/// elsewhere, such as [`unpack_vec`](crate::unpack_vec).
+#[cfg(feature = "alloc")]
+impl<T: PackTo> PackTo for Vec<T> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        T::pack_multiple_to::<E, _>(self, dst)
+    }
+}
+
+/// Packs the elements only, without their count.
+#[cfg(feature = "alloc")]
+impl<T: PackTo> PackTo for Box<[T]> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        T::pack_multiple_to::<E, _>(self, dst)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: PackTo> PackTo for Box<T> {
+    #[inline]
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (**self).pack_to::<E, _>(dst)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: PackTo> PackTo for Rc<T> {
+    #[inline]
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (**self).pack_to::<E, _>(dst)
+    }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T: PackTo> PackTo for Arc<T> {
+    #[inline]
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (**self).pack_to::<E, _>(dst)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: PackTo + Clone> PackTo for Cow<'_, T> {
+    #[inline]
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (**self).pack_to::<E, _>(dst)
+    }
+}
+
+/// Packs the elements only, without their count.
+#[cfg(feature = "alloc")]
+impl<T: PackTo + Clone> PackTo for Cow<'_, [T]> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        T::pack_multiple_to::<E, _>(self, dst)
+    }
+}
+
+/// Packs the UTF-8 bytes only, without their length or a terminator.
+#[cfg(feature = "alloc")]
+impl PackTo for Cow<'_, str> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(self.as_bytes())
+    }
+}
+
+/// Packs the UTF-8 bytes only, without their length or a terminator. Readers
+/// need the length from elsewhere, such as [`unpack_str`](crate::unpack_str).
+impl PackTo for &str {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(self.as_bytes())
+    }
+}
+
+/// Packs the bytes followed by the NUL.
+impl PackTo for &CStr {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(self.to_bytes_with_nul())
+    }
+}
+
+/// Packs the UTF-8 bytes only, without their length or a terminator. Readers
+/// need the length from elsewhere, such as [`unpack_string`](crate::unpack_string).
+#[cfg(feature = "alloc")]
+impl PackTo for String {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(self.as_bytes())
+    }
+}
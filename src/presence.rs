@@ -0,0 +1,343 @@
+use core::marker::PhantomData;
+use core::mem::size_of;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::{PackTo, UnpackFrom};
+
+/// A group of optional fields whose presence is recorded in a bitmap, as used
+/// by [`Presence`].
+///
+/// Implemented for tuples of up to 12 `Option`s, where field `i` corresponds
+/// to bit `i` (the least significant bit is the first field).
+pub trait OptionalFields: Sized {
+    /// Number of optional fields, and so of bits used in the bitmap.
+    const COUNT: u32;
+
+    /// Bitmap of the fields that are present.
+    fn presence(&self) -> u64;
+
+    /// Pack the fields that are present, in order.
+    fn pack_present<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()>;
+
+    /// Unpack the fields whose bits are set in `presence`, in order.
+    fn unpack_present<E: ByteOrder, R: Read + ?Sized>(presence: u64, src: &mut R)
+        -> IoResult<Self>;
+}
+
+macro_rules! impl_tuple {
+    ($count:expr; $($idx:tt => $t:ident),+) => {
+        impl<$($t: PackTo + UnpackFrom),+> OptionalFields for ($(Option<$t>,)+) {
+            const COUNT: u32 = $count;
+
+            fn presence(&self) -> u64 {
+                0 $(| (self.$idx.is_some() as u64) << $idx)+
+            }
+
+            fn pack_present<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                $(
+                    if let Some(field) = &self.$idx {
+                        field.pack_to::<E, _>(dst)?;
+                    }
+                )+
+                Ok(())
+            }
+
+            fn unpack_present<E: ByteOrder, R: Read + ?Sized>(
+                presence: u64,
+                src: &mut R,
+            ) -> IoResult<Self> {
+                Ok(($(
+                    if presence & (1 << $idx) != 0 {
+                        Some($t::unpack_from::<E, _>(src)?)
+                    } else {
+                        None
+                    },
+                )+))
+            }
+        }
+    };
+}
+
+impl_tuple!(1; 0 => T1);
+impl_tuple!(2; 0 => T1, 1 => T2);
+impl_tuple!(3; 0 => T1, 1 => T2, 2 => T3);
+impl_tuple!(4; 0 => T1, 1 => T2, 2 => T3, 3 => T4);
+impl_tuple!(5; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5);
+impl_tuple!(6; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6);
+impl_tuple!(7; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7);
+impl_tuple!(8; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8);
+impl_tuple!(
+    9; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8, 8 => T9
+);
+impl_tuple!(
+    10; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8, 8 => T9,
+    9 => T10
+);
+impl_tuple!(
+    11; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8, 8 => T9,
+    9 => T10, 10 => T11
+);
+impl_tuple!(
+    12; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8, 8 => T9,
+    9 => T10, 10 => T11, 11 => T12
+);
+
+/// Optional fields packed as a bitmap `B` of the fields that are present,
+/// followed by just those fields.
+///
+/// Packing fails with [`ErrorKind::InvalidInput`] if `B` has too few bits for
+/// the fields, and unpacking rejects bitmaps with bits set beyond the last
+/// field with [`ErrorKind::InvalidData`]. For named fields in a derived
+/// struct, `#[pack(presence_of = "...")]` on the bitmap field does the same.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{PackTo, Presence, UnpackFrom};
+///
+/// let fields: Presence<_> = Presence::new((Some(1u8), None::<u16>, Some(3u32)));
+/// let mut buf = vec![];
+/// fields.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0b101, 0x01, 0x00, 0x00, 0x00, 0x03]);
+///
+/// let unpacked = Presence::<(Option<u8>, Option<u16>, Option<u32>)>::unpack_from_be(&mut &buf[..]);
+/// assert_eq!(unpacked.unwrap(), fields);
+///
+/// assert!(Presence::<(Option<u8>,)>::unpack_from_be(&mut &[0b10][..]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct Presence<T, B = u8>(pub T, PhantomData<B>);
+
+impl<T, B> Presence<T, B> {
+    /// Wrap a tuple of optional fields.
+    pub fn new(fields: T) -> Self {
+        Self(fields, PhantomData)
+    }
+
+    /// Unwrap the fields.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, B> PackTo for Presence<T, B>
+where
+    T: OptionalFields,
+    B: PackTo + TryFrom<u64>,
+{
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        let too_narrow = || {
+            IoError::new(
+                ErrorKind::InvalidInput,
+                "presence bitmap too narrow for fields",
+            )
+        };
+        if T::COUNT as usize > size_of::<B>() * 8 {
+            return Err(too_narrow());
+        }
+        let bits = B::try_from(self.0.presence()).map_err(|_| too_narrow())?;
+        bits.pack_to::<E, _>(dst)?;
+        self.0.pack_present::<E, _>(dst)
+    }
+}
+
+impl<T, B> UnpackFrom for Presence<T, B>
+where
+    T: OptionalFields,
+    B: UnpackFrom + Into<u64>,
+{
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let bits: u64 = B::unpack_from::<E, _>(src)?.into();
+        if bits >> T::COUNT != 0 {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "unknown bits set in presence bitmap",
+            ));
+        }
+        Ok(Self::new(T::unpack_present::<E, _>(bits, src)?))
+    }
+}
+
+/// An optional value packed as a presence byte, 1 if it is present and 0 if
+/// not, followed by the value if it is present.
+///
+/// Unpacking rejects presence bytes other than 0 and 1 with
+/// [`ErrorKind::InvalidData`].
+/// # Example
+/// ```rust
+/// use byteorder_pack::{Maybe, PackTo, UnpackFrom};
+///
+/// let mut buf = vec![];
+/// Maybe(Some(0x0102u16)).pack_to_be(&mut buf).unwrap();
+/// Maybe(None::<u16>).pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [1, 0x01, 0x02, 0]);
+///
+/// let mut src = &buf[..];
+/// assert_eq!(Maybe::<u16>::unpack_from_be(&mut src).unwrap(), Maybe(Some(0x0102)));
+/// assert_eq!(Maybe::<u16>::unpack_from_be(&mut src).unwrap(), Maybe(None));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct Maybe<T>(pub Option<T>);
+
+impl<T> Maybe<T> {
+    /// Unwrap the value.
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T> From<Option<T>> for Maybe<T> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: PackTo> PackTo for Maybe<T> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        self.0.is_some().pack_to::<E, _>(dst)?;
+        match &self.0 {
+            Some(value) => value.pack_to::<E, _>(dst),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: UnpackFrom> UnpackFrom for Maybe<T> {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        match bool::unpack_from::<E, _>(src)? {
+            true => T::unpack_from::<E, _>(src).map(|value| Self(Some(value))),
+            false => Ok(Self(None)),
+        }
+    }
+}
+
+/// An integer type that [`OptionSentinel`] can hold, with the sentinel given
+/// as its bit pattern.
+pub trait SentinelInt: Copy + PartialEq {
+    /// Width of the type in bits.
+    const BITS: u32;
+
+    /// The value whose bit pattern is the low [`BITS`](Self::BITS) bits of
+    /// `bits`, zero-extended for types wider than 64 bits.
+    fn from_bits(bits: u64) -> Self;
+}
+
+macro_rules! impl_sentinel_int {
+    ($($ty:ty),+) => {
+        $(
+            impl SentinelInt for $ty {
+                const BITS: u32 = <$ty>::BITS;
+
+                fn from_bits(bits: u64) -> Self {
+                    bits as $ty
+                }
+            }
+        )+
+    };
+}
+
+impl_sentinel_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// An optional value packed as just the value, with `None` packed as the
+/// value whose bit pattern is `SENTINEL`, such as `0xFFFF_FFFF`, or `0xFFFF`
+/// for `-1` as an `i16`.
+///
+/// Packing fails with [`ErrorKind::InvalidInput`] if a present value equals
+/// the sentinel. A sentinel wider than `T` fails to compile.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{OptionSentinel, PackTo, UnpackFrom};
+///
+/// type Offset = OptionSentinel<u32, 0xFFFF_FFFF>;
+///
+/// let mut buf = vec![];
+/// Offset::new(None).pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0xff; 4]);
+/// assert_eq!(Offset::unpack_from_be(&mut &buf[..]).unwrap().get(), None);
+///
+/// assert!(Offset::new(Some(0xFFFF_FFFF)).pack_to_be(&mut buf).is_err());
+///
+/// type Index = OptionSentinel<i16, 0xFFFF>;
+/// assert!(Index::new(Some(-1)).pack_to_be(&mut vec![]).is_err());
+/// assert_eq!(Index::unpack_from_be(&mut &[0xff, 0xff][..]).unwrap().get(), None);
+/// ```
+///
+/// ```compile_fail
+/// use byteorder_pack::{OptionSentinel, PackTo};
+///
+/// OptionSentinel::<u8, 0x100>::new(None).pack_to_be(&mut vec![]).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct OptionSentinel<T, const SENTINEL: u64>(pub Option<T>);
+
+impl<T, const SENTINEL: u64> OptionSentinel<T, SENTINEL> {
+    /// Wrap a value.
+    pub fn new(value: Option<T>) -> Self {
+        Self(value)
+    }
+
+    /// Unwrap the value.
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T: Copy, const SENTINEL: u64> OptionSentinel<T, SENTINEL> {
+    /// The value, if present.
+    pub fn get(&self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T: SentinelInt, const SENTINEL: u64> OptionSentinel<T, SENTINEL> {
+    const FITS: () = assert!(
+        T::BITS >= u64::BITS || SENTINEL >> T::BITS == 0,
+        "sentinel does not fit in the value type"
+    );
+
+    /// The sentinel as a `T`.
+    pub(crate) fn sentinel() -> T {
+        let () = Self::FITS;
+        T::from_bits(SENTINEL)
+    }
+}
+
+impl<T, const SENTINEL: u64> PackTo for OptionSentinel<T, SENTINEL>
+where
+    T: PackTo + SentinelInt,
+{
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        let sentinel = Self::sentinel();
+        match &self.0 {
+            Some(value) if *value == sentinel => Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "value collides with the sentinel",
+            )),
+            Some(value) => value.pack_to::<E, _>(dst),
+            None => sentinel.pack_to::<E, _>(dst),
+        }
+    }
+}
+
+impl<T, const SENTINEL: u64> UnpackFrom for OptionSentinel<T, SENTINEL>
+where
+    T: UnpackFrom + SentinelInt,
+{
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let sentinel = Self::sentinel();
+        let value = T::unpack_from::<E, _>(src)?;
+        Ok(Self((value != sentinel).then_some(value)))
+    }
+}
@@ -0,0 +1,105 @@
+use byteorder::ByteOrder;
+
+use crate::io::{Read, Result as IoResult};
+use crate::UnpackFrom;
+
+/// A snapshot of how far a [`ProgressReader`] has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Bytes read so far.
+    pub bytes: u64,
+    /// Total number of bytes expected.
+    pub total: u64,
+    /// Records unpacked so far with [`ProgressReader::unpack`].
+    pub records: u64,
+}
+
+impl Progress {
+    /// Fraction of the expected bytes read so far, between 0 and 1.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.bytes as f64 / self.total as f64).min(1.0)
+        }
+    }
+}
+
+/// A reader that knows how many bytes to expect, counts what has been read and
+/// reports [`Progress`] to a callback after each record is unpacked.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::ProgressReader;
+///
+/// let data = [0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04];
+/// let mut seen = vec![];
+/// let mut reader = ProgressReader::new(&data[..], data.len() as u64)
+///     .with_callback(|p| seen.push((p.records, p.fraction())));
+///
+/// while reader.progress().bytes < 8 {
+///     reader.unpack::<BigEndian, u16>().unwrap();
+/// }
+///
+/// assert_eq!(reader.progress().records, 4);
+/// drop(reader);
+/// assert_eq!(seen, [(1, 0.25), (2, 0.5), (3, 0.75), (4, 1.0)]);
+/// ```
+#[derive(Debug)]
+pub struct ProgressReader<R, F = fn(Progress)> {
+    inner: R,
+    progress: Progress,
+    callback: F,
+}
+
+impl<R: Read> ProgressReader<R> {
+    /// Wrap `inner`, which is expected to yield `total` bytes.
+    pub fn new(inner: R, total: u64) -> Self {
+        Self {
+            inner,
+            progress: Progress {
+                bytes: 0,
+                total,
+                records: 0,
+            },
+            callback: |_| {},
+        }
+    }
+}
+
+impl<R: Read, F: FnMut(Progress)> ProgressReader<R, F> {
+    /// Call `callback` with the current progress after each record.
+    pub fn with_callback<G: FnMut(Progress)>(self, callback: G) -> ProgressReader<R, G> {
+        ProgressReader {
+            inner: self.inner,
+            progress: self.progress,
+            callback,
+        }
+    }
+
+    /// Unpack one record and report progress.
+    pub fn unpack<E: ByteOrder, T: UnpackFrom>(&mut self) -> IoResult<T> {
+        let value = T::unpack_from::<E, _>(self)?;
+        self.progress.records += 1;
+        (self.callback)(self.progress);
+        Ok(value)
+    }
+
+    /// The progress so far.
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, F> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let n = self.inner.read(buf)?;
+        self.progress.bytes += n as u64;
+        Ok(n)
+    }
+}
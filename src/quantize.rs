@@ -0,0 +1,178 @@
+use core::marker::PhantomData;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// The range of floats represented by a normalized integer wrapper such as
+/// [`UNorm8`] or [`SNorm16`].
+/// # Example
+/// ```rust
+/// use byteorder_pack::{NormRange, PackTo, UNorm16};
+///
+/// /// Headings in degrees.
+/// #[derive(Debug)]
+/// struct Degrees;
+///
+/// impl NormRange for Degrees {
+///     const MIN: f32 = 0.0;
+///     const MAX: f32 = 360.0;
+/// }
+///
+/// let mut buf = vec![];
+/// UNorm16::<Degrees>::new(180.0).pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0x80, 0x00]);
+/// ```
+pub trait NormRange {
+    /// The float packed as the smallest integer.
+    const MIN: f32;
+    /// The float packed as the largest integer.
+    const MAX: f32;
+}
+
+/// The range `0.0..=1.0`, the default for unsigned wrappers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct Unit;
+
+impl NormRange for Unit {
+    const MIN: f32 = 0.0;
+    const MAX: f32 = 1.0;
+}
+
+/// The range `-1.0..=1.0`, the default for signed wrappers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct SignedUnit;
+
+impl NormRange for SignedUnit {
+    const MIN: f32 = -1.0;
+    const MAX: f32 = 1.0;
+}
+
+/// Round to the nearest integer, away from zero on ties, without `std`.
+fn round(v: f32) -> f32 {
+    if v >= 0.0 {
+        (v + 0.5) as i64 as f32
+    } else {
+        (v - 0.5) as i64 as f32
+    }
+}
+
+macro_rules! impl_norm {
+    ($($(#[$doc:meta])* $name:ident($int:ty, $lo:expr, $range:ident);)+) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+            #[cfg_attr(
+                all(feature = "arbitrary", feature = "std"),
+                derive(arbitrary::Arbitrary)
+            )]
+            pub struct $name<R = $range>(pub f32, PhantomData<R>);
+
+            impl<R: NormRange> $name<R> {
+                /// Wrap a float.
+                pub fn new(value: f32) -> Self {
+                    Self(value, PhantomData)
+                }
+
+                /// The wrapped float.
+                pub fn get(&self) -> f32 {
+                    self.0
+                }
+
+                fn to_int(&self) -> $int {
+                    let lo = $lo as f32;
+                    let hi = <$int>::MAX as f32;
+                    let t = (self.0 - R::MIN) / (R::MAX - R::MIN);
+                    round(lo + t * (hi - lo)) as $int
+                }
+
+                pub(crate) fn from_int(int: $int) -> Self {
+                    let lo = $lo as f32;
+                    let hi = <$int>::MAX as f32;
+                    let t = ((int as f32).max(lo) - lo) / (hi - lo);
+                    Self::new(R::MIN + t * (R::MAX - R::MIN))
+                }
+            }
+
+            impl<R: NormRange> PackTo for $name<R> {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    self.to_int().pack_to::<E, _>(dst)
+                }
+            }
+
+            impl<R: NormRange> UnpackFrom for $name<R> {
+                fn unpack_from<E: ByteOrder, R2: Read + ?Sized>(src: &mut R2) -> IoResult<Self> {
+                    Ok(Self::from_int(<$int>::unpack_from::<E, _>(src)?))
+                }
+            }
+
+            impl<R> FixedPackSize for $name<R> {
+                const PACKED_SIZE: usize = <$int>::PACKED_SIZE;
+            }
+
+            #[cfg(feature = "alloc")]
+            impl<R> crate::Describe for $name<R> {
+                fn schema() -> crate::Schema {
+                    <$int as crate::Describe>::schema()
+                }
+            }
+        )+
+    };
+}
+
+impl_norm! {
+    /// An `f32` in the range `R` packed as a `u8`, with `R::MIN` as 0 and
+    /// `R::MAX` as 255.
+    ///
+    /// Values outside the range are clamped and NaN packs as 0, so only about
+    /// 1/255 of the range's precision survives a round trip.
+    /// # Example
+    /// ```rust
+    /// use byteorder_pack::{PackTo, UNorm8, UnpackFrom};
+    ///
+    /// let mut buf = vec![];
+    /// <UNorm8>::new(0.5).pack_to_be(&mut buf).unwrap();
+    /// <UNorm8>::new(2.0).pack_to_be(&mut buf).unwrap();
+    /// assert_eq!(buf, [128, 255]);
+    ///
+    /// let half = <UNorm8>::unpack_from_be(&mut &buf[..]).unwrap().get();
+    /// assert!((half - 0.5).abs() < 1.0 / 255.0);
+    /// ```
+    UNorm8(u8, 0, Unit);
+    /// An `f32` in the range `R` packed as a `u16`, with `R::MIN` as 0 and
+    /// `R::MAX` as 65535.
+    ///
+    /// Values outside the range are clamped and NaN packs as 0.
+    UNorm16(u16, 0, Unit);
+    /// An `f32` in the range `R` packed as an `i8`, with `R::MIN` as -127 and
+    /// `R::MAX` as 127, so the midpoint of the range is exactly 0.
+    ///
+    /// Values outside the range are clamped, NaN packs as 0 and -128 unpacks
+    /// as `R::MIN`.
+    /// # Example
+    /// ```rust
+    /// use byteorder_pack::{PackTo, SNorm8};
+    ///
+    /// let mut buf = vec![];
+    /// for v in [-1.0, 0.0, 1.0] {
+    ///     <SNorm8>::new(v).pack_to_be(&mut buf).unwrap();
+    /// }
+    /// assert_eq!(buf, [0x81, 0x00, 0x7f]);
+    /// ```
+    SNorm8(i8, -(i8::MAX), SignedUnit);
+    /// An `f32` in the range `R` packed as an `i16`, with `R::MIN` as -32767
+    /// and `R::MAX` as 32767, so the midpoint of the range is exactly 0.
+    ///
+    /// Values outside the range are clamped, NaN packs as 0 and -32768
+    /// unpacks as `R::MIN`.
+    SNorm16(i16, -(i16::MAX), SignedUnit);
+}
@@ -0,0 +1,221 @@
+use std::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Seek, SeekFrom};
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{
+    crc32, FixedPackSize, LengthPrefix, Recovered, SliceReader, UnpackFrom, DEFAULT_MAX_FRAME_LEN,
+};
+
+#[derive(Debug, Clone, Copy)]
+enum Layout {
+    Fixed(usize),
+    LengthPrefixed(usize),
+}
+
+/// Reads a file of records, skipping damaged regions and keeping a summary of
+/// what was skipped.
+///
+/// Records are either of a fixed size, or preceded by their length as an `L`
+/// in byte order `E`. With [`with_crc`](Self::with_crc), each record payload
+/// is followed by its [`crc32`] as a `u32`.
+///
+/// A record is considered damaged if its length is impossible, its checksum
+/// does not match, or it does not unpack to exactly one `T`. Damaged fixed-size
+/// records are skipped one record at a time; for length-prefixed records the
+/// reader scans forward byte by byte until a valid record is found. Only
+/// errors from the underlying reader are returned as errors.
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder_pack::{Recovered, RecordFile};
+///
+/// let data = vec![0x01, 0xAA, 0xFF, 0x01, 0xBB];
+/// let mut file = RecordFile::<_, u8, u8>::length_prefixed(Cursor::new(data))
+///     .unwrap()
+///     .with_max_len(4);
+///
+/// assert_eq!(file.next().unwrap().unwrap(), Recovered::Record { offset: 0, value: 0xAA });
+/// assert_eq!(file.next().unwrap().unwrap(), Recovered::Skipped(2..3));
+/// assert_eq!(file.next().unwrap().unwrap(), Recovered::Record { offset: 3, value: 0xBB });
+/// assert!(file.next().is_none());
+/// assert_eq!(file.skipped(), &[2..3]);
+///
+/// assert!(RecordFile::<_, (), u8>::fixed(Cursor::new(vec![]), 0).is_err());
+/// ```
+#[derive(Debug)]
+pub struct RecordFile<R, T, L = u32, E = BigEndian> {
+    src: R,
+    layout: Layout,
+    crc: bool,
+    pos: u64,
+    end: u64,
+    skipped: Vec<Range<u64>>,
+    buf: Vec<u8>,
+    _marker: PhantomData<(T, L, E)>,
+}
+
+impl<R, T, L, E> RecordFile<R, T, L, E>
+where
+    R: Read + Seek,
+    T: UnpackFrom,
+    L: LengthPrefix + UnpackFrom + FixedPackSize,
+    E: ByteOrder,
+{
+    /// Read records of exactly `len` payload bytes, starting at the current
+    /// position of `src`.
+    ///
+    /// Fails with [`ErrorKind::InvalidInput`] if `len` is 0, since the reader
+    /// would never move past such records.
+    pub fn fixed(src: R, len: usize) -> IoResult<Self> {
+        if len == 0 {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "fixed-size records must not be empty",
+            ));
+        }
+        Self::new(src, Layout::Fixed(len))
+    }
+
+    /// Read length-prefixed records with payloads of at most
+    /// [`DEFAULT_MAX_FRAME_LEN`] bytes, starting at the current position of
+    /// `src`.
+    pub fn length_prefixed(src: R) -> IoResult<Self> {
+        Self::new(src, Layout::LengthPrefixed(DEFAULT_MAX_FRAME_LEN))
+    }
+
+    fn new(mut src: R, layout: Layout) -> IoResult<Self> {
+        let pos = src.stream_position()?;
+        let end = src.seek(SeekFrom::End(0))?;
+        src.seek(SeekFrom::Start(pos))?;
+        Ok(Self {
+            src,
+            layout,
+            crc: false,
+            pos,
+            end,
+            skipped: Vec::new(),
+            buf: Vec::new(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Expect a CRC-32 of the payload after each record.
+    pub fn with_crc(mut self) -> Self {
+        self.crc = true;
+        self
+    }
+
+    /// Set the maximum payload size of a length-prefixed record; longer
+    /// lengths are treated as corruption.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        if let Layout::LengthPrefixed(max) = &mut self.layout {
+            *max = max_len;
+        }
+        self
+    }
+
+    /// All byte ranges skipped so far, with adjacent ranges merged.
+    pub fn skipped(&self) -> &[Range<u64>] {
+        &self.skipped
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.src
+    }
+
+    /// Try to read a record at `pos`, returning it and the offset just past it,
+    /// or `None` if the record is damaged.
+    fn try_record(&mut self, pos: u64) -> IoResult<Option<(T, u64)>> {
+        self.src.seek(SeekFrom::Start(pos))?;
+        match self.read_record() {
+            Ok(Some(value)) => Ok(Some((value, self.src.stream_position()?))),
+            Ok(None) => Ok(None),
+            Err(e) if matches!(e.kind(), ErrorKind::InvalidData | ErrorKind::UnexpectedEof) => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn read_record(&mut self) -> IoResult<Option<T>> {
+        let len = match self.layout {
+            Layout::Fixed(len) => len,
+            Layout::LengthPrefixed(max_len) => {
+                let len = L::unpack_from::<E, _>(&mut self.src)?.to_usize()?;
+                if len > max_len {
+                    return Ok(None);
+                }
+                len
+            }
+        };
+        // Check the length against the bytes left before reading anything, so
+        // that scanning damaged data does not read a payload at every offset.
+        let remaining = self.end - self.src.stream_position()?;
+        if len as u64 + if self.crc { 4 } else { 0 } > remaining {
+            return Ok(None);
+        }
+        self.buf.clear();
+        self.buf.resize(len, 0);
+        self.src.read_exact(&mut self.buf)?;
+        if self.crc && u32::unpack_from::<E, _>(&mut self.src)? != crc32(&self.buf) {
+            return Ok(None);
+        }
+        let mut reader = SliceReader::new(&self.buf);
+        let value = T::unpack_from::<E, _>(&mut reader)?;
+        Ok(reader.is_empty().then_some(value))
+    }
+
+    fn skip(&mut self, range: Range<u64>) -> Recovered<T> {
+        match self.skipped.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => self.skipped.push(range.clone()),
+        }
+        self.pos = range.end;
+        Recovered::Skipped(range)
+    }
+
+    fn next_item(&mut self) -> IoResult<Option<Recovered<T>>> {
+        let start = self.pos;
+        if start >= self.end {
+            return Ok(None);
+        }
+        if let Some((value, next)) = self.try_record(start)? {
+            self.pos = next;
+            return Ok(Some(Recovered::Record {
+                offset: start,
+                value,
+            }));
+        }
+        let next = match self.layout {
+            Layout::Fixed(len) => {
+                let stride = len as u64 + if self.crc { 4 } else { 0 };
+                (start + stride).min(self.end)
+            }
+            Layout::LengthPrefixed(_) => {
+                let mut next = start + 1;
+                while next < self.end && self.try_record(next)?.is_none() {
+                    next += 1;
+                }
+                next
+            }
+        };
+        Ok(Some(self.skip(start..next)))
+    }
+}
+
+impl<R, T, L, E> Iterator for RecordFile<R, T, L, E>
+where
+    R: Read + Seek,
+    T: UnpackFrom,
+    L: LengthPrefix + UnpackFrom + FixedPackSize,
+    E: ByteOrder,
+{
+    type Item = IoResult<Recovered<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_item().transpose()
+    }
+}
@@ -0,0 +1,182 @@
+use std::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Seek, SeekFrom};
+use std::ops::Range;
+
+use byteorder::ByteOrder;
+
+use crate::UnpackFrom;
+
+/// How [`Resync`] finds the start of the next record after a decode error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncPattern {
+    /// Every record starts with this byte sequence.
+    Magic(Vec<u8>),
+    /// Records start on multiples of this many bytes, counted from where the
+    /// stream was positioned when the [`Resync`] was created.
+    Stride(u64),
+}
+
+/// An item produced by [`Resync::next_record`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recovered<T> {
+    /// A record that decoded successfully, and the offset it started at.
+    Record { offset: u64, value: T },
+    /// A range of bytes that could not be decoded and was skipped.
+    Skipped(Range<u64>),
+}
+
+/// Decode a stream of records, skipping over corrupt regions instead of
+/// aborting on the first error.
+///
+/// When a record fails to decode with [`ErrorKind::InvalidData`] or
+/// [`ErrorKind::UnexpectedEof`], the reader scans forward to the next
+/// [`SyncPattern`] match, reports the bytes in between as
+/// [`Recovered::Skipped`] and resumes decoding from there. Any other error is
+/// returned as is, and a record that decodes from zero bytes fails with
+/// [`ErrorKind::InvalidInput`], since decoding would never move past it.
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder::BigEndian;
+/// use byteorder_pack::{Recovered, Resync, SyncPattern};
+///
+/// let data = vec![0xAA, 0x55, 0x00, 0x01, 0xFF, 0xFF, 0xAA, 0x55, 0x00, 0x02];
+/// let mut records = Resync::new(Cursor::new(data), SyncPattern::Magic(vec![0xAA, 0x55])).unwrap();
+///
+/// let mut next = || records.next_record::<BigEndian, ([u8; 2], u16)>().unwrap();
+///
+/// assert_eq!(next(), Some(Recovered::Record { offset: 0, value: ([0xAA, 0x55], 1) }));
+/// assert_eq!(next(), Some(Recovered::Skipped(4..6)));
+/// assert_eq!(next(), Some(Recovered::Record { offset: 6, value: ([0xAA, 0x55], 2) }));
+/// assert_eq!(next(), None);
+///
+/// let mut records = Resync::new(Cursor::new([0xAA, 0x55]), SyncPattern::Stride(2)).unwrap();
+/// assert!(records.next_record::<BigEndian, ()>().is_err());
+/// ```
+#[derive(Debug)]
+pub struct Resync<R> {
+    src: R,
+    sync: SyncPattern,
+    base: u64,
+    pos: u64,
+    end: u64,
+}
+
+impl<R: Read + Seek> Resync<R> {
+    /// Start decoding records at the current position of `src`.
+    pub fn new(mut src: R, sync: SyncPattern) -> IoResult<Self> {
+        match &sync {
+            SyncPattern::Magic(magic) if magic.is_empty() => {
+                return Err(IoError::new(ErrorKind::InvalidInput, "empty sync magic"));
+            }
+            SyncPattern::Stride(0) => {
+                return Err(IoError::new(ErrorKind::InvalidInput, "zero sync stride"));
+            }
+            _ => {}
+        }
+        let pos = src.stream_position()?;
+        let end = src.seek(SeekFrom::End(0))?;
+        src.seek(SeekFrom::Start(pos))?;
+        Ok(Self {
+            src,
+            sync,
+            base: pos,
+            pos,
+            end,
+        })
+    }
+
+    /// Decode the next record, or report the next skipped range.
+    ///
+    /// Returns `None` once the end of the stream is reached.
+    pub fn next_record<E: ByteOrder, T: UnpackFrom>(
+        &mut self,
+    ) -> IoResult<Option<Recovered<T>>> {
+        let start = self.pos;
+        if start >= self.end {
+            return Ok(None);
+        }
+        if !self.magic_at(start)? {
+            return self.skip(start, start).map(Some);
+        }
+        self.src.seek(SeekFrom::Start(start))?;
+        match T::unpack_from::<E, _>(&mut self.src) {
+            Ok(value) => {
+                self.pos = self.src.stream_position()?;
+                if self.pos == start {
+                    return Err(IoError::new(
+                        ErrorKind::InvalidInput,
+                        "record decoded from zero bytes",
+                    ));
+                }
+                Ok(Some(Recovered::Record {
+                    offset: start,
+                    value,
+                }))
+            }
+            Err(e) if matches!(e.kind(), ErrorKind::InvalidData | ErrorKind::UnexpectedEof) => {
+                self.skip(start, start + 1).map(Some)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.src
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.src
+    }
+
+    fn magic_at(&mut self, pos: u64) -> IoResult<bool> {
+        let SyncPattern::Magic(magic) = &self.sync else {
+            return Ok(true);
+        };
+        let mut buf = vec![0; magic.len()];
+        self.src.seek(SeekFrom::Start(pos))?;
+        match self.src.read_exact(&mut buf) {
+            Ok(()) => Ok(buf == *magic),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn skip<T>(&mut self, start: u64, search_from: u64) -> IoResult<Recovered<T>> {
+        let next = match &self.sync {
+            SyncPattern::Magic(_) => self.find_magic(search_from)?.unwrap_or(self.end),
+            SyncPattern::Stride(stride) => {
+                let boundary = self.base + ((start - self.base) / stride + 1) * stride;
+                boundary.min(self.end)
+            }
+        };
+        self.pos = next;
+        Ok(Recovered::Skipped(start..next))
+    }
+
+    fn find_magic(&mut self, from: u64) -> IoResult<Option<u64>> {
+        let SyncPattern::Magic(magic) = &self.sync else {
+            return Ok(None);
+        };
+        self.src.seek(SeekFrom::Start(from))?;
+        let mut window = Vec::new();
+        let mut window_start = from;
+        let mut buf = [0; 4096];
+        loop {
+            let n = match self.src.read(&mut buf) {
+                Ok(0) => return Ok(None),
+                Ok(n) => n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            window.extend_from_slice(&buf[..n]);
+            if let Some(i) = window.windows(magic.len()).position(|w| w == &magic[..]) {
+                return Ok(Some(window_start + i as u64));
+            }
+            let drop = window.len() - (magic.len() - 1).min(window.len());
+            window.drain(..drop);
+            window_start += drop as u64;
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::fmt;
+use std::io::{self, Error as IoError, ErrorKind, Read, Result as IoResult, Seek, Write};
+use std::marker::PhantomData;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::frame::read_header;
+use crate::{FixedPackSize, PackTo, Placeholder, UnpackFrom};
+
+/// A four-character code identifying a RIFF/IFF chunk.
+///
+/// Packed as four raw bytes regardless of byte order.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct FourCc(pub [u8; 4]);
+
+impl fmt::Debug for FourCc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FourCc({:?})", self.0.escape_ascii().to_string())
+    }
+}
+
+impl fmt::Display for FourCc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.escape_ascii())
+    }
+}
+
+impl From<&[u8; 4]> for FourCc {
+    fn from(id: &[u8; 4]) -> Self {
+        Self(*id)
+    }
+}
+
+impl PackTo for FourCc {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        dst.write_all(&self.0)
+    }
+}
+
+impl UnpackFrom for FourCc {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        <[u8; 4]>::unpack_from::<E, _>(src).map(Self)
+    }
+}
+
+impl FixedPackSize for FourCc {
+    const PACKED_SIZE: usize = 4;
+}
+
+/// Iterates over the chunks of a RIFF (little-endian sizes, the default) or
+/// IFF (`E = BigEndian`) stream.
+///
+/// Each chunk is a [`FourCc`] id, a `u32` payload size and the payload,
+/// followed by a padding byte if the size is odd. Any part of a chunk's payload
+/// left unread is skipped when the next chunk is requested.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{ChunkReader, FourCc, UnpackFrom};
+///
+/// let data = b"RIFF\x10\x00\x00\x00WAVEdata\x03\x00\x00\x00abc\x00";
+/// let mut riff = ChunkReader::<_>::new(&data[..]);
+///
+/// let chunk = riff.next_chunk().unwrap().unwrap();
+/// assert_eq!(chunk.id(), FourCc(*b"RIFF"));
+///
+/// let (form, mut chunks) = chunk.into_list().unwrap();
+/// assert_eq!(form, FourCc(*b"WAVE"));
+///
+/// let mut data = chunks.next_chunk().unwrap().unwrap();
+/// assert_eq!((data.id(), data.size()), (FourCc(*b"data"), 3));
+/// assert_eq!(<[u8; 3]>::unpack_from_le(&mut data).unwrap(), *b"abc");
+///
+/// assert!(chunks.next_chunk().unwrap().is_none());
+/// assert!(riff.next_chunk().unwrap().is_none());
+/// ```
+#[derive(Debug)]
+pub struct ChunkReader<R, E = LittleEndian> {
+    inner: R,
+    remaining: u64,
+    pad: bool,
+    _marker: PhantomData<E>,
+}
+
+impl<R: Read, E: ByteOrder> ChunkReader<R, E> {
+    /// Read chunks from the current position of `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            remaining: 0,
+            pad: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Advance to the next chunk, skipping whatever is left of the current one.
+    ///
+    /// Returns `None` if the source ends cleanly between chunks.
+    pub fn next_chunk(&mut self) -> IoResult<Option<Chunk<'_, R, E>>> {
+        let skip = self.remaining + u64::from(self.pad);
+        if io::copy(&mut (&mut self.inner).take(skip), &mut io::sink())? != skip {
+            return Err(IoError::new(
+                ErrorKind::UnexpectedEof,
+                "source ended inside a chunk",
+            ));
+        }
+        let Some((id, size)) = read_header::<E, (FourCc, u32), _>(&mut self.inner)? else {
+            self.remaining = 0;
+            self.pad = false;
+            return Ok(None);
+        };
+        self.remaining = size.into();
+        self.pad = size % 2 == 1;
+        Ok(Some(Chunk {
+            id,
+            size,
+            reader: self,
+        }))
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// A chunk produced by [`ChunkReader::next_chunk`], readable as its payload.
+#[derive(Debug)]
+pub struct Chunk<'a, R, E = LittleEndian> {
+    id: FourCc,
+    size: u32,
+    reader: &'a mut ChunkReader<R, E>,
+}
+
+impl<'a, R: Read, E: ByteOrder> Chunk<'a, R, E> {
+    /// The chunk id.
+    pub fn id(&self) -> FourCc {
+        self.id
+    }
+
+    /// The payload size in bytes, excluding padding.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Number of payload bytes not read yet.
+    pub fn remaining(&self) -> u64 {
+        self.reader.remaining
+    }
+
+    /// Treat the payload as a list (`RIFF`, `LIST`, `FORM`...): read its form
+    /// type and return a reader over the chunks nested in it.
+    pub fn into_list(mut self) -> IoResult<(FourCc, ChunkReader<Self, E>)> {
+        let form = FourCc::unpack_from::<E, _>(&mut self)?;
+        Ok((form, ChunkReader::new(self)))
+    }
+}
+
+impl<R: Read, E> Read for Chunk<'_, R, E> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let max = buf
+            .len()
+            .min(usize::try_from(self.reader.remaining).unwrap_or(usize::MAX));
+        let n = self.reader.inner.read(&mut buf[..max])?;
+        self.reader.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+/// Writes RIFF (or, with `E = BigEndian`, IFF) chunks, fixing up their sizes
+/// and padding once each chunk is complete.
+///
+/// The writer implements [`Write`], so payloads can be packed into it directly.
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder_pack::{ChunkWriter, PackTo};
+///
+/// let mut riff = ChunkWriter::<_>::new(Cursor::new(vec![]));
+///
+/// riff.begin_list(b"RIFF", b"WAVE").unwrap();
+/// riff.begin_chunk(b"data").unwrap();
+/// (*b"abc").pack_to_le(&mut riff).unwrap();
+/// riff.end_chunk().unwrap();
+/// riff.end_chunk().unwrap();
+///
+/// let data = riff.finish().unwrap().into_inner();
+/// assert_eq!(data, b"RIFF\x10\x00\x00\x00WAVEdata\x03\x00\x00\x00abc\x00");
+/// ```
+#[derive(Debug)]
+pub struct ChunkWriter<W, E = LittleEndian> {
+    inner: W,
+    open: Vec<Placeholder<u32>>,
+    _marker: PhantomData<E>,
+}
+
+impl<W: Write + Seek, E: ByteOrder> ChunkWriter<W, E> {
+    /// Write chunks starting at the current position of `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            open: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Start a chunk with the given id, nested in the current one.
+    pub fn begin_chunk(&mut self, id: impl Into<FourCc>) -> IoResult<()> {
+        id.into().pack_to::<E, _>(&mut self.inner)?;
+        let size = Placeholder::reserve(&mut self.inner)?;
+        self.open.push(size);
+        Ok(())
+    }
+
+    /// Start a list chunk (`RIFF`, `LIST`, `FORM`...) with the given form type.
+    pub fn begin_list(&mut self, id: impl Into<FourCc>, form: impl Into<FourCc>) -> IoResult<()> {
+        self.begin_chunk(id)?;
+        form.into().pack_to::<E, _>(&mut self.inner)
+    }
+
+    /// Finish the innermost open chunk, writing its size and padding.
+    pub fn end_chunk(&mut self) -> IoResult<u64> {
+        let size = self
+            .open
+            .pop()
+            .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, "no open chunk"))?;
+        let len = size.patch_len::<E, _>(&mut self.inner)?;
+        if len % 2 == 1 {
+            self.inner.write_all(&[0])?;
+        }
+        Ok(len)
+    }
+
+    /// Finish writing, returning the underlying writer.
+    ///
+    /// Fails with [`ErrorKind::InvalidInput`] if any chunk is still open.
+    pub fn finish(self) -> IoResult<W> {
+        if !self.open.is_empty() {
+            return Err(IoError::new(ErrorKind::InvalidInput, "chunk left open"));
+        }
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write, E> Write for ChunkWriter<W, E> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,99 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::mem::size_of;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::{LengthPrefix, PackTo, UnpackFrom, DEFAULT_MAX_FRAME_LEN};
+
+/// A list packed as run-length encoded `(count, value)` pairs, preceded by
+/// the number of runs. Counts are `C`s, and runs too long for a `C` are split.
+///
+/// Unpacking expands the runs into a flat `Vec`, failing with
+/// [`ErrorKind::InvalidData`] rather than allocating if the list would exceed
+/// `MAX_LEN` elements.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{PackTo, Rle, UnpackFrom};
+///
+/// let mask = Rle::<_, u8>::new(vec![0u8, 0, 0, 0, 1, 1, 0]);
+/// let mut buf = vec![];
+/// mask.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [3, 4, 0, 2, 1, 1, 0]);
+///
+/// assert_eq!(Rle::<u8, u8>::unpack_from_be(&mut &buf[..]).unwrap(), mask);
+/// assert!(Rle::<u8, u8, 6>::unpack_from_be(&mut &buf[..]).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct Rle<T, C = u16, const MAX_LEN: usize = DEFAULT_MAX_FRAME_LEN>(
+    pub Vec<T>,
+    PhantomData<C>,
+);
+
+impl<T, C, const MAX_LEN: usize> Rle<T, C, MAX_LEN> {
+    /// Wrap a list.
+    pub fn new(items: Vec<T>) -> Self {
+        Self(items, PhantomData)
+    }
+
+    /// Unwrap the list.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, C, const MAX_LEN: usize> PackTo for Rle<T, C, MAX_LEN>
+where
+    T: PackTo + PartialEq,
+    C: LengthPrefix + PackTo,
+{
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        let bits = size_of::<C>() * 8;
+        let max_run = if bits >= usize::BITS as usize {
+            usize::MAX
+        } else {
+            (1 << bits) - 1
+        };
+        let mut runs = Vec::new();
+        for item in &self.0 {
+            match runs.last_mut() {
+                Some((count, value)) if *value == item && *count < max_run => *count += 1,
+                _ => runs.push((1, item)),
+            }
+        }
+        C::from_usize(runs.len())?.pack_to::<E, _>(dst)?;
+        for (count, value) in runs {
+            C::from_usize(count)?.pack_to::<E, _>(dst)?;
+            value.pack_to::<E, _>(dst)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, C, const MAX_LEN: usize> UnpackFrom for Rle<T, C, MAX_LEN>
+where
+    T: UnpackFrom + Clone,
+    C: LengthPrefix + UnpackFrom,
+{
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let runs = C::unpack_from::<E, _>(src)?.to_usize()?;
+        let mut items = Vec::new();
+        for _ in 0..runs {
+            let count = C::unpack_from::<E, _>(src)?.to_usize()?;
+            let value = T::unpack_from::<E, _>(src)?;
+            if count > MAX_LEN - items.len() {
+                return Err(IoError::new(
+                    ErrorKind::InvalidData,
+                    "run-length encoded list exceeds maximum length",
+                ));
+            }
+            items.resize(items.len() + count, value);
+        }
+        Ok(Self::new(items))
+    }
+}
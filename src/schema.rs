@@ -0,0 +1,260 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::len::read_bytes;
+use crate::{PackTo, UnpackFrom, Value};
+
+/// A layout described in data rather than by a compiled-in type.
+///
+/// [`interpret`](Self::interpret) decodes bytes laid out as described into a
+/// [`Value`] of the matching shape, and [`emit`](Self::emit) encodes such a
+/// value back, so generic inspectors and converters can work on any format
+/// their configuration describes.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::{Schema, Value};
+///
+/// let header = Schema::Struct(vec![
+///     ("magic".into(), Schema::Bytes(2)),
+///     ("counts".into(), Schema::Array(Box::new(Schema::U16), 2)),
+/// ]);
+/// assert_eq!(header.size(), 6);
+///
+/// let data = b"PK\x00\x01\x00\x02";
+/// let value = header.interpret::<BigEndian, _>(&mut &data[..]).unwrap();
+/// assert_eq!(
+///     value.field("counts"),
+///     Some(&Value::Array(vec![Value::U16(1), Value::U16(2)])),
+/// );
+///
+/// let mut buf = vec![];
+/// header.emit::<BigEndian, _>(&value, &mut buf).unwrap();
+/// assert_eq!(buf, data);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schema {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    U128,
+    I128,
+    F32,
+    F64,
+    /// A fixed number of raw bytes.
+    Bytes(usize),
+    /// A fixed number of elements of the same layout.
+    Array(Box<Schema>, usize),
+    /// Named fields laid out in order.
+    Struct(Vec<(String, Schema)>),
+}
+
+impl Schema {
+    /// Packed size in bytes, saturating at `usize::MAX`.
+    pub fn size(&self) -> usize {
+        match self {
+            Schema::U8 | Schema::I8 => 1,
+            Schema::U16 | Schema::I16 => 2,
+            Schema::U32 | Schema::I32 | Schema::F32 => 4,
+            Schema::U64 | Schema::I64 | Schema::F64 => 8,
+            Schema::U128 | Schema::I128 => 16,
+            Schema::Bytes(n) => *n,
+            Schema::Array(elem, n) => elem.size().saturating_mul(*n),
+            Schema::Struct(fields) => fields
+                .iter()
+                .fold(0usize, |size, (_, field)| size.saturating_add(field.size())),
+        }
+    }
+
+    /// Decode a value laid out as described by this schema from `src`.
+    pub fn interpret<E: ByteOrder, R: Read + ?Sized>(&self, src: &mut R) -> IoResult<Value> {
+        Ok(match self {
+            Schema::U8 => Value::U8(u8::unpack_from::<E, _>(src)?),
+            Schema::I8 => Value::I8(i8::unpack_from::<E, _>(src)?),
+            Schema::U16 => Value::U16(u16::unpack_from::<E, _>(src)?),
+            Schema::I16 => Value::I16(i16::unpack_from::<E, _>(src)?),
+            Schema::U32 => Value::U32(u32::unpack_from::<E, _>(src)?),
+            Schema::I32 => Value::I32(i32::unpack_from::<E, _>(src)?),
+            Schema::U64 => Value::U64(u64::unpack_from::<E, _>(src)?),
+            Schema::I64 => Value::I64(i64::unpack_from::<E, _>(src)?),
+            Schema::U128 => Value::U128(u128::unpack_from::<E, _>(src)?),
+            Schema::I128 => Value::I128(i128::unpack_from::<E, _>(src)?),
+            Schema::F32 => Value::F32(f32::unpack_from::<E, _>(src)?),
+            Schema::F64 => Value::F64(f64::unpack_from::<E, _>(src)?),
+            Schema::Bytes(n) => Value::Bytes(read_bytes(src, *n)?),
+            Schema::Array(elem, n) => {
+                let mut elems = Vec::new();
+                for _ in 0..*n {
+                    elems.push(elem.interpret::<E, _>(src)?);
+                }
+                Value::Array(elems)
+            }
+            Schema::Struct(fields) => {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::debug_span!("interpret", fields = fields.len()).entered();
+                let mut values = Vec::with_capacity(fields.len());
+                #[allow(unused_variables)]
+                let mut offset = 0usize;
+                for (name, field) in fields {
+                    let value = field.interpret::<E, _>(src)?;
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(field = name.as_str(), offset, value = ?value, "interpreted field");
+                    offset = offset.saturating_add(field.size());
+                    values.push((name.clone(), value));
+                }
+                Value::Struct(values)
+            }
+        })
+    }
+
+    /// Encode `value` to `dst` as described by this schema.
+    ///
+    /// Fails with [`ErrorKind::InvalidInput`] before writing anything if the
+    /// value does not have the shape this schema describes.
+    pub fn emit<E: ByteOrder, W: Write + ?Sized>(
+        &self,
+        value: &Value,
+        dst: &mut W,
+    ) -> IoResult<()> {
+        if !self.matches(value) {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "value does not match schema",
+            ));
+        }
+        value.pack_to::<E, _>(dst)
+    }
+
+    /// Call `f` with the path, byte offset and schema of every primitive and
+    /// [`Schema::Bytes`] field, in layout order.
+    ///
+    /// Paths name struct fields with `.` and array elements with `[i]`, as in
+    /// `header.counts[1]`; a schema that is itself a primitive has an empty
+    /// path.
+    pub fn for_each_field<F: FnMut(&str, usize, &Schema)>(&self, mut f: F) {
+        self.visit(&mut String::new(), 0, &mut f);
+    }
+
+    fn visit<F: FnMut(&str, usize, &Schema)>(
+        &self,
+        path: &mut String,
+        offset: usize,
+        f: &mut F,
+    ) -> usize {
+        let len = path.len();
+        match self {
+            Schema::Array(elem, n) => {
+                let mut offset = offset;
+                for i in 0..*n {
+                    let _ = write!(path, "[{}]", i);
+                    offset = elem.visit(path, offset, f);
+                    path.truncate(len);
+                }
+                offset
+            }
+            Schema::Struct(fields) => {
+                let mut offset = offset;
+                for (name, field) in fields {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(name);
+                    offset = field.visit(path, offset, f);
+                    path.truncate(len);
+                }
+                offset
+            }
+            _ => {
+                f(path, offset, self);
+                offset.saturating_add(self.size())
+            }
+        }
+    }
+
+    /// Name of a primitive or [`Schema::Bytes`] type, as used in Rust source
+    /// (`u16`, `[u8; 4]`...), or `None` for arrays and structs.
+    pub fn type_name(&self) -> Option<String> {
+        Some(String::from(match self {
+            Schema::U8 => "u8",
+            Schema::I8 => "i8",
+            Schema::U16 => "u16",
+            Schema::I16 => "i16",
+            Schema::U32 => "u32",
+            Schema::I32 => "i32",
+            Schema::U64 => "u64",
+            Schema::I64 => "i64",
+            Schema::U128 => "u128",
+            Schema::I128 => "i128",
+            Schema::F32 => "f32",
+            Schema::F64 => "f64",
+            Schema::Bytes(n) => return Some(format!("[u8; {}]", n)),
+            Schema::Array(..) | Schema::Struct(_) => return None,
+        }))
+    }
+
+    /// Whether `value` has the shape this schema describes.
+    pub fn matches(&self, value: &Value) -> bool {
+        match (self, value) {
+            (Schema::U8, Value::U8(_))
+            | (Schema::I8, Value::I8(_))
+            | (Schema::U16, Value::U16(_))
+            | (Schema::I16, Value::I16(_))
+            | (Schema::U32, Value::U32(_))
+            | (Schema::I32, Value::I32(_))
+            | (Schema::U64, Value::U64(_))
+            | (Schema::I64, Value::I64(_))
+            | (Schema::U128, Value::U128(_))
+            | (Schema::I128, Value::I128(_))
+            | (Schema::F32, Value::F32(_))
+            | (Schema::F64, Value::F64(_)) => true,
+            (Schema::Bytes(n), Value::Bytes(bytes)) => bytes.len() == *n,
+            (Schema::Array(elem, n), Value::Array(elems)) => {
+                elems.len() == *n && elems.iter().all(|e| elem.matches(e))
+            }
+            (Schema::Struct(fields), Value::Struct(values)) => {
+                fields.len() == values.len()
+                    && fields
+                        .iter()
+                        .zip(values)
+                        .all(|((name, field), (n, v))| name == n && field.matches(v))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Whether `E` is little-endian, for exporters that name the byte order.
+pub(crate) fn is_little_endian<E: ByteOrder>() -> bool {
+    E::read_u16(&[1, 0]) == 1
+}
+
+/// `name` as a lowercase identifier usable in other languages: anything but
+/// ASCII letters, digits and `_` becomes `_`, and names not starting with a
+/// letter (such as tuple fields) get an `f` prefix.
+pub(crate) fn identifier(name: &str) -> String {
+    let mut id: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !id.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        id.insert(0, 'f');
+    }
+    id
+}
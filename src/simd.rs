@@ -0,0 +1,47 @@
+use core::simd::{Simd, SimdElement};
+
+use byteorder::ByteOrder;
+
+use crate::io::{Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// Packs the lanes in order, each in the requested byte order.
+/// # Example
+/// ```rust
+/// #![feature(portable_simd)]
+/// use std::simd::Simd;
+/// use byteorder_pack::{PackTo, UnpackFrom};
+///
+/// let v = Simd::from_array([1u16, 2, 3, 4]);
+/// let mut buf = vec![];
+/// v.pack_to_le(&mut buf).unwrap();
+/// assert_eq!(buf, [1, 0, 2, 0, 3, 0, 4, 0]);
+///
+/// assert_eq!(Simd::<u16, 4>::unpack_from_le(&mut &buf[..]).unwrap(), v);
+/// ```
+impl<T, const N: usize> PackTo for Simd<T, N>
+where
+    T: SimdElement + PackTo,
+{
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        T::pack_multiple_to::<E, _>(self.as_array(), dst)
+    }
+}
+
+impl<T, const N: usize> UnpackFrom for Simd<T, N>
+where
+    T: SimdElement + UnpackFrom + Default,
+{
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let mut lanes = [T::default(); N];
+        T::unpack_multiple_into::<E, _>(src, &mut lanes)?;
+        Ok(Self::from_array(lanes))
+    }
+}
+
+impl<T, const N: usize> FixedPackSize for Simd<T, N>
+where
+    T: SimdElement + FixedPackSize,
+{
+    const PACKED_SIZE: usize = T::PACKED_SIZE * N;
+}
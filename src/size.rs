@@ -0,0 +1,167 @@
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8, Saturating, Wrapping,
+};
+use core::ops::{Range, RangeInclusive};
+
+/// A value whose packed representation always has the same size.
+/// # Example
+/// ```rust
+/// use byteorder_pack::FixedPackSize;
+///
+/// assert_eq!(<(u8, u16, [u32; 2])>::PACKED_SIZE, 11);
+/// ```
+pub trait FixedPackSize {
+    /// Number of bytes written by `pack_to` and read by `unpack_from`.
+    const PACKED_SIZE: usize;
+}
+
+impl<T: FixedPackSize> FixedPackSize for &'_ T {
+    const PACKED_SIZE: usize = T::PACKED_SIZE;
+}
+
+impl<T: FixedPackSize> FixedPackSize for Wrapping<T> {
+    const PACKED_SIZE: usize = T::PACKED_SIZE;
+}
+
+impl<T: FixedPackSize> FixedPackSize for Saturating<T> {
+    const PACKED_SIZE: usize = T::PACKED_SIZE;
+}
+
+impl<T: FixedPackSize> FixedPackSize for Range<T> {
+    const PACKED_SIZE: usize = T::PACKED_SIZE * 2;
+}
+
+impl<T: FixedPackSize> FixedPackSize for RangeInclusive<T> {
+    const PACKED_SIZE: usize = T::PACKED_SIZE * 2;
+}
+
+impl FixedPackSize for () {
+    const PACKED_SIZE: usize = 0;
+}
+
+impl<T: FixedPackSize, const N: usize> FixedPackSize for [T; N] {
+    const PACKED_SIZE: usize = T::PACKED_SIZE * N;
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty),+) => {
+        $(
+            impl FixedPackSize for $ty {
+                const PACKED_SIZE: usize = core::mem::size_of::<$ty>();
+            }
+        )+
+    };
+}
+
+impl_primitive!(bool, char, u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+impl_primitive!(
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128
+);
+impl_primitive!(
+    Option<NonZeroU8>,
+    Option<NonZeroU16>,
+    Option<NonZeroU32>,
+    Option<NonZeroU64>,
+    Option<NonZeroU128>,
+    Option<NonZeroI8>,
+    Option<NonZeroI16>,
+    Option<NonZeroI32>,
+    Option<NonZeroI64>,
+    Option<NonZeroI128>
+);
+
+macro_rules! impl_tuple {
+    ($($t:ident),+) => {
+        impl<$($t: FixedPackSize),+> FixedPackSize for ($($t,)+) {
+            const PACKED_SIZE: usize = 0 $(+ $t::PACKED_SIZE)+;
+        }
+    };
+}
+impl_tuple!(T1);
+impl_tuple!(T1, T2);
+impl_tuple!(T1, T2, T3);
+impl_tuple!(T1, T2, T3, T4);
+impl_tuple!(T1, T2, T3, T4, T5);
+impl_tuple!(T1, T2, T3, T4, T5, T6);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+
+/// Pack primitives and arrays of primitives into a byte array at compile time.
+///
+/// The first argument is the byte order (`BigEndian`, `NetworkEndian`, `BE`,
+/// `LittleEndian` or `LE`), followed by a list of `type = value` fields. The
+/// result is a `[u8; N]` expression that can be used to initialize a `const`
+/// or `static`.
+/// # Example
+/// ```rust
+/// use byteorder_pack::const_pack;
+///
+/// static HEADER: [u8; 10] = const_pack!(BigEndian; [u8; 4] = *b"RIFF", u32 = 0x1234, u16 = 1);
+///
+/// assert_eq!(HEADER, [b'R', b'I', b'F', b'F', 0x00, 0x00, 0x12, 0x34, 0x00, 0x01]);
+/// ```
+#[macro_export]
+macro_rules! const_pack {
+    (@len) => { 0 };
+    (@len $t:ident = $v:expr $(, $($rest:tt)*)?) => {
+        <$t as $crate::FixedPackSize>::PACKED_SIZE + $crate::const_pack!(@len $($($rest)*)?)
+    };
+    (@len [$t:ident; $n:expr] = $v:expr $(, $($rest:tt)*)?) => {
+        <[$t; $n] as $crate::FixedPackSize>::PACKED_SIZE + $crate::const_pack!(@len $($($rest)*)?)
+    };
+
+    (@bytes BigEndian, $v:expr) => { $v.to_be_bytes() };
+    (@bytes NetworkEndian, $v:expr) => { $v.to_be_bytes() };
+    (@bytes BE, $v:expr) => { $v.to_be_bytes() };
+    (@bytes LittleEndian, $v:expr) => { $v.to_le_bytes() };
+    (@bytes LE, $v:expr) => { $v.to_le_bytes() };
+
+    (@copy $buf:ident $pos:ident $bytes:expr) => {
+        let bytes = $bytes;
+        let mut i = 0;
+        while i < bytes.len() {
+            $buf[$pos + i] = bytes[i];
+            i += 1;
+        }
+        $pos += bytes.len();
+    };
+
+    (@put $buf:ident $pos:ident $order:ident;) => {};
+    (@put $buf:ident $pos:ident $order:ident; $t:ident = $v:expr $(, $($rest:tt)*)?) => {
+        let value: $t = $v;
+        $crate::const_pack!(@copy $buf $pos $crate::const_pack!(@bytes $order, value));
+        $crate::const_pack!(@put $buf $pos $order; $($($rest)*)?);
+    };
+    (@put $buf:ident $pos:ident $order:ident; [$t:ident; $n:expr] = $v:expr $(, $($rest:tt)*)?) => {
+        let values: [$t; $n] = $v;
+        let mut j = 0;
+        while j < values.len() {
+            $crate::const_pack!(@copy $buf $pos $crate::const_pack!(@bytes $order, values[j]));
+            j += 1;
+        }
+        $crate::const_pack!(@put $buf $pos $order; $($($rest)*)?);
+    };
+
+    ($order:ident; $($fields:tt)*) => {{
+        const LEN: usize = $crate::const_pack!(@len $($fields)*);
+        let mut buf = [0u8; LEN];
+        let mut pos = 0usize;
+        $crate::const_pack!(@put buf pos $order; $($fields)*);
+        let _ = pos;
+        buf
+    }};
+}
@@ -0,0 +1,207 @@
+use core::fmt;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::{PackTo, UnpackFrom};
+
+/// Error returned by [`pack_to_slice`] and [`unpack_from_slice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SliceError {
+    /// The destination slice is too small to hold the packed value.
+    BufferTooSmall,
+    /// The source slice ended before the value was complete.
+    UnexpectedEnd,
+    /// The source slice does not contain a valid encoding of the value.
+    InvalidData,
+}
+
+impl fmt::Display for SliceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SliceError::BufferTooSmall => "destination buffer too small",
+            SliceError::UnexpectedEnd => "unexpected end of source buffer",
+            SliceError::InvalidData => "invalid data in source buffer",
+        })
+    }
+}
+
+impl core::error::Error for SliceError {}
+
+impl From<IoError> for SliceError {
+    fn from(e: IoError) -> Self {
+        match e.kind() {
+            ErrorKind::WriteZero => SliceError::BufferTooSmall,
+            ErrorKind::UnexpectedEof => SliceError::UnexpectedEnd,
+            _ => SliceError::InvalidData,
+        }
+    }
+}
+
+/// Pack `value` into the start of `buf`, returning the number of bytes written.
+/// # Example
+/// ```rust
+/// use byteorder::BigEndian;
+/// use byteorder_pack::pack_to_slice;
+///
+/// let mut buf = [0; 8];
+///
+/// let n = pack_to_slice::<BigEndian, _>(&(1u8, 2u16), &mut buf).unwrap();
+///
+/// assert_eq!(&buf[..n], &[0x01, 0x00, 0x02]);
+/// ```
+pub fn pack_to_slice<E: ByteOrder, T: PackTo>(
+    value: &T,
+    buf: &mut [u8],
+) -> Result<usize, SliceError> {
+    let mut writer = SliceWriter::new(buf);
+    value.pack_to::<E, _>(&mut writer)?;
+    Ok(writer.position())
+}
+
+/// Unpack a value from the start of `buf`, returning it together with the
+/// number of bytes consumed.
+/// # Example
+/// ```rust
+/// use byteorder::LittleEndian;
+/// use byteorder_pack::{unpack_from_slice, SliceError};
+///
+/// let buf = [0x01, 0x02, 0x00, 0xFF];
+///
+/// let ((a, b), n) = unpack_from_slice::<LittleEndian, (u8, u16)>(&buf).unwrap();
+///
+/// assert_eq!((a, b, n), (1, 2, 3));
+/// assert_eq!(unpack_from_slice::<LittleEndian, u32>(&buf[1..]), Err(SliceError::UnexpectedEnd));
+/// ```
+pub fn unpack_from_slice<E: ByteOrder, T: UnpackFrom>(
+    buf: &[u8],
+) -> Result<(T, usize), SliceError> {
+    let mut reader = SliceReader::new(buf);
+    let value = T::unpack_from::<E, _>(&mut reader)?;
+    Ok((value, reader.position()))
+}
+
+/// A position-tracking writer over a mutable byte slice.
+///
+/// Unlike writing through `&mut [u8]` directly, a write that does not fit in
+/// the remaining space fails with [`ErrorKind::WriteZero`] without writing
+/// anything or advancing the position. A composite value is packed with one
+/// write per field, so a failed pack may still leave its leading fields in
+/// [`written`](Self::written).
+/// # Example
+/// ```rust
+/// use byteorder_pack::{PackTo, SliceWriter};
+///
+/// let mut buf = [0; 4];
+/// let mut writer = SliceWriter::new(&mut buf);
+///
+/// 0x0102u16.pack_to_be(&mut writer).unwrap();
+/// assert!(0x03040506u32.pack_to_be(&mut writer).is_err());
+///
+/// assert_eq!(writer.written(), &[0x01, 0x02]);
+/// ```
+#[derive(Debug)]
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Create a writer that starts at the beginning of `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Number of bytes written so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Number of bytes that can still be written.
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// The bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+
+    /// Consume the writer, returning the written part of the slice.
+    pub fn into_written(self) -> &'a mut [u8] {
+        &mut self.buf[..self.pos]
+    }
+}
+
+impl Write for SliceWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        if buf.len() > self.remaining() {
+            return Err(IoError::new(ErrorKind::WriteZero, "slice writer is full"));
+        }
+        self.buf[self.pos..self.pos + buf.len()].copy_from_slice(buf);
+        self.pos += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// A position-tracking reader over a byte slice.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{SliceReader, UnpackFrom};
+///
+/// let mut reader = SliceReader::new(&[0x01, 0x02, 0x03]);
+///
+/// assert_eq!(u16::unpack_from_be(&mut reader).unwrap(), 0x0102);
+/// assert_eq!(reader.position(), 2);
+/// assert_eq!(reader.remaining(), &[0x03]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SliceReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> SliceReader<'a> {
+    /// Create a reader that starts at the beginning of `buf`.
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Number of bytes read so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The bytes that have not been read yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+
+    /// Whether all bytes have been read.
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.buf.len()
+    }
+}
+
+impl Read for SliceReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let n = buf.len().min(self.buf.len() - self.pos);
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> IoResult<()> {
+        if buf.len() > self.buf.len() - self.pos {
+            return Err(IoError::new(
+                ErrorKind::UnexpectedEof,
+                "slice reader is exhausted",
+            ));
+        }
+        self.read(buf).map(|_| ())
+    }
+}
@@ -0,0 +1,338 @@
+//! [`proptest`] support, enabled by the `proptest` feature.
+//!
+//! The crate's wrapper types implement `proptest::arbitrary::Arbitrary`, so
+//! `any::<FourCc>()` and the like work directly. [`value`] generates
+//! [`Value`]s of any [`Schema`], and [`check_roundtrip`] turns a pack/unpack
+//! isomorphism check into a one-liner inside `proptest!`.
+//! # Example
+//! ```rust
+//! use byteorder_pack::byteorder::LittleEndian;
+//! use byteorder_pack::strategy::check_roundtrip;
+//! use byteorder_pack::{CrcFrame, FourCc};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn chunk_header_roundtrips(header: (FourCc, u32)) {
+//!         check_roundtrip::<LittleEndian, _>(&header)?;
+//!     }
+//!
+//!     fn frame_roundtrips(frame: CrcFrame<(u8, [u16; 4])>) {
+//!         check_roundtrip::<LittleEndian, _>(&frame)?;
+//!     }
+//! }
+//! # chunk_header_roundtrips();
+//! # frame_roundtrips();
+//! ```
+
+use std::fmt::Debug;
+
+use byteorder::ByteOrder;
+use proptest::arbitrary::{any, any_with, Arbitrary};
+use proptest::collection::vec;
+use proptest::option::of;
+use proptest::prop_assert_eq;
+use proptest::strategy::{BoxedStrategy, Strategy};
+use proptest::test_runner::TestCaseError;
+
+use crate::{
+    Bcd, CrcFrame, Delta, FixedPoint, FixedStr, FourCc, LengthPrefixed, MacAddr, Maybe, NormRange,
+    OptionSentinel, PackTo, Padding, Presence, Rle, SNorm16, SNorm8, Schema, SentinelInt,
+    SevenBitEncoded, SliceReader, UNorm16, UNorm8, UnpackFrom, Value, VarI64, VarU32, VarU64,
+    VariableByteInteger, ZigZag, I24, U24, U40, U48,
+};
+
+impl Arbitrary for FourCc {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<[u8; 4]>().prop_map(FourCc).boxed()
+    }
+}
+
+impl<T, L, const MAX_LEN: usize> Arbitrary for CrcFrame<T, L, MAX_LEN>
+where
+    T: Arbitrary + 'static,
+    L: Debug + 'static,
+{
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        any_with::<T>(args).prop_map(CrcFrame::new).boxed()
+    }
+}
+
+impl<T: Arbitrary + 'static> Arbitrary for Delta<T> {
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        any_with::<T>(args).prop_map(Delta).boxed()
+    }
+}
+
+impl<L, T> Arbitrary for LengthPrefixed<L, T>
+where
+    L: Debug + 'static,
+    T: Arbitrary + 'static,
+{
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        any_with::<T>(args).prop_map(LengthPrefixed::new).boxed()
+    }
+}
+
+impl<const N: usize, P: Padding + Debug + 'static> Arbitrary for FixedStr<N, P> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<String>().prop_map(|text| Self::new(&text)).boxed()
+    }
+}
+
+impl<T: Arbitrary + 'static> Arbitrary for Maybe<T> {
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        of(any_with::<T>(args)).prop_map(Maybe).boxed()
+    }
+}
+
+impl<I: Arbitrary + 'static, const FRAC_BITS: u32> Arbitrary for FixedPoint<I, FRAC_BITS> {
+    type Parameters = I::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: I::Parameters) -> Self::Strategy {
+        any_with::<I>(args).prop_map(FixedPoint).boxed()
+    }
+}
+
+impl Arbitrary for MacAddr {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<[u8; 6]>().prop_map(MacAddr).boxed()
+    }
+}
+
+impl<T, const SENTINEL: u64> Arbitrary for OptionSentinel<T, SENTINEL>
+where
+    T: Arbitrary + SentinelInt + 'static,
+{
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        let sentinel = OptionSentinel::<T, SENTINEL>::sentinel();
+        let value = any_with::<T>(args)
+            .prop_filter("value equals the sentinel", move |value| *value != sentinel);
+        of(value).prop_map(OptionSentinel::new).boxed()
+    }
+}
+
+impl<T, B> Arbitrary for Presence<T, B>
+where
+    T: Arbitrary + 'static,
+    B: Debug + 'static,
+{
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        any_with::<T>(args).prop_map(Presence::new).boxed()
+    }
+}
+
+impl<T, C, const MAX_LEN: usize> Arbitrary for Rle<T, C, MAX_LEN>
+where
+    T: Arbitrary + 'static,
+    C: Debug + 'static,
+{
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        vec(any_with::<T>(args), 0..64).prop_map(Rle::new).boxed()
+    }
+}
+
+macro_rules! impl_int {
+    ($($name:ident),+) => {
+        $(
+            impl Arbitrary for $name {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: ()) -> Self::Strategy {
+                    ($name::MIN.get()..=$name::MAX.get())
+                        .prop_filter_map("out of range", $name::new)
+                        .boxed()
+                }
+            }
+        )+
+    };
+}
+
+impl_int!(I24, U24, U40, U48);
+
+macro_rules! impl_bcd {
+    ($($int:ty),+) => {
+        $(
+            impl Arbitrary for Bcd<$int> {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: ()) -> Self::Strategy {
+                    (0..=Bcd::<$int>::MAX.0).prop_map(Bcd).boxed()
+                }
+            }
+        )+
+    };
+}
+
+impl_bcd!(u8, u16, u32, u64);
+
+macro_rules! impl_var {
+    ($($name:ident($int:ty)),+) => {
+        $(
+            impl Arbitrary for $name {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: ()) -> Self::Strategy {
+                    any::<$int>().prop_map($name).boxed()
+                }
+            }
+        )+
+    };
+}
+
+impl_var!(VarI64(i64), VarU32(u32), VarU64(u64));
+
+impl<T: Arbitrary + 'static> Arbitrary for SevenBitEncoded<T> {
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        any_with::<T>(args).prop_map(SevenBitEncoded).boxed()
+    }
+}
+
+impl Arbitrary for VariableByteInteger {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (0..=VariableByteInteger::MAX.0)
+            .prop_map(VariableByteInteger)
+            .boxed()
+    }
+}
+
+impl<T: Arbitrary + 'static> Arbitrary for ZigZag<T> {
+    type Parameters = T::Parameters;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        any_with::<T>(args).prop_map(ZigZag).boxed()
+    }
+}
+
+// Generate from the packed integer, so the floats survive a round trip.
+macro_rules! impl_norm {
+    ($($name:ident($int:ty)),+) => {
+        $(
+            impl<R: NormRange + Debug + 'static> Arbitrary for $name<R> {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: ()) -> Self::Strategy {
+                    any::<$int>().prop_map($name::from_int).boxed()
+                }
+            }
+        )+
+    };
+}
+
+impl_norm!(UNorm8(u8), UNorm16(u16), SNorm8(i8), SNorm16(i16));
+
+/// Generate values with the shape `schema` describes.
+///
+/// Floats are never NaN, so generated values compare equal to themselves after
+/// a round trip.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::{strategy, Schema};
+/// use proptest::strategy::{Strategy, ValueTree};
+/// use proptest::test_runner::TestRunner;
+///
+/// let schema = Schema::Struct(vec![
+///     ("id".into(), Schema::U16),
+///     ("samples".into(), Schema::Array(Box::new(Schema::F32), 3)),
+/// ]);
+/// let mut runner = TestRunner::default();
+/// let value = strategy::value(&schema).new_tree(&mut runner).unwrap().current();
+///
+/// let mut buf = vec![];
+/// schema.emit::<BigEndian, _>(&value, &mut buf).unwrap();
+/// assert_eq!(buf.len(), schema.size());
+/// ```
+pub fn value(schema: &Schema) -> BoxedStrategy<Value> {
+    match schema {
+        Schema::U8 => any::<u8>().prop_map(Value::U8).boxed(),
+        Schema::I8 => any::<i8>().prop_map(Value::I8).boxed(),
+        Schema::U16 => any::<u16>().prop_map(Value::U16).boxed(),
+        Schema::I16 => any::<i16>().prop_map(Value::I16).boxed(),
+        Schema::U32 => any::<u32>().prop_map(Value::U32).boxed(),
+        Schema::I32 => any::<i32>().prop_map(Value::I32).boxed(),
+        Schema::U64 => any::<u64>().prop_map(Value::U64).boxed(),
+        Schema::I64 => any::<i64>().prop_map(Value::I64).boxed(),
+        Schema::U128 => any::<u128>().prop_map(Value::U128).boxed(),
+        Schema::I128 => any::<i128>().prop_map(Value::I128).boxed(),
+        Schema::F32 => any::<f32>().prop_map(Value::F32).boxed(),
+        Schema::F64 => any::<f64>().prop_map(Value::F64).boxed(),
+        Schema::Bytes(n) => vec(any::<u8>(), *n).prop_map(Value::Bytes).boxed(),
+        Schema::Array(elem, n) => vec(value(elem), *n).prop_map(Value::Array).boxed(),
+        Schema::Struct(fields) => {
+            let names = fields
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>();
+            fields
+                .iter()
+                .map(|(_, field)| value(field))
+                .collect::<Vec<_>>()
+                .prop_map(move |values| Value::Struct(names.iter().cloned().zip(values).collect()))
+                .boxed()
+        }
+    }
+}
+
+/// Check that `value` packs in byte order `E` to bytes that unpack, consuming
+/// all of them, to an equal value.
+///
+/// Intended for use inside `proptest!`, where the error fails the test case
+/// and triggers shrinking.
+pub fn check_roundtrip<E, T>(value: &T) -> Result<(), TestCaseError>
+where
+    E: ByteOrder,
+    T: PackTo + UnpackFrom + PartialEq + Debug,
+{
+    let mut bytes = Vec::new();
+    value
+        .pack_to::<E, _>(&mut bytes)
+        .map_err(|e| TestCaseError::fail(format!("failed to pack {:?}: {}", value, e)))?;
+    let mut src = SliceReader::new(&bytes);
+    let unpacked = T::unpack_from::<E, _>(&mut src)
+        .map_err(|e| TestCaseError::fail(format!("failed to unpack {:02x?}: {}", bytes, e)))?;
+    prop_assert_eq!(src.remaining(), &[][..], "trailing bytes after unpacking");
+    prop_assert_eq!(&unpacked, value);
+    Ok(())
+}
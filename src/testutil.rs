@@ -0,0 +1,189 @@
+//! Assertions for testing [`PackTo`] and [`UnpackFrom`] impls.
+//!
+//! On failure these panic with the values involved and hexdumps of the packed
+//! bytes, rather than just two unequal byte vectors. The `_described` variants,
+//! for types that implement [`Describe`], also give the first differing field
+//! (see [`diff`]) and annotate the hexdumps with field names.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use byteorder::ByteOrder;
+
+use crate::{diff, Describe, PackTo, PackedDebug, SliceReader, UnpackFrom};
+
+fn pack<E: ByteOrder, T: PackTo + Debug>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    if let Err(e) = value.pack_to::<E, _>(&mut bytes) {
+        panic!("failed to pack {:?}: {}", value, e);
+    }
+    bytes
+}
+
+fn unpack<E: ByteOrder, T: UnpackFrom>(bytes: &[u8]) -> T {
+    let mut src = SliceReader::new(bytes);
+    let value = match T::unpack_from::<E, _>(&mut src) {
+        Ok(value) => value,
+        Err(e) => panic!(
+            "failed to unpack at offset {:#x}: {}\n{}",
+            src.position(),
+            e,
+            PackedDebug::<_, E>::new(&bytes)
+        ),
+    };
+    if !src.is_empty() {
+        panic!(
+            "{} trailing bytes after unpacking at offset {:#x}\n{}",
+            src.remaining().len(),
+            src.position(),
+            PackedDebug::<_, E>::new(&bytes)
+        );
+    }
+    value
+}
+
+/// The unpacked value and its packed bytes if `value` does not survive a
+/// round trip through the bytes it packs to.
+fn roundtrip_mismatch<E, T>(value: &T) -> Option<(T, Vec<u8>, Vec<u8>)>
+where
+    E: ByteOrder,
+    T: PackTo + UnpackFrom + PartialEq + Debug,
+{
+    let bytes = pack::<E, _>(value);
+    let unpacked = unpack::<E, T>(&bytes);
+    let repacked = pack::<E, _>(&unpacked);
+    (unpacked != *value || repacked != bytes).then_some((unpacked, bytes, repacked))
+}
+
+/// The first differing field of two packings of a `T`, as a line to add to a
+/// failure message.
+fn first_difference<E: ByteOrder, T: UnpackFrom + Describe>(left: &[u8], right: &[u8]) -> String {
+    match diff::<E, T>(left, right) {
+        Ok(Some(d)) => format!("\nfirst difference: {}", d),
+        _ => String::new(),
+    }
+}
+
+/// Assert that `value` packs in byte order `E` to bytes that unpack to an equal
+/// value, consuming all of them, and that the unpacked value packs to the same
+/// bytes.
+///
+/// This works for any type, including collections and derived types with
+/// variable-length fields. For types that implement [`Describe`],
+/// [`assert_pack_roundtrip_described`] also reports the first differing
+/// field.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::LittleEndian;
+/// use byteorder_pack::testutil::assert_pack_roundtrip;
+/// use byteorder_pack::LengthPrefixed;
+///
+/// assert_pack_roundtrip::<LittleEndian, _>(&(1u8, [2u16, 3u16]));
+/// assert_pack_roundtrip::<LittleEndian, _>(&LengthPrefixed::<u8, _>::new(String::from("text")));
+/// ```
+#[track_caller]
+pub fn assert_pack_roundtrip<E, T>(value: &T)
+where
+    E: ByteOrder,
+    T: PackTo + UnpackFrom + PartialEq + Debug,
+{
+    if let Some((unpacked, _, _)) = roundtrip_mismatch::<E, T>(value) {
+        panic!(
+            "pack/unpack round trip mismatch\n  original: {:?}\n  unpacked: {:?}\noriginal bytes:\n{}unpacked bytes:\n{}",
+            value,
+            unpacked,
+            PackedDebug::<_, E>::new(value),
+            PackedDebug::<_, E>::new(&unpacked)
+        );
+    }
+}
+
+/// Like [`assert_pack_roundtrip`], but on failure also reports the first
+/// differing field and annotates the hexdumps with the fields of `T`.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::LittleEndian;
+/// use byteorder_pack::testutil::assert_pack_roundtrip_described;
+///
+/// assert_pack_roundtrip_described::<LittleEndian, _>(&(1u8, [2u16, 3u16]));
+/// ```
+#[track_caller]
+pub fn assert_pack_roundtrip_described<E, T>(value: &T)
+where
+    E: ByteOrder,
+    T: PackTo + UnpackFrom + Describe + PartialEq + Debug,
+{
+    if let Some((unpacked, bytes, repacked)) = roundtrip_mismatch::<E, T>(value) {
+        panic!(
+            "pack/unpack round trip mismatch{}\n  original: {:?}\n  unpacked: {:?}\noriginal bytes:\n{}unpacked bytes:\n{}",
+            first_difference::<E, T>(&bytes, &repacked),
+            value,
+            unpacked,
+            PackedDebug::<_, E>::annotated(value),
+            PackedDebug::<_, E>::annotated(&unpacked)
+        );
+    }
+}
+
+/// Assert that `bytes` unpack in byte order `E` to a value equal to
+/// `expected`, consuming all of them.
+///
+/// For types that implement [`Describe`], [`assert_unpack_eq_described`] also
+/// reports the first differing field.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::testutil::assert_unpack_eq;
+/// use byteorder_pack::LengthPrefixed;
+///
+/// assert_unpack_eq::<BigEndian, _>(&[0x01, 0x00, 0x02], &(1u8, 2u16));
+/// let bytes = [0x00, 0x02, 0x07, 0x08];
+/// assert_unpack_eq::<BigEndian, _>(&bytes, &LengthPrefixed::<u16, _>::new(vec![7u8, 8]));
+/// ```
+#[track_caller]
+pub fn assert_unpack_eq<E, T>(bytes: &[u8], expected: &T)
+where
+    E: ByteOrder,
+    T: PackTo + UnpackFrom + PartialEq + Debug,
+{
+    let actual = unpack::<E, T>(bytes);
+    if actual != *expected {
+        panic!(
+            "unpacked value does not match\n    actual: {:?}\n  expected: {:?}\nactual bytes:\n{}expected bytes:\n{}",
+            actual,
+            expected,
+            PackedDebug::<_, E>::new(&actual),
+            PackedDebug::<_, E>::new(expected)
+        );
+    }
+}
+
+/// Like [`assert_unpack_eq`], but on failure also reports the first differing
+/// field and annotates the hexdumps with the fields of `T`.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::BigEndian;
+/// use byteorder_pack::testutil::assert_unpack_eq_described;
+///
+/// assert_unpack_eq_described::<BigEndian, _>(&[0x01, 0x00, 0x02], &(1u8, 2u16));
+/// ```
+#[track_caller]
+pub fn assert_unpack_eq_described<E, T>(bytes: &[u8], expected: &T)
+where
+    E: ByteOrder,
+    T: PackTo + UnpackFrom + Describe + PartialEq + Debug,
+{
+    let actual = unpack::<E, T>(bytes);
+    if actual != *expected {
+        panic!(
+            "unpacked value does not match{}\n    actual: {:?}\n  expected: {:?}\nactual bytes:\n{}expected bytes:\n{}",
+            first_difference::<E, T>(bytes, &pack::<E, _>(expected)),
+            actual,
+            expected,
+            PackedDebug::<_, E>::annotated(&actual),
+            PackedDebug::<_, E>::annotated(expected)
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Packs the whole seconds as a `u64` followed by the nanoseconds as a `u32`.
+///
+/// Unpacking fails with [`ErrorKind::InvalidData`] if the nanoseconds are a
+/// second or more.
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use byteorder_pack::{PackTo, UnpackFrom};
+///
+/// let timeout = Duration::from_millis(1500);
+/// let mut buf = vec![];
+/// timeout.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0, 0, 0, 0, 0, 0, 0, 1, 0x1d, 0xcd, 0x65, 0x00]);
+///
+/// assert_eq!(Duration::unpack_from_be(&mut &buf[..]).unwrap(), timeout);
+/// ```
+impl PackTo for Duration {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (self.as_secs(), self.subsec_nanos()).pack_to::<E, _>(dst)
+    }
+}
+
+impl UnpackFrom for Duration {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let (secs, nanos) = <(u64, u32)>::unpack_from::<E, _>(src)?;
+        if nanos >= NANOS_PER_SEC {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "nanoseconds exceed one second",
+            ));
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+impl FixedPackSize for Duration {
+    const PACKED_SIZE: usize = 12;
+}
+
+/// Packs the time since the Unix epoch as whole seconds in an `i64`, negative
+/// before the epoch, followed by the nanoseconds past them as a `u32`.
+///
+/// So one nanosecond before the epoch is `-1` seconds and `999_999_999`
+/// nanoseconds. Packing fails with [`ErrorKind::InvalidInput`] for times more
+/// than `i64::MAX` seconds from the epoch, and unpacking fails with
+/// [`ErrorKind::InvalidData`] if the nanoseconds are a second or more, or if
+/// the time cannot be represented on this platform.
+/// # Example
+/// ```rust
+/// use std::time::{Duration, SystemTime, UNIX_EPOCH};
+/// use byteorder_pack::{PackTo, UnpackFrom};
+///
+/// let time = UNIX_EPOCH - Duration::from_nanos(1);
+/// let mut buf = vec![];
+/// time.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x3b, 0x9a, 0xc9, 0xff]);
+///
+/// assert_eq!(SystemTime::unpack_from_be(&mut &buf[..]).unwrap(), time);
+/// ```
+#[cfg(feature = "std")]
+impl PackTo for SystemTime {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        let out_of_range = || IoError::new(ErrorKind::InvalidInput, "time is out of range");
+        let (secs, nanos) = match self.duration_since(UNIX_EPOCH) {
+            Ok(since) => (
+                i64::try_from(since.as_secs()).map_err(|_| out_of_range())?,
+                since.subsec_nanos(),
+            ),
+            Err(e) => {
+                let before = e.duration();
+                let secs = i64::try_from(before.as_secs()).map_err(|_| out_of_range())?;
+                match before.subsec_nanos() {
+                    0 => (-secs, 0),
+                    nanos => (
+                        (-secs).checked_sub(1).ok_or_else(out_of_range)?,
+                        NANOS_PER_SEC - nanos,
+                    ),
+                }
+            }
+        };
+        (secs, nanos).pack_to::<E, _>(dst)
+    }
+}
+
+#[cfg(feature = "std")]
+impl UnpackFrom for SystemTime {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let (secs, nanos) = <(i64, u32)>::unpack_from::<E, _>(src)?;
+        if nanos >= NANOS_PER_SEC {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "nanoseconds exceed one second",
+            ));
+        }
+        let whole = Duration::from_secs(secs.unsigned_abs());
+        let time = match secs {
+            0.. => UNIX_EPOCH.checked_add(whole),
+            _ => UNIX_EPOCH.checked_sub(whole),
+        };
+        time.and_then(|time| time.checked_add(Duration::from_nanos(nanos.into())))
+            .ok_or_else(|| IoError::new(ErrorKind::InvalidData, "time is out of range"))
+    }
+}
+
+#[cfg(feature = "std")]
+impl FixedPackSize for SystemTime {
+    const PACKED_SIZE: usize = 12;
+}
+
+/// Packs the time since the Unix epoch like `SystemTime`: whole seconds in
+/// an `i64` followed by the nanoseconds past them as a `u32`.
+///
+/// A leap second has a second or more of nanoseconds, as in `chrono`.
+/// Unpacking fails with [`ErrorKind::InvalidData`] if the time is out of
+/// range for `chrono` or the nanoseconds are invalid.
+/// # Example
+/// ```rust
+/// use byteorder_pack::chrono::DateTime;
+/// use byteorder_pack::{PackTo, UnpackFrom};
+///
+/// let time = DateTime::from_timestamp(1_700_000_000, 5).unwrap();
+/// let mut buf = vec![];
+/// time.pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0, 0, 0, 0, 0x65, 0x53, 0xf1, 0x00, 0, 0, 0, 5]);
+///
+/// assert_eq!(DateTime::unpack_from_be(&mut &buf[..]).unwrap(), time);
+/// ```
+#[cfg(feature = "chrono")]
+impl PackTo for chrono::DateTime<chrono::Utc> {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (self.timestamp(), self.timestamp_subsec_nanos()).pack_to::<E, _>(dst)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl UnpackFrom for chrono::DateTime<chrono::Utc> {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let (secs, nanos) = <(i64, u32)>::unpack_from::<E, _>(src)?;
+        chrono::DateTime::from_timestamp(secs, nanos)
+            .ok_or_else(|| IoError::new(ErrorKind::InvalidData, "time is out of range"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FixedPackSize for chrono::DateTime<chrono::Utc> {
+    const PACKED_SIZE: usize = 12;
+}
+
+/// Packs the time since the Unix epoch like `SystemTime`: whole seconds in
+/// an `i64` followed by the nanoseconds past them as a `u32`.
+///
+/// The offset is not packed, so unpacked times are in UTC. Unpacking fails
+/// with [`ErrorKind::InvalidData`] if the time is out of range for `time` or
+/// the nanoseconds are a second or more.
+/// # Example
+/// ```rust
+/// use byteorder_pack::time::OffsetDateTime;
+/// use byteorder_pack::{PackTo, UnpackFrom};
+///
+/// let time = OffsetDateTime::from_unix_timestamp(-1).unwrap();
+/// let mut buf = vec![];
+/// time.pack_to_le(&mut buf).unwrap();
+/// assert_eq!(buf, [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]);
+///
+/// assert_eq!(OffsetDateTime::unpack_from_le(&mut &buf[..]).unwrap(), time);
+/// ```
+#[cfg(feature = "time")]
+impl PackTo for time::OffsetDateTime {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        (self.unix_timestamp(), self.nanosecond()).pack_to::<E, _>(dst)
+    }
+}
+
+#[cfg(feature = "time")]
+impl UnpackFrom for time::OffsetDateTime {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let (secs, nanos) = <(i64, u32)>::unpack_from::<E, _>(src)?;
+        time::OffsetDateTime::from_unix_timestamp(secs)
+            .and_then(|time| time.replace_nanosecond(nanos))
+            .map_err(|_| IoError::new(ErrorKind::InvalidData, "time is out of range"))
+    }
+}
+
+#[cfg(feature = "time")]
+impl FixedPackSize for time::OffsetDateTime {
+    const PACKED_SIZE: usize = 12;
+}
+
+#[cfg(feature = "alloc")]
+impl crate::Describe for Duration {
+    fn schema() -> crate::Schema {
+        crate::Schema::Struct(alloc::vec![
+            ("secs".into(), crate::Schema::U64),
+            ("nanos".into(), crate::Schema::U32),
+        ])
+    }
+}
+
+#[cfg(feature = "std")]
+impl crate::Describe for SystemTime {
+    fn schema() -> crate::Schema {
+        crate::Schema::Struct(alloc::vec![
+            ("secs".into(), crate::Schema::I64),
+            ("nanos".into(), crate::Schema::U32),
+        ])
+    }
+}
@@ -0,0 +1,369 @@
+//! Hooks called by derived impls to track field offsets for padding and to
+//! report decoding progress through `tracing` when the `tracing` feature is
+//! enabled. Without the feature they compile down to plain unpacking.
+
+use core::fmt::Debug;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::UnpackFrom;
+
+/// Bytes of padding needed after `offset` to reach a multiple of `align`.
+fn padding(offset: u64, align: u64) -> u64 {
+    match offset % align {
+        0 => 0,
+        rem => align - rem,
+    }
+}
+
+/// A writer that counts the bytes written through it, so padding can be
+/// computed relative to the start of the struct being packed.
+pub struct FieldWriter<'a, W: ?Sized> {
+    dst: &'a mut W,
+    offset: u64,
+}
+
+impl<'a, W: Write + ?Sized> FieldWriter<'a, W> {
+    pub fn new(dst: &'a mut W) -> Self {
+        Self { dst, offset: 0 }
+    }
+
+    /// Write `n` zero bytes.
+    pub fn pad(&mut self, mut n: u64) -> IoResult<()> {
+        let zeros = [0u8; 16];
+        while n > 0 {
+            let chunk = n.min(zeros.len() as u64) as usize;
+            self.write_all(&zeros[..chunk])?;
+            n -= chunk as u64;
+        }
+        Ok(())
+    }
+
+    /// Write zero bytes up to the next multiple of `align`.
+    pub fn align(&mut self, align: u64) -> IoResult<()> {
+        self.pad(padding(self.offset, align))
+    }
+}
+
+impl<W: Write + ?Sized> Write for FieldWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let n = self.dst.write(buf)?;
+        self.offset += n as u64;
+        Ok(n)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> IoResult<()> {
+        self.dst.write_all(buf)?;
+        self.offset += buf.len() as u64;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        self.dst.flush()
+    }
+}
+
+/// A writer that discards the bytes written to it.
+pub struct Discard;
+
+impl Write for Discard {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        Ok(())
+    }
+}
+
+/// Number of bytes that `pack` writes.
+pub fn packed_len(
+    pack: impl FnOnce(&mut FieldWriter<'_, Discard>) -> IoResult<()>,
+) -> IoResult<usize> {
+    let mut discard = Discard;
+    let mut dst = FieldWriter::new(&mut discard);
+    pack(&mut dst)?;
+    Ok(usize::try_from(dst.offset).unwrap_or(usize::MAX))
+}
+
+/// A reader that counts the bytes read through it, so field offsets can be
+/// reported relative to the start of the struct being unpacked.
+pub struct FieldReader<'a, R: ?Sized> {
+    src: &'a mut R,
+    offset: u64,
+}
+
+impl<'a, R: Read + ?Sized> FieldReader<'a, R> {
+    pub fn new(src: &'a mut R) -> Self {
+        Self { src, offset: 0 }
+    }
+
+    /// Unpack the field `name`, emitting a `trace` event with its offset and
+    /// a `debug` event with its value.
+    pub fn field<E: ByteOrder, T: UnpackFrom>(
+        &mut self,
+        name: &'static str,
+        value: impl for<'v> FnOnce(&'v T) -> &'v dyn Debug,
+    ) -> IoResult<T> {
+        self.field_with(name, value, |src| T::unpack_from::<E, _>(src))
+    }
+
+    /// Like [`field`](Self::field), but unpack the value with `unpack`.
+    #[allow(unused_variables)]
+    pub fn field_with<T>(
+        &mut self,
+        name: &'static str,
+        value: impl for<'v> FnOnce(&'v T) -> &'v dyn Debug,
+        unpack: impl FnOnce(&mut Self) -> IoResult<T>,
+    ) -> IoResult<T> {
+        let offset = self.offset;
+        #[cfg(feature = "tracing")]
+        tracing::trace!(field = name, offset, "unpacking field");
+        let result = unpack(self);
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(v) => tracing::debug!(field = name, offset, value = ?value(v), "unpacked field"),
+            Err(e) => tracing::debug!(field = name, offset, error = %e, "failed to unpack field"),
+        }
+        result
+    }
+
+    /// Unpack with `unpack` from exactly the next `len` bytes.
+    pub fn bounded<T>(
+        &mut self,
+        len: usize,
+        unpack: impl FnOnce(&mut FieldReader<'_, Bounded<'_, Self>>) -> IoResult<T>,
+    ) -> IoResult<T> {
+        let offset = self.offset;
+        let mut src = Bounded {
+            src: self,
+            left: len as u64,
+        };
+        let value = unpack(&mut FieldReader {
+            src: &mut src,
+            offset,
+        });
+        match value {
+            Ok(_) if src.left > 0 => Err(IoError::new(
+                ErrorKind::InvalidData,
+                "field is shorter than its length",
+            )),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof && src.left == 0 => Err(IoError::new(
+                ErrorKind::InvalidData,
+                "field is longer than its length",
+            )),
+            value => value,
+        }
+    }
+
+    /// Read and discard `n` bytes of padding.
+    pub fn skip(&mut self, mut n: u64) -> IoResult<()> {
+        let mut buf = [0u8; 16];
+        while n > 0 {
+            let chunk = n.min(buf.len() as u64) as usize;
+            self.read_exact(&mut buf[..chunk])?;
+            n -= chunk as u64;
+        }
+        Ok(())
+    }
+
+    /// Skip padding up to the next multiple of `align`.
+    pub fn align(&mut self, align: u64) -> IoResult<()> {
+        self.skip(padding(self.offset, align))
+    }
+
+    /// Read `expected.len()` bytes and fail with [`ErrorKind::InvalidData`]
+    /// unless they equal `expected`.
+    pub fn magic(&mut self, expected: &'static [u8]) -> IoResult<()> {
+        // Only the first bytes, which cover any common magic, are kept for
+        // the error message.
+        let mut found = [0u8; 16];
+        let head = expected.len().min(found.len());
+        self.read_exact(&mut found[..head])?;
+        let mut matches = found[..head] == expected[..head];
+        let mut rest = [0u8; 16];
+        for chunk in expected[head..].chunks(rest.len()) {
+            let rest = &mut rest[..chunk.len()];
+            self.read_exact(rest)?;
+            matches &= rest == chunk;
+        }
+        if matches {
+            Ok(())
+        } else {
+            Err(bad_magic(expected, &found[..head]))
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn bad_magic(expected: &[u8], found: &[u8]) -> IoError {
+    IoError::new(
+        ErrorKind::InvalidData,
+        format!(
+            "bad magic: expected {:02x?}, found {:02x?}",
+            expected, found
+        ),
+    )
+}
+
+#[cfg(not(feature = "std"))]
+fn bad_magic(_: &[u8], _: &[u8]) -> IoError {
+    IoError::new(ErrorKind::InvalidData, "bad magic")
+}
+
+impl<R: Read + ?Sized> Read for FieldReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let n = self.src.read(buf)?;
+        self.offset += n as u64;
+        Ok(n)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> IoResult<()> {
+        self.src.read_exact(buf)?;
+        self.offset += buf.len() as u64;
+        Ok(())
+    }
+}
+
+/// A reader that records the bytes read through it, so that each variant of
+/// an untagged enum can be tried from the same starting point.
+#[cfg(feature = "alloc")]
+pub struct Replay<'a, R: ?Sized> {
+    src: &'a mut R,
+    buf: Vec<u8>,
+    pos: usize,
+    offset: u64,
+    /// Whether every attempt so far ran out of input.
+    eof: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, 'b, R: Read + ?Sized> Replay<'a, FieldReader<'b, R>> {
+    pub fn new(fields: &'a mut FieldReader<'b, R>) -> Self {
+        let offset = fields.offset;
+        Self {
+            src: fields,
+            buf: Vec::new(),
+            pos: 0,
+            offset,
+            eof: true,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Read + ?Sized> Replay<'_, R> {
+    /// Unpack the variant `name` with `f`, starting over from the first byte
+    /// recorded. Returns `Ok(None)` if the input is not valid for it.
+    ///
+    /// A variant that matches must consume every byte that earlier attempts
+    /// read, since they cannot be given back to the source.
+    #[allow(unused_variables)]
+    pub fn attempt<T>(
+        &mut self,
+        name: &'static str,
+        f: impl FnOnce(&mut FieldReader<'_, Self>) -> IoResult<T>,
+    ) -> IoResult<Option<T>> {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(variant = name, "trying variant");
+        self.pos = 0;
+        let offset = self.offset;
+        match f(&mut FieldReader { src: self, offset }) {
+            Ok(_) if self.pos < self.buf.len() => Err(IoError::new(
+                ErrorKind::InvalidData,
+                "enum variant is shorter than the input read by earlier variants",
+            )),
+            Ok(value) => Ok(Some(value)),
+            Err(e) if matches!(e.kind(), ErrorKind::InvalidData | ErrorKind::UnexpectedEof) => {
+                self.eof &= e.kind() == ErrorKind::UnexpectedEof;
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The error once no variant matched: [`ErrorKind::UnexpectedEof`] if
+    /// every attempt ran out of input, [`ErrorKind::InvalidData`] otherwise.
+    pub fn no_match(&self) -> IoError {
+        let kind = match self.eof {
+            true => ErrorKind::UnexpectedEof,
+            false => ErrorKind::InvalidData,
+        };
+        IoError::new(kind, "no enum variant matches")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<R: Read + ?Sized> Read for Replay<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let n = if self.pos < self.buf.len() {
+            let n = buf.len().min(self.buf.len() - self.pos);
+            buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+            n
+        } else {
+            let n = self.src.read(buf)?;
+            self.buf.extend_from_slice(&buf[..n]);
+            n
+        };
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// A reader that yields at most `left` more bytes from `src`.
+pub struct Bounded<'a, R: ?Sized> {
+    src: &'a mut R,
+    left: u64,
+}
+
+impl<R: Read + ?Sized> Read for Bounded<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let max = buf
+            .len()
+            .min(usize::try_from(self.left).unwrap_or(usize::MAX));
+        let n = self.src.read(&mut buf[..max])?;
+        self.left -= n as u64;
+        Ok(n)
+    }
+}
+
+/// Unpack a struct named `name` from `src` field by field inside a `debug`
+/// span.
+pub fn unpack_struct<R, T, F>(name: &'static str, src: &mut R, f: F) -> IoResult<T>
+where
+    R: Read + ?Sized,
+    F: FnOnce(&mut FieldReader<'_, R>) -> IoResult<T>,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("unpack", r#struct = name).entered();
+    #[cfg(not(feature = "tracing"))]
+    let _ = name;
+    f(&mut FieldReader::new(src))
+}
+
+/// Wraps a field value so that `(&&MaybeDebug(&v)).as_debug()` uses its
+/// `Debug` impl if it has one and prints a placeholder otherwise.
+pub struct MaybeDebug<'a, T>(pub &'a T);
+
+pub trait ViaDebug<'a> {
+    fn as_debug(&self) -> &'a dyn Debug;
+}
+
+impl<'a, T: Debug> ViaDebug<'a> for &MaybeDebug<'a, T> {
+    fn as_debug(&self) -> &'a dyn Debug {
+        self.0
+    }
+}
+
+pub trait ViaOpaque<'a> {
+    fn as_debug(&self) -> &'a dyn Debug;
+}
+
+impl<'a, T> ViaOpaque<'a> for MaybeDebug<'a, T> {
+    fn as_debug(&self) -> &'a dyn Debug {
+        &"<no Debug impl>"
+    }
+}
@@ -0,0 +1,70 @@
+use std::io::{Read, Result as IoResult, Seek, SeekFrom};
+
+use byteorder::ByteOrder;
+
+use crate::{LengthPrefix, UnpackFrom};
+
+/// Search backwards from the end of `src` for the last occurrence of `magic`
+/// that starts within the final `window` bytes, returning its offset.
+///
+/// Only the final `window` bytes are read, so the search is bounded even for
+/// very large sources.
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder_pack::find_trailing_magic;
+///
+/// let mut cursor = Cursor::new(b"PK..data..PK\x05\x06\x00\x00".to_vec());
+///
+/// assert_eq!(find_trailing_magic(&mut cursor, b"PK\x05\x06", 64).unwrap(), Some(10));
+/// assert_eq!(find_trailing_magic(&mut cursor, b"PK\x05\x06", 4).unwrap(), None);
+/// ```
+pub fn find_trailing_magic<R: Read + Seek + ?Sized>(
+    src: &mut R,
+    magic: &[u8],
+    window: u64,
+) -> IoResult<Option<u64>> {
+    let end = src.seek(SeekFrom::End(0))?;
+    let start = end.saturating_sub(window);
+    src.seek(SeekFrom::Start(start))?;
+    let mut buf = vec![0; (end - start).to_usize()?];
+    src.read_exact(&mut buf)?;
+    if magic.is_empty() || magic.len() > buf.len() {
+        return Ok(None);
+    }
+    Ok(buf
+        .windows(magic.len())
+        .rposition(|w| w == magic)
+        .map(|i| start + i as u64))
+}
+
+/// Find the last occurrence of `magic` within the final `window` bytes of
+/// `src` and unpack a `T` starting at it, returning its offset and value.
+///
+/// This is the lookup used for ZIP end-of-central-directory records and
+/// firmware image trailers. The magic is part of the unpacked value.
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use byteorder::LittleEndian;
+/// use byteorder_pack::unpack_trailer;
+///
+/// let mut cursor = Cursor::new(b"....PK\x05\x06\x02\x00".to_vec());
+///
+/// let (offset, (magic, entries)) =
+///     unpack_trailer::<LittleEndian, ([u8; 4], u16), _>(&mut cursor, b"PK\x05\x06", 22).unwrap().unwrap();
+///
+/// assert_eq!((offset, magic, entries), (4, *b"PK\x05\x06", 2));
+/// ```
+pub fn unpack_trailer<E: ByteOrder, T: UnpackFrom, R: Read + Seek + ?Sized>(
+    src: &mut R,
+    magic: &[u8],
+    window: u64,
+) -> IoResult<Option<(u64, T)>> {
+    let Some(offset) = find_trailing_magic(src, magic, window)? else {
+        return Ok(None);
+    };
+    src.seek(SeekFrom::Start(offset))?;
+    let value = T::unpack_from::<E, _>(src)?;
+    Ok(Some((offset, value)))
+}
@@ -1,6 +1,25 @@
-use std::io::{Read, Result as IoResult};
+use core::mem::size_of;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU8, Saturating, Wrapping,
+};
+use core::ops::{Range, RangeInclusive};
 
-use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt};
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, vec::Vec};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::io::{Error, ErrorKind, Read, Result as IoResult};
+
+/// Size of the stack buffer used when unpacking primitives in bulk.
+#[cfg(not(target_pointer_width = "16"))]
+pub(crate) const CHUNK_SIZE: usize = 1024;
+/// Size of the stack buffer used when unpacking primitives in bulk, kept small
+/// for 16-bit microcontrollers with only a few KiB of RAM.
+#[cfg(target_pointer_width = "16")]
+pub(crate) const CHUNK_SIZE: usize = 64;
 
 /// Read a value from a [`Read`].
 pub trait UnpackFrom: Sized {
@@ -58,6 +77,34 @@ pub trait UnpackFrom: Sized {
     }
 
     /// Unpack multiple values from `src`.
+    ///
+    /// Like all unpacking, this reads through [`Read::read_exact`], so reads
+    /// failing with `ErrorKind::Interrupted` are retried and short reads are
+    /// continued until enough bytes arrive.
+    /// # Example
+    /// ```rust
+    /// use std::io::{self, ErrorKind, Read};
+    /// use byteorder::BigEndian;
+    /// use byteorder_pack::UnpackFrom;
+    ///
+    /// /// Yields one byte per call and is interrupted every other call.
+    /// struct Flaky<'a>(&'a [u8], bool);
+    ///
+    /// impl Read for Flaky<'_> {
+    ///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    ///         self.1 = !self.1;
+    ///         if self.1 {
+    ///             return Err(ErrorKind::Interrupted.into());
+    ///         }
+    ///         (&mut self.0).take(1).read(buf)
+    ///     }
+    /// }
+    ///
+    /// let mut values = [0u16; 3];
+    /// let mut src = Flaky(&[0, 1, 0, 2, 0, 3], false);
+    /// u16::unpack_multiple_into::<BigEndian, _>(&mut src, &mut values).unwrap();
+    /// assert_eq!(values, [1, 2, 3]);
+    /// ```
     fn unpack_multiple_into<E: ByteOrder, R: Read + ?Sized>(
         src: &mut R,
         dst: &mut [Self],
@@ -67,6 +114,38 @@ pub trait UnpackFrom: Sized {
         }
         Ok(())
     }
+
+    /// Unpack `count` values from `src` into a new `Vec`, as packed from a
+    /// slice by [`PackTo::pack_multiple_to`](crate::PackTo::pack_multiple_to).
+    ///
+    /// This is how `Vec`s and other collections behind a length prefix are
+    /// unpacked. Types that override `unpack_multiple_into`, whether for
+    /// speed or because their elements depend on each other like [`Delta`]'s,
+    /// override this too, typically with a chunked call to
+    /// `unpack_multiple_into`.
+    ///
+    /// [`Delta`]: crate::Delta
+    /// # Example
+    /// ```rust
+    /// use byteorder::BigEndian;
+    /// use byteorder_pack::UnpackFrom;
+    ///
+    /// let mut src = &[0, 1, 0, 2, 0, 3][..];
+    /// let values = u16::unpack_multiple_vec::<BigEndian, _>(&mut src, 3).unwrap();
+    /// assert_eq!(values, [1, 2, 3]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn unpack_multiple_vec<E: ByteOrder, R: Read + ?Sized>(
+        src: &mut R,
+        count: usize,
+    ) -> IoResult<Vec<Self>> {
+        let mut items = Vec::with_capacity(crate::len::capped_capacity::<Self>(count));
+        crate::len::unpack_each::<E, Self, _>(src, count, |item| {
+            items.push(item);
+            Ok(())
+        })?;
+        Ok(items)
+    }
 }
 
 impl<T: UnpackFrom + Default + Copy, const N: usize> UnpackFrom for [T; N] {
@@ -117,14 +196,30 @@ macro_rules! impl_primitive {
         $(
             impl UnpackFrom for $ty {
                 fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
-                    src.$name::<E>()
+                    let mut buf = [0; size_of::<$ty>()];
+                    src.read_exact(&mut buf)?;
+                    Ok(E::$name(&buf))
                 }
 
                 fn unpack_multiple_into<E: ByteOrder, R: Read + ?Sized>(
                     src: &mut R,
                     dst: &mut [Self],
                 ) -> IoResult<()> {
-                    src.$name2::<E>(dst)
+                    let mut buf = [0; CHUNK_SIZE];
+                    for chunk in dst.chunks_mut(CHUNK_SIZE / size_of::<$ty>()) {
+                        let bytes = &mut buf[..chunk.len() * size_of::<$ty>()];
+                        src.read_exact(bytes)?;
+                        E::$name2(bytes, chunk);
+                    }
+                    Ok(())
+                }
+
+                #[cfg(feature = "alloc")]
+                fn unpack_multiple_vec<E: ByteOrder, R: Read + ?Sized>(
+                    src: &mut R,
+                    count: usize,
+                ) -> IoResult<Vec<Self>> {
+                    crate::len::unpack_chunked::<E, _, _>(src, count)
                 }
             }
         )+
@@ -139,7 +234,9 @@ impl_primitive!(
 
 impl UnpackFrom for u8 {
     fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
-        src.read_u8()
+        let mut buf = [0];
+        src.read_exact(&mut buf)?;
+        Ok(buf[0])
     }
 
     fn unpack_multiple_into<E: ByteOrder, R: Read + ?Sized>(
@@ -148,17 +245,150 @@ impl UnpackFrom for u8 {
     ) -> IoResult<()> {
         src.read_exact(dst)
     }
+
+    #[cfg(feature = "alloc")]
+    fn unpack_multiple_vec<E: ByteOrder, R: Read + ?Sized>(
+        src: &mut R,
+        count: usize,
+    ) -> IoResult<Vec<Self>> {
+        crate::len::unpack_chunked::<E, _, _>(src, count)
+    }
 }
 
 impl UnpackFrom for i8 {
     fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
-        src.read_i8()
+        Ok(u8::unpack_from::<E, _>(src)? as i8)
     }
 
     fn unpack_multiple_into<E: ByteOrder, R: Read + ?Sized>(
         src: &mut R,
         dst: &mut [Self],
     ) -> IoResult<()> {
-        src.read_i8_into(dst)
+        let mut buf = [0; CHUNK_SIZE];
+        for chunk in dst.chunks_mut(CHUNK_SIZE) {
+            let bytes = &mut buf[..chunk.len()];
+            src.read_exact(bytes)?;
+            for (d, b) in chunk.iter_mut().zip(bytes.iter()) {
+                *d = *b as i8;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn unpack_multiple_vec<E: ByteOrder, R: Read + ?Sized>(
+        src: &mut R,
+        count: usize,
+    ) -> IoResult<Vec<Self>> {
+        crate::len::unpack_chunked::<E, _, _>(src, count)
+    }
+}
+
+/// Unpacks a single byte, failing with [`ErrorKind::InvalidData`] unless it is
+/// 0 or 1.
+impl UnpackFrom for bool {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        match u8::unpack_from::<E, _>(src)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::new(ErrorKind::InvalidData, "bool is not 0 or 1")),
+        }
+    }
+}
+
+/// Unpacks a `u32` code point, failing with [`ErrorKind::InvalidData`] unless
+/// it is a Unicode scalar value.
+impl UnpackFrom for char {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        char::from_u32(u32::unpack_from::<E, _>(src)?)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "char is not a Unicode scalar value"))
+    }
+}
+
+macro_rules! impl_nonzero {
+    ($($ty:ident => $int:ty),+) => {
+        $(
+            /// Fails with [`ErrorKind::InvalidData`] if the value is zero.
+            impl UnpackFrom for $ty {
+                fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+                    $ty::new(<$int>::unpack_from::<E, _>(src)?)
+                        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "non-zero value is zero"))
+                }
+            }
+
+            /// Unpacks zero as `None`.
+            impl UnpackFrom for Option<$ty> {
+                fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+                    Ok($ty::new(<$int>::unpack_from::<E, _>(src)?))
+                }
+            }
+        )+
+    };
+}
+
+impl_nonzero!(
+    NonZeroU8 => u8, NonZeroU16 => u16, NonZeroU32 => u32, NonZeroU64 => u64,
+    NonZeroU128 => u128, NonZeroI8 => i8, NonZeroI16 => i16, NonZeroI32 => i32,
+    NonZeroI64 => i64, NonZeroI128 => i128
+);
+
+impl<T: UnpackFrom> UnpackFrom for Wrapping<T> {
+    #[inline]
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        T::unpack_from::<E, _>(src).map(Wrapping)
+    }
+}
+
+impl<T: UnpackFrom> UnpackFrom for Saturating<T> {
+    #[inline]
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        T::unpack_from::<E, _>(src).map(Saturating)
+    }
+}
+
+impl<T: UnpackFrom> UnpackFrom for Range<T> {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let (start, end) = <(T, T)>::unpack_from::<E, _>(src)?;
+        Ok(start..end)
+    }
+}
+
+impl<T: UnpackFrom> UnpackFrom for RangeInclusive<T> {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let (start, end) = <(T, T)>::unpack_from::<E, _>(src)?;
+        Ok(start..=end)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: UnpackFrom> UnpackFrom for Box<T> {
+    #[inline]
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        T::unpack_from::<E, _>(src).map(Box::new)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: UnpackFrom> UnpackFrom for Rc<T> {
+    #[inline]
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        T::unpack_from::<E, _>(src).map(Rc::new)
+    }
+}
+
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+impl<T: UnpackFrom> UnpackFrom for Arc<T> {
+    #[inline]
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        T::unpack_from::<E, _>(src).map(Arc::new)
+    }
+}
+
+/// Unpacks an owned value.
+#[cfg(feature = "alloc")]
+impl<T: UnpackFrom + Clone> UnpackFrom for Cow<'_, T> {
+    #[inline]
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        T::unpack_from::<E, _>(src).map(Cow::Owned)
     }
 }
@@ -0,0 +1,73 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use byteorder::ByteOrder;
+
+use crate::io::{Result as IoResult, Write};
+use crate::PackTo;
+
+/// A dynamically typed packed value, as produced and consumed by
+/// [`Format`](crate::Format) and [`Schema`](crate::Schema).
+/// # Example
+/// ```rust
+/// use byteorder_pack::{PackTo, Value};
+///
+/// let mut buf = vec![];
+/// Value::U16(0x0102).pack_to_le(&mut buf).unwrap();
+/// Value::Bytes(b"ab".to_vec()).pack_to_le(&mut buf).unwrap();
+///
+/// assert_eq!(buf, [0x02, 0x01, b'a', b'b']);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    U8(u8),
+    I8(i8),
+    U16(u16),
+    I16(i16),
+    U32(u32),
+    I32(i32),
+    U64(u64),
+    I64(i64),
+    U128(u128),
+    I128(i128),
+    F32(f32),
+    F64(f64),
+    /// Raw bytes, packed as-is.
+    Bytes(Vec<u8>),
+    /// Elements packed one after another.
+    Array(Vec<Value>),
+    /// Named fields packed in order.
+    Struct(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Look up a field of a [`Value::Struct`] by name.
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Struct(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+impl PackTo for Value {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        match self {
+            Value::U8(v) => v.pack_to::<E, _>(dst),
+            Value::I8(v) => v.pack_to::<E, _>(dst),
+            Value::U16(v) => v.pack_to::<E, _>(dst),
+            Value::I16(v) => v.pack_to::<E, _>(dst),
+            Value::U32(v) => v.pack_to::<E, _>(dst),
+            Value::I32(v) => v.pack_to::<E, _>(dst),
+            Value::U64(v) => v.pack_to::<E, _>(dst),
+            Value::I64(v) => v.pack_to::<E, _>(dst),
+            Value::U128(v) => v.pack_to::<E, _>(dst),
+            Value::I128(v) => v.pack_to::<E, _>(dst),
+            Value::F32(v) => v.pack_to::<E, _>(dst),
+            Value::F64(v) => v.pack_to::<E, _>(dst),
+            Value::Bytes(v) => dst.write_all(v),
+            Value::Array(v) => v.iter().try_for_each(|v| v.pack_to::<E, _>(dst)),
+            Value::Struct(v) => v.iter().try_for_each(|(_, v)| v.pack_to::<E, _>(dst)),
+        }
+    }
+}
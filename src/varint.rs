@@ -0,0 +1,437 @@
+use byteorder::ByteOrder;
+
+use crate::io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write};
+use crate::{LengthOverflow, LengthPrefix, PackTo, UnpackFrom};
+
+fn too_long() -> IoError {
+    IoError::new(ErrorKind::InvalidData, "varint is too long")
+}
+
+fn overflow() -> IoError {
+    IoError::new(ErrorKind::InvalidData, "varint overflows its type")
+}
+
+fn read_byte<R: Read + ?Sized>(src: &mut R) -> IoResult<u8> {
+    let mut byte = [0];
+    src.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+/// Write `value` as unsigned LEB128: seven bits per byte, least significant
+/// group first, with the high bit set on all but the last byte.
+pub(crate) fn write_uleb<W: Write + ?Sized>(dst: &mut W, mut value: u64) -> IoResult<()> {
+    let mut buf = [0u8; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    dst.write_all(&buf[..len])
+}
+
+/// Read an unsigned LEB128 value of at most `bits` bits, in at most
+/// `max_len` bytes.
+pub(crate) fn read_uleb<R: Read + ?Sized>(src: &mut R, bits: u32, max_len: u32) -> IoResult<u64> {
+    let mut value = 0u64;
+    for i in 0..max_len {
+        let byte = read_byte(src)?;
+        let shift = 7 * i;
+        let payload = u64::from(byte & 0x7f);
+        if shift >= bits || (bits - shift < 7 && payload >> (bits - shift) != 0) {
+            return Err(overflow());
+        }
+        value |= payload << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(too_long())
+}
+
+/// A `u32` packed as unsigned LEB128 in 1 to 5 bytes, as used by WebAssembly,
+/// DWARF and Protocol Buffers.
+///
+/// Byte order does not apply. Unpacking fails with [`ErrorKind::InvalidData`]
+/// on a value that does not fit in a `u32` or takes more than 5 bytes. It can
+/// also be used as a [`LengthPrefix`].
+/// # Example
+/// ```rust
+/// use byteorder_pack::{PackTo, UnpackFrom, VarU32};
+///
+/// let mut buf = vec![];
+/// VarU32(624485).pack_to_le(&mut buf).unwrap();
+/// assert_eq!(buf, [0xe5, 0x8e, 0x26]);
+///
+/// assert_eq!(VarU32::unpack_from_le(&mut &buf[..]).unwrap(), VarU32(624485));
+/// assert!(VarU32::unpack_from_le(&mut &[0xff, 0xff, 0xff, 0xff, 0x1f][..]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct VarU32(pub u32);
+
+/// A `u64` packed as unsigned LEB128 in 1 to 10 bytes.
+///
+/// Byte order does not apply. Unpacking fails with [`ErrorKind::InvalidData`]
+/// on a value that does not fit in a `u64` or takes more than 10 bytes. It can
+/// also be used as a [`LengthPrefix`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct VarU64(pub u64);
+
+/// An `i64` packed as signed LEB128 in 1 to 10 bytes, with the sign in bit 6
+/// of the last byte, as used by WebAssembly and DWARF.
+///
+/// Byte order does not apply. Unpacking fails with [`ErrorKind::InvalidData`]
+/// on a value that does not fit in an `i64` or takes more than 10 bytes.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{PackTo, UnpackFrom, VarI64};
+///
+/// let mut buf = vec![];
+/// VarI64(-123456).pack_to_le(&mut buf).unwrap();
+/// assert_eq!(buf, [0xc0, 0xbb, 0x78]);
+///
+/// assert_eq!(VarI64::unpack_from_le(&mut &buf[..]).unwrap(), VarI64(-123456));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct VarI64(pub i64);
+
+macro_rules! impl_var_unsigned {
+    ($($name:ident($int:ty, $max_len:expr)),+) => {
+        $(
+            impl From<$int> for $name {
+                fn from(value: $int) -> Self {
+                    Self(value)
+                }
+            }
+
+            impl From<$name> for $int {
+                fn from(value: $name) -> Self {
+                    value.0
+                }
+            }
+
+            impl PackTo for $name {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    write_uleb(dst, u64::from(self.0))
+                }
+            }
+
+            impl UnpackFrom for $name {
+                fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+                    let value = read_uleb(src, <$int>::BITS, $max_len)?;
+                    Ok(Self(value as $int))
+                }
+            }
+
+            impl LengthPrefix for $name {
+                fn to_usize(self) -> Result<usize, LengthOverflow> {
+                    self.0.to_usize()
+                }
+
+                fn from_usize(len: usize) -> Result<Self, LengthOverflow> {
+                    <$int>::from_usize(len).map(Self)
+                }
+            }
+        )+
+    };
+}
+
+impl_var_unsigned!(VarU32(u32, 5), VarU64(u64, 10));
+
+impl From<i64> for VarI64 {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<VarI64> for i64 {
+    fn from(value: VarI64) -> Self {
+        value.0
+    }
+}
+
+impl PackTo for VarI64 {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        let mut buf = [0u8; 10];
+        let mut len = 0;
+        let mut value = self.0;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            // Arithmetic shift, so negative values end in all ones.
+            value >>= 7;
+            let sign = byte & 0x40 != 0;
+            if (value == 0 && !sign) || (value == -1 && sign) {
+                buf[len] = byte;
+                len += 1;
+                break;
+            }
+            buf[len] = byte | 0x80;
+            len += 1;
+        }
+        dst.write_all(&buf[..len])
+    }
+}
+
+impl UnpackFrom for VarI64 {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        let mut value = 0i64;
+        for i in 0..10 {
+            let byte = read_byte(src)?;
+            let shift = 7 * i;
+            let payload = i64::from(byte & 0x7f);
+            // The tenth byte holds only the sign bit, which must be extended
+            // through the rest of its payload.
+            if shift == 63 && payload != 0 && payload != 0x7f {
+                return Err(overflow());
+            }
+            value |= payload << shift;
+            if byte & 0x80 == 0 {
+                if shift < 57 && byte & 0x40 != 0 {
+                    value |= -1 << (shift + 7);
+                }
+                return Ok(Self(value));
+            }
+        }
+        Err(too_long())
+    }
+}
+
+/// A signed integer mapped to an unsigned one by zigzag encoding, so that
+/// values of small magnitude stay small, then packed as unsigned LEB128.
+///
+/// This is the encoding of the `sint32` and `sint64` types of Protocol
+/// Buffers: 0, -1, 1, -2... are packed as 0, 1, 2, 3... Byte order does not
+/// apply, and unpacking fails like [`VarU32`] and [`VarU64`] do.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{PackTo, UnpackFrom, ZigZag};
+///
+/// let mut buf = vec![];
+/// ZigZag(-1i32).pack_to_le(&mut buf).unwrap();
+/// ZigZag(-65i64).pack_to_le(&mut buf).unwrap();
+/// assert_eq!(buf, [0x01, 0x81, 0x01]);
+///
+/// let mut src = &buf[..];
+/// assert_eq!(ZigZag::<i32>::unpack_from_le(&mut src).unwrap(), ZigZag(-1));
+/// assert_eq!(ZigZag::<i64>::unpack_from_le(&mut src).unwrap(), ZigZag(-65));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct ZigZag<T>(pub T);
+
+impl<T> ZigZag<T> {
+    /// Unwrap the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for ZigZag<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+macro_rules! impl_zigzag {
+    ($($int:ty => $uint:ty),+) => {
+        $(
+            impl PackTo for ZigZag<$int> {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    let zigzag = ((self.0 << 1) ^ (self.0 >> (<$int>::BITS - 1))) as $uint;
+                    write_uleb(dst, u64::from(zigzag))
+                }
+            }
+
+            impl UnpackFrom for ZigZag<$int> {
+                fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+                    let bits = <$int>::BITS;
+                    let zigzag = read_uleb(src, bits, bits.div_ceil(7))? as $uint;
+                    Ok(Self(((zigzag >> 1) as $int) ^ -((zigzag & 1) as $int)))
+                }
+            }
+        )+
+    };
+}
+
+impl_zigzag!(i8 => u8, i16 => u16, i32 => u32, i64 => u64);
+
+/// An integer packed like .NET's `BinaryWriter.Write7BitEncodedInt` and
+/// `Write7BitEncodedInt64`, for `u32`/`i32` and `u64`/`i64` respectively.
+///
+/// This is unsigned LEB128 of the value's bits, so negative values take the
+/// full 5 or 10 bytes. Byte order does not apply, and unpacking fails with
+/// [`ErrorKind::InvalidData`] where .NET's `Read7BitEncodedInt` would. As
+/// a [`LengthPrefix`], `SevenBitEncoded<u32>` matches the length of strings
+/// written by `BinaryWriter.Write(string)`.
+/// # Example
+/// ```rust
+/// use byteorder_pack::{LengthPrefixed, PackTo, SevenBitEncoded, UnpackFrom};
+///
+/// let mut buf = vec![];
+/// SevenBitEncoded(300u32).pack_to_le(&mut buf).unwrap();
+/// SevenBitEncoded(-1i32).pack_to_le(&mut buf).unwrap();
+/// assert_eq!(buf, [0xac, 0x02, 0xff, 0xff, 0xff, 0xff, 0x0f]);
+///
+/// let mut src = &buf[..];
+/// assert_eq!(SevenBitEncoded::<u32>::unpack_from_le(&mut src).unwrap().0, 300);
+/// assert_eq!(SevenBitEncoded::<i32>::unpack_from_le(&mut src).unwrap().0, -1);
+///
+/// type DotNetString = LengthPrefixed<SevenBitEncoded<u32>, String>;
+/// let mut buf = vec![];
+/// DotNetString::new("hi".to_string()).pack_to_le(&mut buf).unwrap();
+/// assert_eq!(buf, [2, b'h', b'i']);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct SevenBitEncoded<T>(pub T);
+
+impl<T> SevenBitEncoded<T> {
+    /// Unwrap the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for SevenBitEncoded<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+macro_rules! impl_seven_bit {
+    ($($int:ty => $uint:ty),+) => {
+        $(
+            impl PackTo for SevenBitEncoded<$int> {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    write_uleb(dst, u64::from(self.0 as $uint))
+                }
+            }
+
+            impl UnpackFrom for SevenBitEncoded<$int> {
+                fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+                    let bits = <$uint>::BITS;
+                    let value = read_uleb(src, bits, bits.div_ceil(7))?;
+                    Ok(Self(value as $uint as $int))
+                }
+            }
+        )+
+    };
+}
+
+impl_seven_bit!(u32 => u32, i32 => u32, u64 => u64, i64 => u64);
+
+impl LengthPrefix for SevenBitEncoded<u32> {
+    fn to_usize(self) -> Result<usize, LengthOverflow> {
+        self.0.to_usize()
+    }
+
+    fn from_usize(len: usize) -> Result<Self, LengthOverflow> {
+        u32::from_usize(len).map(Self)
+    }
+}
+
+impl LengthPrefix for SevenBitEncoded<u64> {
+    fn to_usize(self) -> Result<usize, LengthOverflow> {
+        self.0.to_usize()
+    }
+
+    fn from_usize(len: usize) -> Result<Self, LengthOverflow> {
+        u64::from_usize(len).map(Self)
+    }
+}
+
+/// A `u32` packed as an MQTT variable byte integer, the unsigned LEB128
+/// encoding in 1 to 4 bytes used for the remaining length of MQTT packets.
+///
+/// Byte order does not apply. Packing fails with [`ErrorKind::InvalidInput`]
+/// on values greater than [`MAX`](Self::MAX), and unpacking fails with
+/// [`ErrorKind::InvalidData`] on more than 4 bytes, which MQTT treats as a
+/// malformed packet. It can also be used as a [`LengthPrefix`].
+/// # Example
+/// ```rust
+/// use byteorder_pack::{PackTo, UnpackFrom, VariableByteInteger};
+///
+/// let mut buf = vec![];
+/// VariableByteInteger(321).pack_to_be(&mut buf).unwrap();
+/// assert_eq!(buf, [0xc1, 0x02]);
+/// assert_eq!(
+///     VariableByteInteger::unpack_from_be(&mut &buf[..]).unwrap(),
+///     VariableByteInteger(321),
+/// );
+///
+/// assert!(VariableByteInteger(268_435_456).pack_to_be(&mut vec![]).is_err());
+/// assert!(VariableByteInteger::unpack_from_be(&mut &[0xff, 0xff, 0xff, 0xff, 0x01][..]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    all(feature = "arbitrary", feature = "std"),
+    derive(arbitrary::Arbitrary)
+)]
+pub struct VariableByteInteger(pub u32);
+
+impl VariableByteInteger {
+    /// The largest value that fits in 4 bytes, 268,435,455.
+    pub const MAX: Self = Self((1 << 28) - 1);
+}
+
+impl From<VariableByteInteger> for u32 {
+    fn from(value: VariableByteInteger) -> Self {
+        value.0
+    }
+}
+
+impl PackTo for VariableByteInteger {
+    fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+        if self.0 > Self::MAX.0 {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "value is too large for a variable byte integer",
+            ));
+        }
+        write_uleb(dst, u64::from(self.0))
+    }
+}
+
+impl UnpackFrom for VariableByteInteger {
+    fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+        read_uleb(src, 28, 4).map(|value| Self(value as u32))
+    }
+}
+
+impl LengthPrefix for VariableByteInteger {
+    fn to_usize(self) -> Result<usize, LengthOverflow> {
+        self.0.to_usize()
+    }
+
+    fn from_usize(len: usize) -> Result<Self, LengthOverflow> {
+        u32::from_usize(len)
+            .ok()
+            .filter(|&len| len <= Self::MAX.0)
+            .map(Self)
+            .ok_or(LengthOverflow::new(len as u128))
+    }
+}
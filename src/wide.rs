@@ -0,0 +1,46 @@
+use byteorder::ByteOrder;
+use ethnum::{I256, U256};
+
+use crate::io::{Read, Result as IoResult, Write};
+use crate::{FixedPackSize, PackTo, UnpackFrom};
+
+/// Whether `E` is little-endian, to order the halves of a wide integer.
+fn is_little_endian<E: ByteOrder>() -> bool {
+    E::read_u16(&[1, 0]) == 1
+}
+
+macro_rules! impl_wide {
+    ($($ty:ident($half:ty)),+) => {
+        $(
+            /// Packs all 32 bytes in the requested byte order, like the
+            /// primitive integers.
+            impl PackTo for $ty {
+                fn pack_to<E: ByteOrder, W: Write + ?Sized>(&self, dst: &mut W) -> IoResult<()> {
+                    let (hi, lo) = self.into_words();
+                    if is_little_endian::<E>() {
+                        (lo, hi).pack_to::<E, _>(dst)
+                    } else {
+                        (hi, lo).pack_to::<E, _>(dst)
+                    }
+                }
+            }
+
+            impl UnpackFrom for $ty {
+                fn unpack_from<E: ByteOrder, R: Read + ?Sized>(src: &mut R) -> IoResult<Self> {
+                    let (a, b) = <($half, $half)>::unpack_from::<E, _>(src)?;
+                    Ok(if is_little_endian::<E>() {
+                        $ty::from_words(b, a)
+                    } else {
+                        $ty::from_words(a, b)
+                    })
+                }
+            }
+
+            impl FixedPackSize for $ty {
+                const PACKED_SIZE: usize = 32;
+            }
+        )+
+    };
+}
+
+impl_wide!(U256(u128), I256(i128));
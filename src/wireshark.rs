@@ -0,0 +1,158 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write as _;
+
+use byteorder::ByteOrder;
+
+use crate::schema::{identifier, is_little_endian};
+use crate::Schema;
+
+const LUA_KEYWORDS: &[&str] = &[
+    "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in",
+    "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+/// Generate a Wireshark Lua dissector for messages laid out as `schema` in
+/// byte order `E`, showing one tree item per primitive field.
+///
+/// The protocol is named after `name` and its fields can be filtered on as
+/// `<name>.<field>`, with a numeric suffix on fields whose names would
+/// otherwise clash once cleaned up. 128-bit integers are shown as raw bytes.
+/// The script does not register the dissector for any port; a commented-out
+/// line at the end shows how.
+/// # Example
+/// ```rust
+/// use byteorder_pack::byteorder::LittleEndian;
+/// use byteorder_pack::{wireshark_dissector, Schema};
+///
+/// let header = Schema::Struct(vec![
+///     ("magic".into(), Schema::Bytes(4)),
+///     ("counts".into(), Schema::Array(Box::new(Schema::U16), 2)),
+/// ]);
+/// let lua = wireshark_dissector::<LittleEndian>("header", &header);
+/// assert!(lua.contains(r#"magic = ProtoField.bytes("header.magic", "magic"),"#));
+/// assert!(lua.contains(r#"counts_1 = ProtoField.uint16("header.counts_1", "counts[1]", base.DEC),"#));
+/// assert!(lua.contains("subtree:add_le(f.counts_1, buffer(6, 2))"));
+///
+/// let clash = Schema::Struct(vec![("a-b".into(), Schema::U8), ("a\"b".into(), Schema::U8)]);
+/// let lua = wireshark_dissector::<LittleEndian>("clash", &clash);
+/// assert!(lua.contains(r#"a_b = ProtoField.uint8("clash.a_b", "a-b", base.DEC),"#));
+/// assert!(lua.contains(r#"a_b_2 = ProtoField.uint8("clash.a_b_2", "a\"b", base.DEC),"#));
+/// ```
+pub fn wireshark_dissector<E: ByteOrder>(name: &str, schema: &Schema) -> String {
+    let proto = identifier(name);
+    let add = if is_little_endian::<E>() {
+        "add_le"
+    } else {
+        "add"
+    };
+    let size = schema.size();
+
+    let mut fields: Vec<(String, String, usize, Schema)> = Vec::new();
+    schema.for_each_field(|path, offset, field| {
+        let mut id = if path.is_empty() {
+            String::from("value")
+        } else {
+            identifier(path.replace("].", ".").trim_end_matches(']'))
+        };
+        if LUA_KEYWORDS.contains(&id.as_str()) {
+            id.push('_');
+        }
+        let mut unique = id.clone();
+        let mut suffix = 2;
+        while fields.iter().any(|(other, ..)| *other == unique) {
+            unique = format!("{}_{}", id, suffix);
+            suffix += 1;
+        }
+        fields.push((unique, String::from(path), offset, field.clone()));
+    });
+
+    let mut out = format!(
+        "-- Wireshark dissector for {0}.\n\
+         local proto = Proto(\"{1}\", \"{0}\")\n\n\
+         local f = {{\n",
+        lua_escape(name),
+        proto
+    );
+    for (id, path, _, field) in &fields {
+        let (kind, base) = match field {
+            Schema::U8 => ("uint8", true),
+            Schema::I8 => ("int8", true),
+            Schema::U16 => ("uint16", true),
+            Schema::I16 => ("int16", true),
+            Schema::U32 => ("uint32", true),
+            Schema::I32 => ("int32", true),
+            Schema::U64 => ("uint64", true),
+            Schema::I64 => ("int64", true),
+            Schema::F32 => ("float", false),
+            Schema::F64 => ("double", false),
+            _ => ("bytes", false),
+        };
+        let label = if path.is_empty() { "value" } else { path };
+        let label = lua_escape(label);
+        let _ = write!(
+            out,
+            "    {} = ProtoField.{}(\"{}.{}\", \"{}\"",
+            id, kind, proto, id, label
+        );
+        if base {
+            out.push_str(", base.DEC");
+        }
+        out.push_str("),\n");
+    }
+    let _ = write!(
+        out,
+        "}}\n\
+         proto.fields = f\n\n\
+         function proto.dissector(buffer, pinfo, tree)\n\
+        \x20   if buffer:len() < {0} then\n\
+        \x20       return 0\n\
+        \x20   end\n\
+        \x20   pinfo.cols.protocol = proto.name\n\
+        \x20   local subtree = tree:add(proto, buffer(0, {0}))\n",
+        size
+    );
+    for (id, _, offset, field) in &fields {
+        let add = match field {
+            Schema::U8 | Schema::I8 | Schema::Bytes(_) | Schema::U128 | Schema::I128 => "add",
+            _ => add,
+        };
+        let _ = writeln!(
+            out,
+            "    subtree:{}(f.{}, buffer({}, {}))",
+            add,
+            id,
+            offset,
+            field.size()
+        );
+    }
+    let _ = write!(
+        out,
+        "    return {}\n\
+         end\n\n\
+         -- Register the dissector for a port, for example:\n\
+         -- DissectorTable.get(\"udp.port\"):add(9000, proto)\n",
+        size
+    );
+    out
+}
+
+/// `text` escaped for use inside a double-quoted Lua string.
+fn lua_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            c if c.is_ascii_control() => {
+                let _ = write!(out, "\\{:03}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}